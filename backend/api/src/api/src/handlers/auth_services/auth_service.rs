@@ -1,8 +1,12 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, StatusResponse};
 use crate::paths::AUTH_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
-use models::User;
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
+use models::{Session, User};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -20,6 +24,8 @@ pub struct AuthResponse {
     pub status: StatusResponse,
     #[schema(value_type=User)]
     pub user: Option<User>,
+    #[schema(value_type=Session)]
+    pub session: Option<Session>,
 }
 
 #[axum::debug_handler]
@@ -28,7 +34,7 @@ pub struct AuthResponse {
     path = "/user/auth",
     request_body = AuthRequest,
     summary = "Аутентификация",
-    description = "Аутентификация пользователя по логину и паролю",
+    description = "Аутентификация пользователя по логину и паролю. Возвращает access- и refresh-токены",
     responses(
         (status = StatusCode::OK, description = "Пользователь успешно авторизирован", body = AuthResponse),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
@@ -36,18 +42,15 @@ pub struct AuthResponse {
     tags = ["auth"]
 )]
 pub async fn handle_auth(
+    State(services): State<BusinessServices>,
     ExtractJson(payload): ExtractJson<AuthRequest>,
 ) -> Result<Json<AuthResponse>, StatusCode> {
     let mut status = StatusResponse::new();
     log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
 
-    let service = match BUSINESS_SERVICES::get("auther").await {
-        Some(BLServices::AuthService(s)) => s,
-        _ => {
-            log::warn!("Can't get AuthService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.auther;
+
+    let session_service = services.session_manager;
 
     let user = match service.auth(&payload.email, &payload.pswd).await {
         Ok(user) => user,
@@ -55,7 +58,11 @@ pub async fn handle_auth(
             ServiceError::InvalidDataError(e) => {
                 status.code = ResponseStatusCode::INVALID_AUTH_DATA as isize;
                 status.message = format!("Invalid {e}");
-                let response = AuthResponse { status, user: None };
+                let response = AuthResponse {
+                    status,
+                    user: None,
+                    session: None,
+                };
                 log::warn!("Sended error response {:#?}", response);
 
                 return Ok(Json(response));
@@ -64,11 +71,21 @@ pub async fn handle_auth(
         },
     };
 
+    let session = match session_service.open_session(&user).await {
+        Ok(session) => session,
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
     let response = AuthResponse {
         status,
         user: Some(user),
+        session: Some(session),
     };
-    log::info!("Sended response {:#?}", response);
+    log::info!(
+        "Sended response {:#?} {:#?}",
+        response.status,
+        response.user
+    );
 
     Ok(Json(response))
 }
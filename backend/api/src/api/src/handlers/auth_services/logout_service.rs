@@ -0,0 +1,47 @@
+use super::BusinessServices;
+use super::{ResponseWithoutData, StatusResponse};
+use crate::paths::LOGOUT_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct LogoutRequest {
+    #[schema(example = "3f9c1e0b7a4d4c2e9b1f8a6d5e2c7b0a3f9c1e0b7a4d4c2e9b1f8a6d5e2c7b0a")]
+    pub refresh_token: String,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/user/logout",
+    request_body = LogoutRequest,
+    summary = "Выход",
+    description = "Завершение сессии пользователя: refresh-токен становится недействительным",
+    responses(
+        (status = StatusCode::OK, description = "Сессия успешно завершена", body = ResponseWithoutData),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    tags = ["auth"]
+)]
+pub async fn handle_logout(
+    State(services): State<BusinessServices>,
+    ExtractJson(payload): ExtractJson<LogoutRequest>,
+) -> Result<Json<ResponseWithoutData>, StatusCode> {
+    let status = StatusResponse::new();
+    log::info!("Received request from {}", PATH.as_str());
+
+    let service = services.session_manager;
+
+    let response = match service.close_session(&payload.refresh_token).await {
+        Ok(_) => ResponseWithoutData { status },
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response))
+}
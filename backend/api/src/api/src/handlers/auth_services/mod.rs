@@ -1,6 +1,8 @@
 pub mod auth_service;
+pub mod logout_service;
 pub mod passport_confirm_service;
+pub mod refresh_service;
 pub mod registration_service;
 
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, CurrentUser, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType, ResponseWithoutData, StatusResponse};
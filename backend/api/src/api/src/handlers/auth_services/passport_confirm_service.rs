@@ -1,6 +1,10 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, CurrentUser, ServiceError};
 use crate::paths::PASSPORT_CONF_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
 use models::Document;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -9,8 +13,6 @@ use super::{ResponseStatusCode, ResponseStatusCodeType, ResponseWithoutData, Sta
 
 #[derive(ToSchema, Deserialize, Serialize, Debug)]
 pub struct PassportConfRequest {
-    #[schema(example = "exist@exist.com")]
-    email: String,
     passport: Document,
 }
 
@@ -19,30 +21,34 @@ pub struct PassportConfRequest {
     post,
     path = "/user/passport-confirm",
     summary = "Подтверждениее пасспортных данных",
-    description = "Подтверждениее пасспортных данных пользователя",
+    description = "Подтверждениее пасспортных данных авторизованного пользователя",
     request_body = PassportConfRequest,
     responses(
         (status = StatusCode::OK, description = "Пользователь успешно обновил паспортные данные", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Отсутствует или недействителен токен доступа", body = ResponseWithoutData),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав", body = ResponseWithoutData),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["auth"]
 )]
 pub async fn handle_passport_conf(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
     ExtractJson(payload): ExtractJson<PassportConfRequest>,
 ) -> Result<Json<ResponseWithoutData>, StatusCode> {
     let mut status = StatusResponse::new();
-    println!("Received request from {}: {:?}", PATH.as_str(), payload);
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        PATH.as_str(),
+        user.email,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("auther").await {
-        Some(BLServices::AuthService(s)) => s,
-        _ => {
-            log::warn!("Can't get AuthService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.auther;
 
     let response = match service
-        .passport_confirm(&payload.email, &payload.passport)
+        .passport_confirm(&user.email, &payload.passport)
         .await
     {
         Ok(_) => ResponseWithoutData { status },
@@ -0,0 +1,75 @@
+use super::{BusinessServices, ServiceError};
+use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
+use crate::paths::REFRESH_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
+use models::Session;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RefreshRequest {
+    #[schema(example = "3f9c1e0b7a4d4c2e9b1f8a6d5e2c7b0a3f9c1e0b7a4d4c2e9b1f8a6d5e2c7b0a")]
+    pub refresh_token: String,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RefreshResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    #[schema(value_type=Session)]
+    pub session: Option<Session>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/user/refresh",
+    request_body = RefreshRequest,
+    summary = "Обновление сессии",
+    description = "Выдача новой пары токенов по refresh-токену. Использованный refresh-токен становится недействительным",
+    responses(
+        (status = StatusCode::OK, description = "Сессия успешно обновлена", body = RefreshResponse),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    tags = ["auth"]
+)]
+pub async fn handle_refresh(
+    State(services): State<BusinessServices>,
+    ExtractJson(payload): ExtractJson<RefreshRequest>,
+) -> Result<Json<RefreshResponse>, StatusCode> {
+    let mut status = StatusResponse::new();
+    log::info!("Received request from {}", PATH.as_str());
+
+    let service = services.session_manager;
+
+    let session = match service.refresh_session(&payload.refresh_token).await {
+        Ok(session) => session,
+        Err(e) => match e {
+            ServiceError::InvalidDataError(e) => {
+                status.code =
+                    ResponseStatusCode::from(&e, ResponseStatusCodeType::INVALID_DATA) as isize;
+                status.message = format!("Invalid {e}");
+                let response = RefreshResponse {
+                    status,
+                    session: None,
+                };
+                log::warn!("Sended error response {:#?}", response);
+
+                return Ok(Json(response));
+            }
+            _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
+    };
+
+    let response = RefreshResponse {
+        status,
+        session: Some(session),
+    };
+    log::info!("Sended response with refreshed session");
+
+    Ok(Json(response))
+}
@@ -1,6 +1,10 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use crate::paths::REG_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -36,18 +40,13 @@ pub struct RegRequest {
     tags = ["auth"]
 )]
 pub async fn handle_reg(
+    State(services): State<BusinessServices>,
     ExtractJson(payload): ExtractJson<RegRequest>,
 ) -> Result<Json<ResponseWithoutData>, StatusCode> {
     let mut status = StatusResponse::new();
     log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
 
-    let service = match BUSINESS_SERVICES::get("auther").await {
-        Some(BLServices::AuthService(s)) => s,
-        _ => {
-            log::warn!("Can't get AuthService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.auther;
 
     let response = match service
         .register(
@@ -1,8 +1,8 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
 use crate::paths::CAMERA_GET_BY_CORDS_SERVICE_PATH as PATH;
 use axum::{
-    extract::Json as ExtractJson,
+    extract::{Json as ExtractJson, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
@@ -27,23 +27,21 @@ use super::camera_response::CameraResponse;
             },
             "camera": null
         })),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["camera"]
 )]
 pub async fn handle_get_camera_by_cords(
+    State(services): State<BusinessServices>,
     ExtractJson(payload): ExtractJson<Location>,
 ) -> Result<Response, StatusCode> {
     let mut status = StatusResponse::new();
     log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
 
-    let service = match BUSINESS_SERVICES::get("camera_data_getter").await {
-        Some(BLServices::CameraDataGetService(s)) => s,
-        _ => {
-            log::warn!("Can't get CameraDataGetService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.camera_data_getter;
 
     let camera = match service
         .get_camera_by_location(&Location {
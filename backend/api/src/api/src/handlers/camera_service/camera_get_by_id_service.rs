@@ -1,9 +1,9 @@
 use super::camera_response::CameraResponse;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
 use crate::paths::CAMERA_GET_BY_ID_SERVICE_PATH as PATH;
 use axum::{
-    extract::Path,
+    extract::{Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
@@ -24,11 +24,17 @@ use axum::{
             },
             "camera": null
         })),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["camera"]
 )]
-pub async fn handle_get_camera_by_id(Path(id): Path<i64>) -> Result<Response, StatusCode> {
+pub async fn handle_get_camera_by_id(
+    State(services): State<BusinessServices>,
+    Path(id): Path<i64>,
+) -> Result<Response, StatusCode> {
     let mut status = StatusResponse::new();
     log::info!(
         "Received request from {}: {{ id: {:?} }}",
@@ -36,13 +42,7 @@ pub async fn handle_get_camera_by_id(Path(id): Path<i64>) -> Result<Response, St
         id
     );
 
-    let service = match BUSINESS_SERVICES::get("camera_data_getter").await {
-        Some(BLServices::CameraDataGetService(s)) => s,
-        _ => {
-            log::warn!("Can't get CameraDataGetService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.camera_data_getter;
 
     let camera = match service.get_camera_by_id(id as usize).await {
         Ok(camera) => camera,
@@ -0,0 +1,192 @@
+use super::super::error_response;
+use super::camera_response::CameraResponse;
+use super::BusinessServices;
+use super::StatusResponse;
+use crate::handlers::{CurrentUser, ResponseWithoutData};
+use crate::paths::{CAMERA_GET_BY_ID_SERVICE_PATH, CAMERA_PATH};
+use axum::{
+    extract::{Json as ExtractJson, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::{Camera, Location};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct CameraRequest {
+    #[schema(example = true)]
+    pub is_radar: bool,
+    pub location: Location,
+    /// Дата установки. Без нее при добавлении берется текущая дата
+    #[schema(example = "04.07.2021")]
+    pub install_date: Option<String>,
+    #[schema(example = 60)]
+    pub speed_limit: Option<u16>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct CameraUpdateRequest {
+    #[schema(example = true)]
+    pub is_radar: bool,
+    pub location: Location,
+    /// Дата установки. Без нее остается прежней
+    #[schema(example = "04.07.2021")]
+    pub install_date: Option<String>,
+    #[schema(example = 60)]
+    pub speed_limit: Option<u16>,
+    /// Камера принимает снимки только в эксплуатации
+    #[schema(example = true)]
+    pub is_active: bool,
+}
+
+fn camera_response(code: StatusCode, camera: Camera) -> Result<Response, StatusCode> {
+    let response = CameraResponse {
+        status: StatusResponse::new(),
+        camera: Some(camera),
+    };
+    log::info!("Sended response {:#?}", response);
+    Ok((code, Json(response)).into_response())
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/camera",
+    summary = "Добавление камеры",
+    description = "Добавление камеры в эксплуатацию. id камеры назначается сервером",
+    request_body = CameraRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Камера добавлена", body = CameraResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["camera"]
+)]
+pub async fn handle_add_camera(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<CameraRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        CAMERA_PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.camera_manager;
+
+    match service
+        .add_camera(&Camera {
+            id: 0,
+            is_radar: payload.is_radar,
+            location: payload.location,
+            speed_limit: payload.speed_limit,
+            install_date: payload.install_date,
+            is_active: true,
+        })
+        .await
+    {
+        Ok(camera) => camera_response(StatusCode::CREATED, camera),
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/camera/{id}",
+    summary = "Изменение камеры",
+    description = "Замена расположения, типа, даты установки, ограничения скорости и признака эксплуатации камеры",
+    params(("id" = usize, Path, description = "id камеры")),
+    request_body = CameraUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Камера изменена", body = CameraResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Камера не найдена", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["camera"]
+)]
+pub async fn handle_update_camera(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Path(id): Path<usize>,
+    ExtractJson(payload): ExtractJson<CameraUpdateRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {{ id: {}, {:?} }}",
+        CAMERA_GET_BY_ID_SERVICE_PATH.as_str(),
+        user.email,
+        id,
+        payload
+    );
+
+    let service = services.camera_manager;
+
+    match service
+        .update_camera(&Camera {
+            id,
+            is_radar: payload.is_radar,
+            location: payload.location,
+            speed_limit: payload.speed_limit,
+            install_date: payload.install_date,
+            is_active: payload.is_active,
+        })
+        .await
+    {
+        Ok(camera) => camera_response(StatusCode::OK, camera),
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/camera/{id}",
+    summary = "Вывод камеры из эксплуатации",
+    description = "Камера остается в реестре вместе со снимками, но новые снимки с нее не принимаются",
+    params(("id" = usize, Path, description = "id камеры")),
+    responses(
+        (status = StatusCode::OK, description = "Камера выведена из эксплуатации", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Камера не найдена", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["camera"]
+)]
+pub async fn handle_decommission_camera(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Path(id): Path<usize>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {{ id: {} }}",
+        CAMERA_GET_BY_ID_SERVICE_PATH.as_str(),
+        user.email,
+        id
+    );
+
+    let service = services.camera_manager;
+
+    match service.decommission_camera(id).await {
+        Ok(_) => {
+            let response = ResponseWithoutData {
+                status: StatusResponse::new(),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
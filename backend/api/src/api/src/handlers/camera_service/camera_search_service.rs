@@ -0,0 +1,166 @@
+use super::super::error_response;
+use super::BusinessServices;
+use super::StatusResponse;
+use crate::handlers::{CurrentUser, ResponseWithoutData};
+use crate::paths::{
+    CAMERA_IN_BBOX_SERVICE_PATH, CAMERA_NEAREST_SERVICE_PATH, CAMERA_WITHIN_SERVICE_PATH,
+};
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::{BoundingBox, Location, NearbyCamera};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct NearestCamerasRequest {
+    pub location: Location,
+    /// Количество камер (от 1 до 50)
+    #[schema(example = 5)]
+    pub count: usize,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct CamerasWithinRequest {
+    pub location: Location,
+    /// Радиус поиска (м, не более 50000)
+    #[schema(example = 1000.0)]
+    pub radius: f64,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct NearbyCamerasResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub cameras: Option<Vec<NearbyCamera>>,
+}
+
+fn cameras_response(cameras: Vec<NearbyCamera>) -> Result<Response, StatusCode> {
+    let response = NearbyCamerasResponse {
+        status: StatusResponse::new(),
+        cameras: Some(cameras),
+    };
+    log::info!("Sended response {:#?}", response);
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/camera/nearest",
+    summary = "Ближайшие камеры",
+    description = "Ближайшие к точке камеры в порядке возрастания расстояния (м)",
+    request_body = NearestCamerasRequest,
+    responses(
+        (status = StatusCode::OK, description = "Камеры успешно получены", body = NearbyCamerasResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["camera"]
+)]
+pub async fn handle_find_nearest_cameras(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<NearestCamerasRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        CAMERA_NEAREST_SERVICE_PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.camera_data_getter;
+
+    match service
+        .find_nearest_cameras(&payload.location, payload.count)
+        .await
+    {
+        Ok(cameras) => cameras_response(cameras),
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/camera/within",
+    summary = "Камеры в радиусе",
+    description = "Камеры в радиусе от точки в порядке возрастания расстояния (м)",
+    request_body = CamerasWithinRequest,
+    responses(
+        (status = StatusCode::OK, description = "Камеры успешно получены", body = NearbyCamerasResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["camera"]
+)]
+pub async fn handle_find_cameras_within(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<CamerasWithinRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        CAMERA_WITHIN_SERVICE_PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.camera_data_getter;
+
+    match service
+        .find_cameras_within(&payload.location, payload.radius)
+        .await
+    {
+        Ok(cameras) => cameras_response(cameras),
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/camera/in-bbox",
+    summary = "Камеры в прямоугольной области",
+    description = "Камеры внутри области, заданной юго-западным и северо-восточным углами. \
+        Расстояние (м) считается от центра области",
+    request_body = BoundingBox,
+    responses(
+        (status = StatusCode::OK, description = "Камеры успешно получены", body = NearbyCamerasResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["camera"]
+)]
+pub async fn handle_find_cameras_in_bbox(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<BoundingBox>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        CAMERA_IN_BBOX_SERVICE_PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.camera_data_getter;
+
+    match service.find_cameras_in_bbox(&payload).await {
+        Ok(cameras) => cameras_response(cameras),
+        Err(e) => error_response(e),
+    }
+}
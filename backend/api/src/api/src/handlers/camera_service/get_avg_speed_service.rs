@@ -1,8 +1,8 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
 use crate::paths::GET_AVG_SPEED_ON_CAMERA_PATH as PATH;
 use axum::{
-    extract::Json as ExtractJson,
+    extract::{Json as ExtractJson, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
@@ -42,23 +42,21 @@ pub struct AvgSpeedResponse {
             },
             "avg_speed": null
         })),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["camera"]
 )]
 pub async fn handle_get_avg_speed_for_car_on_camera(
+    State(services): State<BusinessServices>,
     ExtractJson(payload): ExtractJson<AvgSpeedRequest>,
 ) -> Result<Response, StatusCode> {
     let mut status = StatusResponse::new();
     log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
 
-    let service = match BUSINESS_SERVICES::get("camera_data_getter").await {
-        Some(BLServices::CameraDataGetService(s)) => s,
-        _ => {
-            log::warn!("Can't get CameraDataGetService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.camera_data_getter;
 
     let avg_speed = match service
         .get_avg_speed_of_car_on_camera_by_gos_num(&payload.gos_num, &payload.location)
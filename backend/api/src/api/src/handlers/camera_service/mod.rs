@@ -1,7 +1,9 @@
 pub mod camera_get_by_cords_service;
 pub mod camera_get_by_id_service;
+pub mod camera_manage_service;
 pub mod camera_response;
+pub mod camera_search_service;
 pub mod get_avg_speed_service;
 
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
@@ -0,0 +1,66 @@
+use super::error_response;
+use super::BusinessServices;
+use super::CurrentUser;
+use super::{ResponseWithoutData, StatusResponse};
+use crate::paths::CAR_GET_BY_VIN_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::DetailedCar;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct DetailedCarResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub car: Option<DetailedCar>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/car/{vin}",
+    summary = "Регистрационная карточка автомобиля",
+    description = "Полные данные СТС и ПТС автомобиля по VIN и его текущий пробег",
+    params(("vin" = String, Path, description = "VIN автомобиля", example = "XTA210990Y2765432")),
+    responses(
+        (status = StatusCode::OK, description = "Автомобиль успешно получен", body = DetailedCarResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректный VIN", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Автомобиль не найден", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["car"]
+)]
+pub async fn handle_get_car_by_vin(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Path(vin): Path<String>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {{ vin: {:?} }}",
+        PATH.as_str(),
+        user.email,
+        vin
+    );
+
+    let service = services.searcher;
+
+    match service.get_car_by_vin(&vin).await {
+        Ok(car) => {
+            let response = DetailedCarResponse {
+                status: StatusResponse::new(),
+                car: Some(car),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
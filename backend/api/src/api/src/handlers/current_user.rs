@@ -0,0 +1,86 @@
+use super::{BusinessServices, ServiceError};
+use super::{ResponseStatusCode, ResponseWithoutData, StatusResponse};
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap, StatusCode},
+    Json,
+};
+use models::User;
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+pub struct CurrentUser(pub User);
+
+impl CurrentUser {
+    pub(crate) fn rejection(
+        http_code: StatusCode,
+        code: ResponseStatusCode,
+        message: &str,
+    ) -> (StatusCode, Json<ResponseWithoutData>) {
+        let response = ResponseWithoutData {
+            status: StatusResponse {
+                code: code as isize,
+                message: message.to_string(),
+            },
+        };
+        log::warn!("Sended error response {:#?}", response);
+        (http_code, Json(response))
+    }
+
+    pub(crate) fn bearer_token(headers: &HeaderMap) -> Option<String> {
+        headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .map(|token| token.trim().to_string())
+    }
+
+    fn unauthorized(message: &str) -> (StatusCode, Json<ResponseWithoutData>) {
+        Self::rejection(
+            StatusCode::UNAUTHORIZED,
+            ResponseStatusCode::INVALID_TOKEN,
+            message,
+        )
+    }
+
+    fn internal_error() -> (StatusCode, Json<ResponseWithoutData>) {
+        Self::rejection(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ResponseStatusCode::UNKNOWN_ERROR,
+            "Internal server error",
+        )
+    }
+}
+
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    BusinessServices: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ResponseWithoutData>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<User>() {
+            return Ok(CurrentUser(user.clone()));
+        }
+
+        let access_token = match Self::bearer_token(&parts.headers) {
+            Some(token) => token,
+            None => return Err(Self::unauthorized("Missing bearer token")),
+        };
+
+        let service = BusinessServices::from_ref(state).session_manager;
+
+        match service.get_session_user(&access_token).await {
+            Ok(user) => {
+                log::debug!("Request authorized for user: {}", user.email);
+                Ok(CurrentUser(user))
+            }
+            Err(ServiceError::InvalidDataError(_)) => Err(Self::unauthorized("Invalid token")),
+            Err(e) => {
+                log::error!("Failed to resolve session user: {}", e);
+                Err(Self::internal_error())
+            }
+        }
+    }
+}
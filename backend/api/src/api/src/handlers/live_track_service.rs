@@ -0,0 +1,132 @@
+use super::BusinessServices;
+use super::CurrentUser;
+use super::ResponseWithoutData;
+use super::{error_status, status_response};
+use crate::paths::LIVE_TRACK_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Response, Sse,
+    },
+};
+use di_container::snap_hub::{LiveEvent, SnapSubscription};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::time::Duration;
+use utoipa::{IntoParams, ToSchema};
+
+const SNAP_EVENT: &str = "snap";
+const LAGGED_EVENT: &str = "lagged";
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct LiveTrackQuery {
+    /// Гос.номера через запятую
+    #[param(example = "А777МР77,В123ОР77")]
+    gos_nums: String,
+}
+
+// Событие о снимках, пропущенных медленным клиентом
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct LiveLaggedEvent {
+    #[schema(example = 12)]
+    pub skipped: u64,
+}
+
+fn to_sse_event(event: LiveEvent) -> Event {
+    let res = match event {
+        LiveEvent::Item(snap) => Event::default().event(SNAP_EVENT).json_data(&*snap),
+        LiveEvent::Lagged(skipped) => Event::default()
+            .event(LAGGED_EVENT)
+            .json_data(LiveLaggedEvent { skipped }),
+    };
+
+    res.unwrap_or_else(|e| {
+        log::error!("Failed to serialize live event: {}", e);
+        Event::default().comment("serialization error")
+    })
+}
+
+fn live_stream(
+    subscription: SnapSubscription,
+    expires_at: u64,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    // Поток завершается с истечением токена, продолжить можно только с новым,
+    // или с закрытием хаба при остановке сервера.
+    // Подписка освобождается вместе с потоком при отключении клиента
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+    let expires_in = Duration::from_secs(expires_at.saturating_sub(now));
+
+    stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.next().await?;
+        Some((Ok(to_sse_event(event)), subscription))
+    })
+    .take_until(tokio::time::sleep(expires_in))
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/car/live",
+    summary = "Отслеживание в реальном времени",
+    description = "Поток Server-Sent Events со снимками указанных автомобилей, принятыми после подписки. \
+        Событие snap содержит снимок (Snap). Клиент, не успевающий читать поток, \
+        пропускает самые старые снимки и получает событие lagged (LiveLaggedEvent) с их количеством. \
+        Поток завершается, когда истекает токен доступа или останавливается сервер",
+    params(LiveTrackQuery),
+    responses(
+        (status = StatusCode::OK, description = "Поток событий", content_type = "text/event-stream"),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["car"]
+)]
+pub async fn handle_live_track(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    headers: HeaderMap,
+    Query(query): Query<LiveTrackQuery>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        PATH.as_str(),
+        user.email,
+        query
+    );
+
+    let gos_nums: Vec<String> = query
+        .gos_nums
+        .split(',')
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty())
+        .collect();
+
+    let expires_at = match CurrentUser::bearer_token(&headers) {
+        Some(token) => services
+            .session_manager
+            .get_session_expiry(&token)
+            .await
+            .map_err(|e| {
+                log::warn!("Failed to get token expiry for {}: {}", user.email, e);
+                StatusCode::UNAUTHORIZED
+            })?,
+        None => return Err(StatusCode::UNAUTHORIZED),
+    };
+
+    let service = services.live_tracker;
+
+    match service.subscribe(&gos_nums, &user.email).await {
+        Ok(subscription) => {
+            log::info!("Started live stream for {} ({:?})", user.email, gos_nums);
+            Ok(Sse::new(live_stream(subscription, expires_at))
+                .keep_alive(KeepAlive::default())
+                .into_response())
+        }
+        Err(e) => Ok(status_response(StatusCode::BAD_REQUEST, error_status(e)?)),
+    }
+}
@@ -1,46 +1,97 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
 use serde::{Deserialize, Serialize};
-use utoipa::OpenApi;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::ToSchema;
+use utoipa::{Modify, OpenApi};
 
-use models::Location;
+use models::{
+    BoundingBox, Camera, DetailedCar, DetailedRoute, Location, NearbyCamera, OwnershipRecord,
+    RoadSection, RoutePoint, RouteSegment, RouteStats, RouteStop, RouteTrips, Session, Snap,
+    SortKey, SpeedViolation, Trip, ViolationSeverity, WatchlistAlert, WatchlistEntry,
+};
 
 pub use di_container::error::ServiceError;
-pub use di_container::{BLServices, BUSINESS_SERVICES};
+pub use di_container::BusinessServices;
 
 use auth_services::{
-    auth_service::__path_handle_auth, passport_confirm_service::__path_handle_passport_conf,
+    auth_service::__path_handle_auth, logout_service::__path_handle_logout,
+    passport_confirm_service::__path_handle_passport_conf, refresh_service::__path_handle_refresh,
     registration_service::__path_handle_reg,
 };
 use auth_services::{
     auth_service::{AuthRequest, AuthResponse},
+    logout_service::LogoutRequest,
     passport_confirm_service::PassportConfRequest,
+    refresh_service::{RefreshRequest, RefreshResponse},
     registration_service::RegRequest,
 };
 use camera_service::{
     camera_get_by_cords_service::__path_handle_get_camera_by_cords,
     camera_get_by_id_service::__path_handle_get_camera_by_id,
+    camera_manage_service::{
+        __path_handle_add_camera, __path_handle_decommission_camera, __path_handle_update_camera,
+        CameraRequest, CameraUpdateRequest,
+    },
     camera_response::CameraResponse,
+    camera_search_service::{
+        __path_handle_find_cameras_in_bbox, __path_handle_find_cameras_within,
+        __path_handle_find_nearest_cameras, CamerasWithinRequest, NearbyCamerasResponse,
+        NearestCamerasRequest,
+    },
     get_avg_speed_service::{
-        AvgSpeedRequest, AvgSpeedResponse, __path_handle_get_avg_speed_for_car_on_camera,
+        __path_handle_get_avg_speed_for_car_on_camera, AvgSpeedRequest, AvgSpeedResponse,
     },
 };
+use car_get_by_vin_service::{__path_handle_get_car_by_vin, DetailedCarResponse};
+use live_track_service::{__path_handle_live_track, LiveLaggedEvent};
+use owner_history_service::{
+    __path_handle_get_owner_history, OwnerHistoryRequest, OwnerHistoryResponse,
+};
+use road_section_service::{
+    __path_handle_add_road_section, __path_handle_delete_road_section,
+    __path_handle_get_road_sections, RoadSectionRequest, RoadSectionResponse, RoadSectionsResponse,
+};
+use route_export_service::__path_handle_route_export;
 use route_get_service::__path_handle_route;
-use route_get_service::{RouteRequest, RouteResponse};
-use snap_send_service::SnapSendRequest;
+use route_get_service::{DetailedRouteResponse, RouteRequest, RouteResponse};
+use route_period_service::{__path_handle_route_by_period, RoutePeriodRequest};
+use route_trips_service::{__path_handle_route_trips, RouteTripsResponse};
+use snap_send_batch_service::{
+    __path_handle_snap_send_batch, SnapBatchItemResult, SnapSendBatchResponse,
+};
 use snap_send_service::__path_handle_snap_send;
+use snap_send_service::SnapSendRequest;
+use speed_violation_service::{
+    __path_handle_search_speed_violations, SpeedViolationSearchRequest, SpeedViolationsResponse,
+};
 
 use search_services::{
     car_search_services::{
-        CarSearcherResponse, SearchCarByFilterRequest, __path_handle_search_car_by_fio,
-        __path_handle_search_car_by_gos_num_mask, __path_handle_search_car_by_passport,
-        __path_handle_search_cars_by_filters,
+        __path_handle_search_car_by_fio, __path_handle_search_car_by_gos_num_mask,
+        __path_handle_search_car_by_passport, __path_handle_search_cars_by_filters,
+        CarSearcherResponse, DetailedCarSearcherResponse, SearchCarByFilterRequest,
     },
     search_requests::*,
     track_info_search_services::{
-        SearchTrackInfoByFilterRequest, TrackInfoSearcherResponse,
         __path_handle_search_track_info_by_date, __path_handle_search_track_info_by_filters,
         __path_handle_search_track_info_by_fio, __path_handle_search_track_info_by_gos_num_mask,
-        __path_handle_search_track_info_by_passport,
+        __path_handle_search_track_info_by_passport, SearchTrackInfoByFilterRequest,
+        TrackInfoSearcherResponse,
+    },
+};
+use watchlist_services::{
+    watchlist_alert_services::{
+        __path_handle_get_watchlist_alerts, __path_handle_watchlist_alerts_live,
+        WatchlistAlertsResponse,
+    },
+    watchlist_entry_services::{
+        __path_handle_add_watchlist_entry, __path_handle_delete_watchlist_entry,
+        __path_handle_get_watchlist, __path_handle_update_watchlist_entry, WatchlistEntryRequest,
+        WatchlistEntryResponse, WatchlistResponse,
     },
 };
 
@@ -50,11 +101,24 @@ mod status_response;
 pub use response_status_code::{ResponseStatusCode, ResponseStatusCodeType};
 pub use status_response::StatusResponse;
 
+mod current_user;
+pub use current_user::CurrentUser;
+
 pub mod auth_services;
 pub mod camera_service;
+pub mod car_get_by_vin_service;
+pub mod live_track_service;
+pub mod owner_history_service;
+pub mod road_section_service;
+pub mod route_export_service;
 pub mod route_get_service;
+pub mod route_period_service;
+pub mod route_trips_service;
 pub mod search_services;
+pub mod snap_send_batch_service;
 pub mod snap_send_service;
+pub mod speed_violation_service;
+pub mod watchlist_services;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -72,13 +136,34 @@ pub mod snap_send_service;
         ),
     ),
     paths(
-        handle_route, 
+        handle_route,
+        handle_route_export,
+        handle_route_by_period,
+        handle_route_trips,
+        handle_live_track,
+        handle_get_car_by_vin,
+        handle_get_owner_history,
+
+        handle_add_watchlist_entry,
+        handle_get_watchlist,
+        handle_update_watchlist_entry,
+        handle_delete_watchlist_entry,
+        handle_get_watchlist_alerts,
+        handle_watchlist_alerts_live,
+
+        handle_search_speed_violations,
+        handle_add_road_section,
+        handle_get_road_sections,
+        handle_delete_road_section,
 
         handle_auth,
+        handle_refresh,
+        handle_logout,
         handle_reg,
-        handle_passport_conf, 
-        
+        handle_passport_conf,
+
         handle_snap_send,
+        handle_snap_send_batch,
 
         handle_search_cars_by_filters,
         handle_search_car_by_fio,
@@ -93,15 +178,32 @@ pub mod snap_send_service;
 
         handle_get_camera_by_id,
         handle_get_camera_by_cords,
+        handle_add_camera,
+        handle_update_camera,
+        handle_decommission_camera,
         handle_get_avg_speed_for_car_on_camera,
+        handle_find_nearest_cameras,
+        handle_find_cameras_within,
+        handle_find_cameras_in_bbox,
     ),
     components(schemas(
         RouteRequest, RouteResponse, AuthRequest, AuthResponse, Location,
         RegRequest, PassportConfRequest, SnapSendRequest, ResponseWithoutData,
         CarSearcherResponse, TrackInfoSearcherResponse, SearchByFIORequest,
         SearchByDateRequest, SearchByGosNumRequest, SearchByPassportRequest, CameraResponse,
-        AvgSpeedRequest, AvgSpeedResponse, SearchTrackInfoByFilterRequest, SearchCarByFilterRequest
+        AvgSpeedRequest, AvgSpeedResponse, SearchTrackInfoByFilterRequest, SearchCarByFilterRequest,
+        Session, RefreshRequest, RefreshResponse, LogoutRequest,
+        SnapSendBatchResponse, SnapBatchItemResult, DetailedRouteResponse, DetailedRoute,
+        RoutePoint, RouteSegment, RouteStats, RoutePeriodRequest, RouteTripsResponse, RouteTrips,
+        Trip, RouteStop, Snap, Camera, LiveLaggedEvent, WatchlistEntry, WatchlistAlert,
+        WatchlistEntryRequest, WatchlistEntryResponse, WatchlistResponse, WatchlistAlertsResponse,
+        SpeedViolation, ViolationSeverity, SpeedViolationSearchRequest, SpeedViolationsResponse,
+        RoadSection, RoadSectionRequest, RoadSectionResponse, RoadSectionsResponse,
+        NearbyCamera, BoundingBox, NearestCamerasRequest, CamerasWithinRequest, NearbyCamerasResponse,
+        CameraRequest, CameraUpdateRequest, OwnershipRecord, OwnerHistoryRequest, OwnerHistoryResponse,
+        DetailedCar, DetailedCarResponse, DetailedCarSearcherResponse, SortKey
     )),
+    modifiers(&SecurityAddon),
     tags(
         (name = "route", description = "Получение маршрута"),
         (name = "auth", description = "Авторизация"),
@@ -110,12 +212,65 @@ pub mod snap_send_service;
         (name = "car", description = "Автомобили"),
         (name = "camera", description = "Камера"),
         (name = "track-info", description = "Информация об отслеживании"),
+        (name = "watchlist", description = "Список розыска"),
+        (name = "violation", description = "Нарушения скоростного режима и участки контроля средней скорости"),
     )
 )]
 pub struct ApiDoc;
 
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            )
+        }
+    }
+}
+
 #[derive(ToSchema, Deserialize, Serialize, Debug)]
 pub struct ResponseWithoutData {
     #[schema(example = json!({ "code": 0, "message": "OK" }))]
     pub status: StatusResponse,
 }
+
+pub(crate) fn error_status(e: ServiceError) -> Result<StatusResponse, StatusCode> {
+    match e {
+        ServiceError::InvalidDataError(e) => Ok(StatusResponse {
+            code: ResponseStatusCode::from(&e, ResponseStatusCodeType::INVALID_DATA) as isize,
+            message: format!("Invalid {e}"),
+        }),
+        ServiceError::NotFoundError(e) => Ok(StatusResponse {
+            code: ResponseStatusCode::from(&e, ResponseStatusCodeType::NOT_FOUNDED_DATA) as isize,
+            message: format!("Not founded {e}"),
+        }),
+        ServiceError::IsExistError(e) => Ok(StatusResponse {
+            code: ResponseStatusCode::from(&e, ResponseStatusCodeType::EXIST_DATA) as isize,
+            message: format!("{e} is exist"),
+        }),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+pub(crate) fn status_response(code: StatusCode, status: StatusResponse) -> Response {
+    let response = ResponseWithoutData { status };
+    log::warn!("Sended error response {:#?}", response);
+    (code, Json(response)).into_response()
+}
+
+pub(crate) fn error_response(e: ServiceError) -> Result<Response, StatusCode> {
+    let code = match &e {
+        ServiceError::NotFoundError(_) => StatusCode::NOT_FOUND,
+        ServiceError::IsExistError(_) => StatusCode::CONFLICT,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    Ok(status_response(code, error_status(e)?))
+}
@@ -0,0 +1,79 @@
+use super::error_response;
+use super::BusinessServices;
+use super::CurrentUser;
+use super::{ResponseWithoutData, StatusResponse};
+use crate::paths::CAR_OWNER_HISTORY_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::OwnershipRecord;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct OwnerHistoryRequest {
+    /// Указывается ровно одно из полей vin и gos_num
+    #[schema(example = "XTA210990Y2765432")]
+    pub vin: Option<String>,
+    #[schema(example = json!(null))]
+    pub gos_num: Option<String>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct OwnerHistoryResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub owners: Vec<OwnershipRecord>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/car/owner-history",
+    summary = "История владения автомобилем",
+    description = "Цепочка владельцев автомобиля по VIN или гос.номеру в порядке регистрации: \
+        ФИО владельца, период владения и пробег на момент регистрации",
+    request_body = OwnerHistoryRequest,
+    responses(
+        (status = StatusCode::OK, description = "История владения успешно получена", body = OwnerHistoryResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Автомобиль не найден", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["car"]
+)]
+pub async fn handle_get_owner_history(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<OwnerHistoryRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.searcher;
+
+    match service
+        .get_owner_history(payload.vin.as_deref(), payload.gos_num.as_deref())
+        .await
+    {
+        Ok(owners) => {
+            let response = OwnerHistoryResponse {
+                status: StatusResponse::new(),
+                owners,
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
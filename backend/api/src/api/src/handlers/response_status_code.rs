@@ -15,6 +15,31 @@ pub enum ResponseStatusCode {
     INVALID_DATE = 1001,
     INVALID_GOS_NUM = 1002,
     INVALID_GOS_NUM_MASK = 1003,
+    INVALID_SNAP = 1004,
+    INVALID_SNAP_BATCH = 1005,
+    INVALID_IDEMPOTENCY_KEY = 1006,
+    INVALID_API_VERSION = 1007,
+    INVALID_EXPORT_FORMAT = 1008,
+    INVALID_DATETIME = 1009,
+    INVALID_PERIOD = 1010,
+    INVALID_TRIP_GAP = 1011,
+    INVALID_SUBSCRIPTION = 1012,
+    INVALID_REASON = 1013,
+    INVALID_ROAD_SECTION = 1014,
+    INVALID_LOCATION = 1015,
+    INVALID_CAMERAS_COUNT = 1016,
+    INVALID_RADIUS = 1017,
+    INVALID_BOUNDING_BOX = 1018,
+    INVALID_CAMERA = 1019,
+    CAMERA_DECOMMISSIONED = 1020,
+    INVALID_SPEED_LIMIT = 1021,
+    CAMERA_MISMATCH = 1022,
+    SPEED_WITHOUT_RADAR = 1023,
+    INVALID_CAR_IDENTIFIER = 1024,
+    INVALID_VIN = 1025,
+    INVALID_PAGE_SIZE = 1026,
+    INVALID_SORT_KEY = 1027,
+    INVALID_RELEASE_YEAR = 1028,
 
     INVALID_AUTH_DATA = 2000,
     INVALID_EMAIL = 2001,
@@ -23,12 +48,19 @@ pub enum ResponseStatusCode {
     INVALID_PASSPORT = 2004,
     PASSPORT_EXIST = 2005,
     INVALID_TIME = 2006,
+    INVALID_TOKEN = 2007,
+    ACCESS_DENIED = 2008,
 
     EMAIL_EXIST = 3001,
     EMAIL_NOT_FOUNDED = 3002,
+    ROAD_SECTION_EXIST = 3003,
 
+    CAR_NOT_FOUNDED = 4001,
     CAMERA_NOT_FOUNDED = 4002,
     AVG_SPEED_NOT_FOUNDED = 4003,
+    ROUTE_NOT_FOUNDED = 4004,
+    WATCHLIST_ENTRY_NOT_FOUNDED = 4005,
+    ROAD_SECTION_NOT_FOUNDED = 4006,
 
     UNKNOWN_ERROR = 9999,
 }
@@ -37,14 +69,38 @@ impl ResponseStatusCode {
     fn from_invalid_data(err: &str) -> Self {
         match err {
             "date" => ResponseStatusCode::INVALID_DATE,
+            "datetime" => ResponseStatusCode::INVALID_DATETIME,
+            "period" => ResponseStatusCode::INVALID_PERIOD,
+            "trip gap" => ResponseStatusCode::INVALID_TRIP_GAP,
+            "subscription" => ResponseStatusCode::INVALID_SUBSCRIPTION,
+            "reason" => ResponseStatusCode::INVALID_REASON,
+            "road section" => ResponseStatusCode::INVALID_ROAD_SECTION,
+            "location" => ResponseStatusCode::INVALID_LOCATION,
+            "cameras count" => ResponseStatusCode::INVALID_CAMERAS_COUNT,
+            "radius" => ResponseStatusCode::INVALID_RADIUS,
+            "bounding box" => ResponseStatusCode::INVALID_BOUNDING_BOX,
+            "camera" => ResponseStatusCode::INVALID_CAMERA,
+            "decommissioned camera" => ResponseStatusCode::CAMERA_DECOMMISSIONED,
+            "speed limit" => ResponseStatusCode::INVALID_SPEED_LIMIT,
+            "camera mismatch" => ResponseStatusCode::CAMERA_MISMATCH,
+            "speed without radar" => ResponseStatusCode::SPEED_WITHOUT_RADAR,
+            "car identifier" => ResponseStatusCode::INVALID_CAR_IDENTIFIER,
+            "vin" => ResponseStatusCode::INVALID_VIN,
+            "page size" => ResponseStatusCode::INVALID_PAGE_SIZE,
+            "sort key" => ResponseStatusCode::INVALID_SORT_KEY,
+            "release year" => ResponseStatusCode::INVALID_RELEASE_YEAR,
             "gos number" => ResponseStatusCode::INVALID_GOS_NUM,
             "gos number mask" => ResponseStatusCode::INVALID_GOS_NUM_MASK,
+            "snap" => ResponseStatusCode::INVALID_SNAP,
+            "snap batch" => ResponseStatusCode::INVALID_SNAP_BATCH,
+            "idempotency key" => ResponseStatusCode::INVALID_IDEMPOTENCY_KEY,
             "email or password" => ResponseStatusCode::INVALID_AUTH_DATA,
             "email" => ResponseStatusCode::INVALID_EMAIL,
             "password" => ResponseStatusCode::INVALID_PSWD,
             "passwords unmatch" => ResponseStatusCode::INVALID_PSWDS,
             "time" => ResponseStatusCode::INVALID_TIME,
             "passport" => ResponseStatusCode::INVALID_PASSPORT,
+            "token" => ResponseStatusCode::INVALID_TOKEN,
             _ => ResponseStatusCode::UNKNOWN_ERROR,
         }
     }
@@ -53,6 +109,7 @@ impl ResponseStatusCode {
         match err {
             "email" => ResponseStatusCode::EMAIL_EXIST,
             "passport" => ResponseStatusCode::PASSPORT_EXIST,
+            "road section" => ResponseStatusCode::ROAD_SECTION_EXIST,
             _ => ResponseStatusCode::UNKNOWN_ERROR,
         }
     }
@@ -60,8 +117,12 @@ impl ResponseStatusCode {
     fn from_not_founded_data(err: &str) -> Self {
         match err {
             "email" => ResponseStatusCode::EMAIL_NOT_FOUNDED,
+            "car" => ResponseStatusCode::CAR_NOT_FOUNDED,
             "camera" => ResponseStatusCode::CAMERA_NOT_FOUNDED,
             "average speed" => ResponseStatusCode::AVG_SPEED_NOT_FOUNDED,
+            "route" => ResponseStatusCode::ROUTE_NOT_FOUNDED,
+            "watchlist entry" => ResponseStatusCode::WATCHLIST_ENTRY_NOT_FOUNDED,
+            "road section" => ResponseStatusCode::ROAD_SECTION_NOT_FOUNDED,
             _ => ResponseStatusCode::UNKNOWN_ERROR,
         }
     }
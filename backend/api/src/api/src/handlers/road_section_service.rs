@@ -0,0 +1,178 @@
+use super::error_response;
+use super::BusinessServices;
+use super::CurrentUser;
+use super::{ResponseWithoutData, StatusResponse};
+use crate::paths::{ROAD_SECTION_PATH, ROAD_SECTION_SERVICE_PATH};
+use axum::{
+    extract::{Json as ExtractJson, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::RoadSection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RoadSectionRequest {
+    #[schema(example = 1)]
+    pub start_camera_id: usize,
+    #[schema(example = 2)]
+    pub end_camera_id: usize,
+    /// Длина участка по дороге (м)
+    #[schema(example = 10000)]
+    pub distance: u32,
+    #[schema(example = 60)]
+    pub speed_limit: u16,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RoadSectionResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub section: Option<RoadSection>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RoadSectionsResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub sections: Option<Vec<RoadSection>>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/road-section",
+    summary = "Добавление участка контроля средней скорости",
+    description = "Участок задается парой камер по направлению движения, длиной и ограничением скорости. \
+        Автомобиль, зафиксированный на обеих камерах, проверяется по времени проезда участка",
+    request_body = RoadSectionRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Участок добавлен", body = RoadSectionResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Камера не найдена", body = ResponseWithoutData),
+        (status = StatusCode::CONFLICT, description = "Участок между камерами уже существует", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["violation"]
+)]
+pub async fn handle_add_road_section(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<RoadSectionRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        ROAD_SECTION_PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.speed_controller;
+
+    match service
+        .add_section(
+            payload.start_camera_id,
+            payload.end_camera_id,
+            payload.distance,
+            payload.speed_limit,
+        )
+        .await
+    {
+        Ok(section) => {
+            let response = RoadSectionResponse {
+                status: StatusResponse::new(),
+                section: Some(section),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::CREATED, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/road-section",
+    summary = "Участки контроля средней скорости",
+    responses(
+        (status = StatusCode::OK, description = "Участки успешно получены", body = RoadSectionsResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["violation"]
+)]
+pub async fn handle_get_road_sections(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+) -> Result<Json<RoadSectionsResponse>, StatusCode> {
+    log::info!(
+        "Received request from {} by {}",
+        ROAD_SECTION_PATH.as_str(),
+        user.email
+    );
+
+    let service = services.speed_controller;
+
+    let sections = service
+        .get_sections()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let response = RoadSectionsResponse {
+        status: StatusResponse::new(),
+        sections: Some(sections),
+    };
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response))
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/road-section/{id}",
+    summary = "Удаление участка контроля средней скорости",
+    description = "Удаление участка вместе с зафиксированными на нем нарушениями",
+    params(("id" = usize, Path, description = "id участка")),
+    responses(
+        (status = StatusCode::OK, description = "Участок удален", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Участок не найден", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["violation"]
+)]
+pub async fn handle_delete_road_section(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Path(id): Path<usize>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {{ id: {} }}",
+        ROAD_SECTION_SERVICE_PATH.as_str(),
+        user.email,
+        id
+    );
+
+    let service = services.speed_controller;
+
+    match service.delete_section(id).await {
+        Ok(_) => {
+            let response = ResponseWithoutData {
+                status: StatusResponse::new(),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,301 @@
+use super::route_get_service::RouteRequest;
+use super::CurrentUser;
+use super::ResponseWithoutData;
+use super::{error_response, status_response, BusinessServices};
+use crate::paths::ROUTE_EXPORT_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDateTime;
+use models::{DetailedRoute, RoutePoint};
+use serde::Deserialize;
+use serde_json::json;
+use utoipa::IntoParams;
+
+use super::{ResponseStatusCode, StatusResponse};
+
+// Время снимков хранится по Москве
+const SNAP_TIME_OFFSET: &str = "+03:00";
+const GPX_EXTENSIONS_NS: &str = "https://autotracker.local/gpx/1";
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct RouteExportQuery {
+    /// Формат выгрузки: geojson, gpx или kml
+    #[param(example = "geojson")]
+    format: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    GeoJson,
+    Gpx,
+    Kml,
+}
+
+impl ExportFormat {
+    pub fn from(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "geojson" => Some(ExportFormat::GeoJson),
+            "gpx" => Some(ExportFormat::Gpx),
+            "kml" => Some(ExportFormat::Kml),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::GeoJson => "application/geo+json",
+            ExportFormat::Gpx => "application/gpx+xml",
+            ExportFormat::Kml => "application/vnd.google-earth.kml+xml",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::GeoJson => "geojson",
+            ExportFormat::Gpx => "gpx",
+            ExportFormat::Kml => "kml",
+        }
+    }
+
+    pub fn render(&self, gos_num: &str, route: &DetailedRoute) -> String {
+        match self {
+            ExportFormat::GeoJson => render_geojson(gos_num, route),
+            ExportFormat::Gpx => render_gpx(gos_num, route),
+            ExportFormat::Kml => render_kml(gos_num, route),
+        }
+    }
+}
+
+fn iso_time(point: &RoutePoint) -> String {
+    NaiveDateTime::parse_from_str(&format!("{} {}", point.date, point.time), "%d.%m.%Y %H:%M")
+        .map(|dt| format!("{}{}", dt.format("%Y-%m-%dT%H:%M:%S"), SNAP_TIME_OFFSET))
+        .unwrap_or_default()
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn route_name(gos_num: &str, route: &DetailedRoute) -> String {
+    match route.points.first() {
+        Some(point) => format!("{} {}", gos_num, point.date),
+        None => gos_num.to_string(),
+    }
+}
+
+// Линия маршрута со временем и скоростью точек в свойствах и отдельные точки камер
+pub fn render_geojson(gos_num: &str, route: &DetailedRoute) -> String {
+    let coordinates: Vec<_> = route
+        .points
+        .iter()
+        .map(|p| json!([p.cords.longitude, p.cords.latitude]))
+        .collect();
+
+    let mut features = vec![json!({
+        "type": "Feature",
+        "geometry": { "type": "LineString", "coordinates": coordinates },
+        "properties": {
+            "gos_num": gos_num,
+            "times": route.points.iter().map(iso_time).collect::<Vec<_>>(),
+            "speeds": route.points.iter().map(|p| p.speed).collect::<Vec<_>>(),
+            "camera_ids": route.points.iter().map(|p| p.camera_id).collect::<Vec<_>>(),
+            "distance_km": route.stats.distance_km,
+            "duration_secs": route.stats.duration_secs,
+            "max_speed": route.stats.max_speed,
+            "avg_speed": route.stats.avg_speed,
+        },
+    })];
+
+    features.extend(route.points.iter().map(|p| {
+        json!({
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [p.cords.longitude, p.cords.latitude] },
+            "properties": {
+                "time": iso_time(p),
+                "speed": p.speed,
+                "camera_id": p.camera_id,
+                "is_radar": p.is_radar,
+            },
+        })
+    }));
+
+    json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
+
+// GPX 1.1 не содержит скорости у точек трека, она передается в расширениях
+pub fn render_gpx(gos_num: &str, route: &DetailedRoute) -> String {
+    let mut gpx = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <gpx version=\"1.1\" creator=\"AutoTracker\" \
+        xmlns=\"http://www.topografix.com/GPX/1/1\" xmlns:at=\"{}\">\n\
+        <trk>\n<name>{}</name>\n<trkseg>\n",
+        GPX_EXTENSIONS_NS,
+        escape_xml(&route_name(gos_num, route))
+    );
+
+    for point in &route.points {
+        gpx.push_str(&format!(
+            "<trkpt lat=\"{}\" lon=\"{}\">\n<time>{}</time>\n<extensions>\n",
+            point.cords.latitude,
+            point.cords.longitude,
+            iso_time(point)
+        ));
+        if let Some(speed) = point.speed {
+            gpx.push_str(&format!("<at:speed>{}</at:speed>\n", speed));
+        }
+        gpx.push_str(&format!(
+            "<at:camera_id>{}</at:camera_id>\n</extensions>\n</trkpt>\n",
+            point.camera_id
+        ));
+    }
+
+    gpx.push_str("</trkseg>\n</trk>\n</gpx>\n");
+    gpx
+}
+
+pub fn render_kml(gos_num: &str, route: &DetailedRoute) -> String {
+    let name = escape_xml(&route_name(gos_num, route));
+    let mut kml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <kml xmlns=\"http://www.opengis.net/kml/2.2\" xmlns:gx=\"http://www.google.com/kml/ext/2.2\">\n\
+        <Document>\n<name>{}</name>\n\
+        <Schema id=\"route\">\n\
+        <gx:SimpleArrayField name=\"speed\" type=\"int\"><displayName>Скорость</displayName></gx:SimpleArrayField>\n\
+        <gx:SimpleArrayField name=\"camera_id\" type=\"int\"><displayName>Камера</displayName></gx:SimpleArrayField>\n\
+        </Schema>\n\
+        <Placemark>\n<name>{}</name>\n<gx:Track>\n",
+        name, name
+    );
+
+    for point in &route.points {
+        kml.push_str(&format!("<when>{}</when>\n", iso_time(point)));
+    }
+    for point in &route.points {
+        kml.push_str(&format!(
+            "<gx:coord>{} {} 0</gx:coord>\n",
+            point.cords.longitude, point.cords.latitude
+        ));
+    }
+
+    kml.push_str(
+        "<ExtendedData>\n<SchemaData schemaUrl=\"#route\">\n<gx:SimpleArrayData name=\"speed\">\n",
+    );
+    for point in &route.points {
+        // Пустое значение сохраняет соответствие точкам трека для камер без радара
+        match point.speed {
+            Some(speed) => kml.push_str(&format!("<gx:value>{}</gx:value>\n", speed)),
+            None => kml.push_str("<gx:value/>\n"),
+        }
+    }
+    kml.push_str("</gx:SimpleArrayData>\n<gx:SimpleArrayData name=\"camera_id\">\n");
+    for point in &route.points {
+        kml.push_str(&format!("<gx:value>{}</gx:value>\n", point.camera_id));
+    }
+    kml.push_str(
+        "</gx:SimpleArrayData>\n</SchemaData>\n</ExtendedData>\n\
+        </gx:Track>\n</Placemark>\n</Document>\n</kml>\n",
+    );
+    kml
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/car/route/export",
+    summary = "Выгрузка маршрута",
+    description = "Выгрузка маршрута по гос.номеру и дате в формате GeoJSON, GPX 1.1 или KML. \
+        Время и скорость точек передаются в свойствах трека",
+    params(RouteExportQuery),
+    request_body = RouteRequest,
+    responses(
+        (status = StatusCode::OK, description = "Маршрут в запрошенном формате", content(
+            (String = "application/geo+json"),
+            (String = "application/gpx+xml"),
+            (String = "application/vnd.google-earth.kml+xml"),
+        )),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Маршрут не найден", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["route"]
+)]
+pub async fn handle_route_export(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<RouteExportQuery>,
+    ExtractJson(payload): ExtractJson<RouteRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {} ({:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        query,
+        payload
+    );
+
+    let Some(format) = query.format.as_deref().and_then(ExportFormat::from) else {
+        return Ok(status_response(
+            StatusCode::BAD_REQUEST,
+            StatusResponse {
+                code: ResponseStatusCode::INVALID_EXPORT_FORMAT as isize,
+                message: format!("Invalid export format: {:?}", query.format),
+            },
+        ));
+    };
+
+    let service = services.route_getter;
+
+    let route = match service
+        .get_car_route_detailed(&payload.gos_num, &user.email, &payload.date)
+        .await
+    {
+        Ok(Some(route)) => route,
+        Ok(None) => {
+            return Ok(status_response(
+                StatusCode::NOT_FOUND,
+                StatusResponse {
+                    code: ResponseStatusCode::ROUTE_NOT_FOUNDED as isize,
+                    message: "Not founded route".to_string(),
+                },
+            ))
+        }
+        Err(e) => return error_response(e),
+    };
+
+    let body = format.render(&payload.gos_num, &route);
+    let disposition = format!(
+        "attachment; filename=\"route_{}.{}\"",
+        payload.date,
+        format.extension()
+    );
+    log::info!(
+        "Sended {} route with {} points ({} bytes)",
+        format.extension(),
+        route.points.len(),
+        body.len()
+    );
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, format.content_type().to_string()),
+            (CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -1,20 +1,34 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::CurrentUser;
+use super::ResponseWithoutData;
+use super::{error_status, status_response, BusinessServices};
 use crate::paths::ROUTE_GET_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
-use models::PointData;
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::{DetailedRoute, PointData};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
-use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
+use super::{ResponseStatusCode, StatusResponse};
+
+const DETAILED_ROUTE_VERSION: u8 = 2;
 
 #[derive(ToSchema, Deserialize, Serialize, Debug)]
 pub struct RouteRequest {
-    #[schema(example = "example@example.com")]
-    user_login: String,
     #[schema(example = "А777МР77")]
-    gos_num: String,
+    pub(crate) gos_num: String,
     #[schema(example = "01.01.2025")]
-    date: String,
+    pub(crate) date: String,
+}
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct RouteQuery {
+    /// Версия ответа: 1 - точки маршрута (по умолчанию), 2 - маршрут со статистикой (DetailedRouteResponse)
+    #[param(example = 2)]
+    version: Option<u8>,
 }
 
 #[derive(ToSchema, Deserialize, Serialize, Debug)]
@@ -24,67 +38,82 @@ pub struct RouteResponse {
     pub route: Option<Vec<PointData>>,
 }
 
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct DetailedRouteResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub route: Option<DetailedRoute>,
+}
+
 #[axum::debug_handler]
 #[utoipa::path(
     post,
     path = "/car/route",
     summary = "Получение маршрута",
-    description = "Получение маршрута по гос.номеру и дате",
+    description = "Получение маршрута по гос.номеру и дате. \
+        С параметром version=2 возвращается DetailedRouteResponse: время, камера и радар каждой точки, \
+        расстояние, время и средняя скорость на участках и итоги маршрута",
+    params(RouteQuery),
     request_body = RouteRequest,
     responses(
         (status = StatusCode::OK, description = "Маршрут успешно получен", body = RouteResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Неподдерживаемая версия ответа", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["route"]
 )]
 pub async fn handle_route(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<RouteQuery>,
     ExtractJson(payload): ExtractJson<RouteRequest>,
-) -> Result<Json<RouteResponse>, StatusCode> {
-    let mut status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {} ({:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        query,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("route_getter").await {
-        Some(BLServices::RouteGetService(s)) => s,
-        _ => {
-            log::warn!("Can't get RouteGetService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.route_getter;
 
-    let route = match service
-        .get_car_route(&payload.gos_num, &payload.user_login, &payload.date)
-        .await
-    {
-        Ok(route) => route,
-        Err(e) => match e {
-            ServiceError::InvalidDataError(e) => {
-                status.code =
-                    ResponseStatusCode::from(&e, ResponseStatusCodeType::INVALID_DATA) as isize;
-                status.message = format!("Invalid {e}");
-                let response = RouteResponse {
-                    status,
-                    route: None,
-                };
-                log::warn!("Sended error response {:#?}", response);
-                return Ok(Json(response));
-            }
-            ServiceError::NotFoundError(e) => {
-                status.code =
-                    ResponseStatusCode::from(&e, ResponseStatusCodeType::NOT_FOUNDED_DATA) as isize;
-                status.message = format!("Not founded {e}");
-                let response = RouteResponse {
-                    status,
-                    route: None,
-                };
-                log::warn!("Sended error response {:#?}", response);
-                return Ok(Json(response));
-            }
-            _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
-        },
-    };
+    match query.version {
+        None | Some(1) => {
+            let (status, route) = match service
+                .get_car_route(&payload.gos_num, &user.email, &payload.date)
+                .await
+            {
+                Ok(route) => (StatusResponse::new(), route),
+                Err(e) => (error_status(e)?, None),
+            };
 
-    let response = RouteResponse { status, route };
-    log::info!("Sended response {:#?}", response);
+            let response = RouteResponse { status, route };
+            log::info!("Sended response {:#?}", response);
+            Ok(Json(response).into_response())
+        }
+        Some(DETAILED_ROUTE_VERSION) => {
+            let (status, route) = match service
+                .get_car_route_detailed(&payload.gos_num, &user.email, &payload.date)
+                .await
+            {
+                Ok(route) => (StatusResponse::new(), route),
+                Err(e) => (error_status(e)?, None),
+            };
 
-    Ok(Json(response))
+            let response = DetailedRouteResponse { status, route };
+            log::info!("Sended response {:#?}", response);
+            Ok(Json(response).into_response())
+        }
+        Some(version) => Ok(status_response(
+            StatusCode::BAD_REQUEST,
+            StatusResponse {
+                code: ResponseStatusCode::INVALID_API_VERSION as isize,
+                message: format!("Invalid api version: {version}"),
+            },
+        )),
+    }
 }
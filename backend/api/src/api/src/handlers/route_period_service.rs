@@ -0,0 +1,68 @@
+use super::error_status;
+use super::route_get_service::DetailedRouteResponse;
+use super::BusinessServices;
+use super::CurrentUser;
+use crate::paths::ROUTE_BY_PERIOD_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::StatusResponse;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RoutePeriodRequest {
+    #[schema(example = "А777МР77")]
+    gos_num: String,
+    #[schema(example = "01.01.2025 22:00")]
+    from: String,
+    #[schema(example = "02.01.2025 06:00")]
+    to: String,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/car/route/by-period",
+    summary = "Получение маршрута за период",
+    description = "Получение маршрута по гос.номеру между датой и временем начала и конца периода. \
+        Точки упорядочены по времени снимка, период не может превышать 7 суток",
+    request_body = RoutePeriodRequest,
+    responses(
+        (status = StatusCode::OK, description = "Маршрут успешно получен", body = DetailedRouteResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["route"]
+)]
+pub async fn handle_route_by_period(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<RoutePeriodRequest>,
+) -> Result<Json<DetailedRouteResponse>, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.route_getter;
+
+    let (status, route) = match service
+        .get_car_route_by_period(&payload.gos_num, &user.email, &payload.from, &payload.to)
+        .await
+    {
+        Ok(route) => (StatusResponse::new(), route),
+        Err(e) => (error_status(e)?, None),
+    };
+
+    let response = DetailedRouteResponse { status, route };
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response))
+}
@@ -0,0 +1,82 @@
+use super::error_status;
+use super::route_get_service::RouteRequest;
+use super::BusinessServices;
+use super::CurrentUser;
+use crate::paths::ROUTE_TRIPS_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    Json,
+};
+use models::RouteTrips;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::StatusResponse;
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct RouteTripsQuery {
+    /// Перерыв между фиксациями (мин), после которого начинается новая поездка. По умолчанию из настроек
+    #[param(example = 15)]
+    gap_minutes: Option<u32>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct RouteTripsResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub route: Option<RouteTrips>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/car/route/trips",
+    summary = "Поездки и стоянки",
+    description = "Разбиение маршрута за дату на поездки по перерывам между фиксациями. \
+        Каждая поездка возвращается отдельной линией с камерами и временем начала и конца, \
+        между поездками - стоянки со временем стоянки",
+    params(RouteTripsQuery),
+    request_body = RouteRequest,
+    responses(
+        (status = StatusCode::OK, description = "Поездки успешно получены", body = RouteTripsResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["route"]
+)]
+pub async fn handle_route_trips(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<RouteTripsQuery>,
+    ExtractJson(payload): ExtractJson<RouteRequest>,
+) -> Result<Json<RouteTripsResponse>, StatusCode> {
+    log::info!(
+        "Received request from {} by {} ({:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        query,
+        payload
+    );
+
+    let service = services.route_getter;
+
+    let (status, route) = match service
+        .get_car_trips(
+            &payload.gos_num,
+            &user.email,
+            &payload.date,
+            query.gap_minutes,
+        )
+        .await
+    {
+        Ok(route) => (StatusResponse::new(), route),
+        Err(e) => (error_status(e)?, None),
+    };
+
+    let response = RouteTripsResponse { status, route };
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response))
+}
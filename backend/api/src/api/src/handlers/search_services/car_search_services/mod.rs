@@ -1,10 +1,18 @@
-use super::search_requests::{SearchByFIORequest, SearchByGosNumRequest, SearchByPassportRequest};
+use super::search_requests::{
+    PageQuery, SearchByFIORequest, SearchByGosNumRequest, SearchByPassportRequest,
+};
+use super::CurrentUser;
 use super::StatusResponse;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
-use models::Car;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::{Car, DetailedCar};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 pub mod search_by_filters;
 pub mod search_by_fio;
@@ -12,7 +20,7 @@ pub mod search_by_gos_num_mask;
 pub mod search_by_passport;
 
 pub use search_by_filters::{
-    SearchCarByFilterRequest, __path_handle_search_cars_by_filters, handle_search_cars_by_filters,
+    __path_handle_search_cars_by_filters, handle_search_cars_by_filters, SearchCarByFilterRequest,
 };
 pub use search_by_fio::{__path_handle_search_car_by_fio, handle_search_car_by_fio};
 pub use search_by_gos_num_mask::{
@@ -26,4 +34,53 @@ pub struct CarSearcherResponse {
     pub status: StatusResponse,
     #[schema(example = json!([]))]
     pub cars: Vec<Car>,
+    // Число найденных автомобилей без учета страницы
+    #[schema(example = 0)]
+    pub total: usize,
+}
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct CarSearchQuery {
+    /// Вернуть регистрационные карточки автомобилей (DetailedCarSearcherResponse)
+    #[param(example = true)]
+    #[serde(default)]
+    pub detail: bool,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct DetailedCarSearcherResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    #[schema(example = json!([]))]
+    pub cars: Vec<DetailedCar>,
+    #[schema(example = 0)]
+    pub total: usize,
+}
+
+// Ответ с ошибкой поиска отдается в исходном виде
+pub(crate) async fn search_response(
+    services: &BusinessServices,
+    response: CarSearcherResponse,
+    query: &CarSearchQuery,
+) -> Result<Response, StatusCode> {
+    if !query.detail || response.status.code != ResponseStatusCode::OK as isize {
+        log::info!("Sended response {:#?}", response);
+        return Ok(Json(response).into_response());
+    }
+
+    let cars = services
+        .searcher
+        .get_detailed_cars(&response.cars)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get registration cards: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let response = DetailedCarSearcherResponse {
+        status: response.status,
+        cars,
+        total: response.total,
+    };
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response).into_response())
 }
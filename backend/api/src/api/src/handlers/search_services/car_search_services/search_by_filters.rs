@@ -1,9 +1,15 @@
-use super::CarSearcherResponse;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::CurrentUser;
+use super::PageQuery;
+use super::{search_response, CarSearchQuery, CarSearcherResponse};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
 use crate::paths::TRACK_INFO_SEARCH_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
-use models::Document;
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    response::Response,
+};
+use models::{CarFilter, Document};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -21,45 +27,87 @@ pub struct SearchCarByFilterRequest {
     pub passport: Option<Document>,
     #[schema(example = "А*23**99")]
     pub gos_num: Option<String>,
+    #[schema(example = "JTDBR32E")]
+    pub vin: Option<String>,
+    #[schema(example = "Toyota")]
+    pub mark: Option<String>,
+    #[schema(example = "Camry")]
+    pub model: Option<String>,
+    #[schema(example = "White")]
+    pub color: Option<String>,
+    #[schema(example = 2015)]
+    pub year_from: Option<u16>,
+    #[schema(example = 2020)]
+    pub year_to: Option<u16>,
+    #[schema(example = "petrol")]
+    pub engine_type: Option<String>,
+    #[schema(example = "B")]
+    pub car_class: Option<String>,
+}
+
+impl SearchCarByFilterRequest {
+    fn filter(self) -> CarFilter {
+        CarFilter {
+            firstname: self.name,
+            surname: self.surname,
+            lastname: self.lastname,
+            passport: self.passport,
+            gos_num_mask: self.gos_num,
+            vin: self.vin,
+            mark: self.mark,
+            model: self.model,
+            color: self.color,
+            year_from: self.year_from,
+            year_to: self.year_to,
+            engine_type: self.engine_type,
+            car_class: self.car_class,
+        }
+    }
 }
 
 #[utoipa::path(
     post,
     path = "/car/search",
     summary = "Поиск автомобилей",
-    description = "Поиск автомобилей по общим фильтрам",
+    description = "Поиск автомобилей по общим фильтрам: ФИО и паспорт владельца, маска гос.номера, \
+        начало VIN, марка, модель, цвет, диапазон годов выпуска, тип двигателя и класс автомобиля. \
+        С параметром detail=true возвращается DetailedCarSearcherResponse с регистрационными карточками автомобилей",
+    params(CarSearchQuery, PageQuery),
     request_body = SearchCarByFilterRequest,
     responses(
         (status = StatusCode::OK, description = "Автомобили успешно найдены", body = CarSearcherResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["search", "car"]
 )]
 pub async fn handle_search_cars_by_filters(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<CarSearchQuery>,
+    Query(paging): Query<PageQuery>,
     ExtractJson(payload): ExtractJson<SearchCarByFilterRequest>,
-) -> Result<Json<CarSearcherResponse>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let mut status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+    log::info!(
+        "Received request from {} by {} ({:?} {:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        query,
+        paging,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("searcher").await {
-        Some(BLServices::SearchService(s)) => s,
-        _ => {
-            log::warn!("Can't get SearchService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = &services.searcher;
 
-    let response = match service
-        .search_car(
-            payload.name,
-            payload.surname,
-            payload.lastname,
-            payload.passport,
-            payload.gos_num,
-        )
-        .await
-    {
-        Ok(cars) => CarSearcherResponse { status, cars },
+    let response = match service.search_car(payload.filter(), &paging.page()).await {
+        Ok(cars) => CarSearcherResponse {
+            status,
+            cars: cars.items,
+            total: cars.total,
+        },
         Err(e) => match e {
             ServiceError::InvalidDataError(e) => {
                 status.code =
@@ -68,12 +116,12 @@ pub async fn handle_search_cars_by_filters(
                 CarSearcherResponse {
                     status,
                     cars: vec![],
+                    total: 0,
                 }
             }
             _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
         },
     };
 
-    log::info!("Sended response {:#?}", response);
-    Ok(Json(response))
+    search_response(&services, response, &query).await
 }
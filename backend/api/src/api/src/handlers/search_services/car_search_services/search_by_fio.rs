@@ -1,8 +1,15 @@
-use super::{CarSearcherResponse, SearchByFIORequest};
+use super::CurrentUser;
+use super::PageQuery;
+use super::{search_response, CarSearchQuery, CarSearcherResponse, SearchByFIORequest};
 
-use super::{BLServices, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
+use super::{ResponseStatusCode, ResponseStatusCodeType};
 use crate::paths::CAR_SEARCH_BY_FIO_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    response::Response,
+};
 
 use super::StatusResponse;
 
@@ -11,36 +18,66 @@ use super::StatusResponse;
     post,
     path = "/car/search/by-fio",
     summary = "Поиск автомобиля",
-    description = "Поиск автомобиля по ФИО владельца",
+    description = "Поиск автомобиля по ФИО владельца. \
+        С параметром detail=true возвращается DetailedCarSearcherResponse с регистрационными карточками автомобилей",
+    params(CarSearchQuery, PageQuery),
     request_body = SearchByFIORequest,
     responses(
         (status = StatusCode::OK, description = "Автомобили успешно найдены", body = CarSearcherResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["search", "car"]
 )]
 pub async fn handle_search_car_by_fio(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<CarSearchQuery>,
+    Query(paging): Query<PageQuery>,
     ExtractJson(payload): ExtractJson<SearchByFIORequest>,
-) -> Result<Json<CarSearcherResponse>, StatusCode> {
-    let status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+) -> Result<Response, StatusCode> {
+    let mut status = StatusResponse::new();
+    log::info!(
+        "Received request from {} by {} ({:?} {:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        query,
+        paging,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("searcher").await {
-        Some(BLServices::SearchService(s)) => s,
-        _ => {
-            log::warn!("Can't get SearchService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = &services.searcher;
 
     let response = match service
-        .search_cars_by_owner_fio(payload.name, payload.surname, payload.lastname)
+        .search_cars_by_owner_fio(
+            payload.name,
+            payload.surname,
+            payload.lastname,
+            &paging.page(),
+        )
         .await
     {
-        Ok(cars) => CarSearcherResponse { status, cars },
-        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+        Ok(cars) => CarSearcherResponse {
+            status,
+            cars: cars.items,
+            total: cars.total,
+        },
+        Err(e) => match e {
+            ServiceError::InvalidDataError(e) => {
+                status.code =
+                    ResponseStatusCode::from(&e, ResponseStatusCodeType::INVALID_DATA) as isize;
+                status.message = format!("Invalid {e}");
+                CarSearcherResponse {
+                    status,
+                    cars: vec![],
+                    total: 0,
+                }
+            }
+            _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
     };
 
-    log::info!("Sended response {:#?}", response);
-    Ok(Json(response))
+    search_response(&services, response, &query).await
 }
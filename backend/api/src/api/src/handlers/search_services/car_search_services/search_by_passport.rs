@@ -1,9 +1,15 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
-use super::{CarSearcherResponse, SearchByPassportRequest};
+use super::CurrentUser;
+use super::PageQuery;
+use super::{search_response, CarSearchQuery, CarSearcherResponse, SearchByPassportRequest};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
 
 use crate::paths::CAR_SEARCH_BY_PASSPORT_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    response::Response,
+};
 
 use super::StatusResponse;
 
@@ -12,33 +18,47 @@ use super::StatusResponse;
     post,
     path = "/car/search/by-passport",
     summary = "Поиск автомобиля",
-    description = "Поиск автомобиля по паспортным данным",
+    description = "Поиск автомобиля по паспортным данным. \
+        С параметром detail=true возвращается DetailedCarSearcherResponse с регистрационными карточками автомобилей",
+    params(CarSearchQuery, PageQuery),
     request_body = SearchByPassportRequest,
     responses(
         (status = StatusCode::OK, description = "Автомобили успешно найдены", body = CarSearcherResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["search", "car"]
 )]
 pub async fn handle_search_car_by_passport(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<CarSearchQuery>,
+    Query(paging): Query<PageQuery>,
     ExtractJson(payload): ExtractJson<SearchByPassportRequest>,
-) -> Result<Json<CarSearcherResponse>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let mut status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+    log::info!(
+        "Received request from {} by {} ({:?} {:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        query,
+        paging,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("searcher").await {
-        Some(BLServices::SearchService(s)) => s,
-        _ => {
-            log::warn!("Can't get SearchService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = &services.searcher;
 
     let response = match service
-        .search_cars_by_owner_passport(&payload.passport)
+        .search_cars_by_owner_passport(&payload.passport, &paging.page())
         .await
     {
-        Ok(cars) => CarSearcherResponse { status, cars },
+        Ok(cars) => CarSearcherResponse {
+            status,
+            cars: cars.items,
+            total: cars.total,
+        },
         Err(e) => match e {
             ServiceError::InvalidDataError(e) => {
                 status.code =
@@ -47,12 +67,12 @@ pub async fn handle_search_car_by_passport(
                 CarSearcherResponse {
                     status,
                     cars: vec![],
+                    total: 0,
                 }
             }
             _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
         },
     };
 
-    log::info!("Sended response {:#?}", response);
-    Ok(Json(response))
+    search_response(&services, response, &query).await
 }
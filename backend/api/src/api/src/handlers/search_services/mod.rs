@@ -3,6 +3,7 @@ pub mod track_info_search_services;
 
 pub mod search_requests;
 
+use super::CurrentUser;
 use super::StatusResponse;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
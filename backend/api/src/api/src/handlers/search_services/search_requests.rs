@@ -1,6 +1,6 @@
-use models::Document;
+use models::{Document, Page, SortKey};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(ToSchema, Deserialize, Serialize, Debug)]
 pub struct SearchByFIORequest {
@@ -14,9 +14,9 @@ pub struct SearchByFIORequest {
 
 #[derive(ToSchema, Deserialize, Serialize, Debug)]
 pub struct SearchByPassportRequest {
-    #[schema(example = json!({ 
-        "serial": "1111", 
-        "number": "111111" 
+    #[schema(example = json!({
+        "serial": "1111",
+        "number": "111111"
     }))]
     pub passport: Document,
 }
@@ -32,3 +32,28 @@ pub struct SearchByDateRequest {
     #[schema(example = "01.01.2025")]
     pub date: String,
 }
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct PageQuery {
+    /// Число пропускаемых записей
+    #[param(example = 0)]
+    #[serde(default)]
+    pub offset: usize,
+    /// Размер страницы, по умолчанию 20, не больше 100
+    #[param(example = 20)]
+    pub limit: Option<usize>,
+    /// Ключ сортировки, по умолчанию gos_num
+    #[param(example = "owner_surname")]
+    pub sort: Option<SortKey>,
+}
+
+impl PageQuery {
+    pub fn page(&self) -> Page {
+        let default = Page::default();
+        Page {
+            offset: self.offset,
+            limit: self.limit.unwrap_or(default.limit),
+            sort: self.sort.unwrap_or(default.sort),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use super::search_requests::*;
+use super::CurrentUser;
 use super::StatusResponse;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
 use models::TrackInfo;
 use serde::{Deserialize, Serialize};
@@ -16,8 +17,8 @@ pub use search_by_date::{
     __path_handle_search_track_info_by_date, handle_search_track_info_by_date,
 };
 pub use search_by_filters::{
-    SearchTrackInfoByFilterRequest, __path_handle_search_track_info_by_filters,
-    handle_search_track_info_by_filters,
+    __path_handle_search_track_info_by_filters, handle_search_track_info_by_filters,
+    SearchTrackInfoByFilterRequest,
 };
 pub use search_by_fio::{__path_handle_search_track_info_by_fio, handle_search_track_info_by_fio};
 pub use search_by_gos_num_mask::{
@@ -33,4 +34,7 @@ pub struct TrackInfoSearcherResponse {
     pub status: StatusResponse,
     #[schema(example = json!([]))]
     pub track_info: Vec<TrackInfo>,
+    // Число найденных записей без учета страницы
+    #[schema(example = 0)]
+    pub total: usize,
 }
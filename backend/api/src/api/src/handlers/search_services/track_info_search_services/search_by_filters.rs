@@ -1,8 +1,14 @@
+use super::CurrentUser;
+use super::PageQuery;
 use super::TrackInfoSearcherResponse;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
 use crate::paths::TRACK_INFO_SEARCH_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    Json,
+};
 use models::Document;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -30,26 +36,33 @@ pub struct SearchTrackInfoByFilterRequest {
     path = "/track-info/search",
     summary = "Поиск отслеживаний",
     description = "Поиск отслеживаний по общим фильтрам",
+    params(PageQuery),
     request_body = SearchTrackInfoByFilterRequest,
     responses(
         (status = StatusCode::OK, description = "Информация об отслеживании успешно найдена", body = TrackInfoSearcherResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["search", "track-info"]
 )]
 pub async fn handle_search_track_info_by_filters(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(paging): Query<PageQuery>,
     ExtractJson(payload): ExtractJson<SearchTrackInfoByFilterRequest>,
 ) -> Result<Json<TrackInfoSearcherResponse>, StatusCode> {
     let mut status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+    log::info!(
+        "Received request from {} by {} ({:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        paging,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("searcher").await {
-        Some(BLServices::SearchService(s)) => s,
-        _ => {
-            log::warn!("Can't get SearchService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.searcher;
 
     let response = match service
         .search_track_info(
@@ -59,10 +72,15 @@ pub async fn handle_search_track_info_by_filters(
             payload.passport,
             payload.gos_num,
             payload.date,
+            &paging.page(),
         )
         .await
     {
-        Ok(track_info) => TrackInfoSearcherResponse { status, track_info },
+        Ok(track_info) => TrackInfoSearcherResponse {
+            status,
+            track_info: track_info.items,
+            total: track_info.total,
+        },
         Err(e) => match e {
             ServiceError::InvalidDataError(e) => {
                 status.code =
@@ -71,6 +89,7 @@ pub async fn handle_search_track_info_by_filters(
                 TrackInfoSearcherResponse {
                     status,
                     track_info: vec![],
+                    total: 0,
                 }
             }
             _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
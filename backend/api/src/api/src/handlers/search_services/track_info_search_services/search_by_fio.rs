@@ -1,7 +1,14 @@
-use super::{BLServices, BUSINESS_SERVICES};
+use super::CurrentUser;
+use super::PageQuery;
+use super::{BusinessServices, ServiceError};
+use super::{ResponseStatusCode, ResponseStatusCodeType};
 use super::{SearchByFIORequest, TrackInfoSearcherResponse};
 use crate::paths::TRACK_INFO_SEARCH_BY_FIO_SERVICE_PATH as PATH;
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    Json,
+};
 
 use super::StatusResponse;
 
@@ -10,33 +17,61 @@ use super::StatusResponse;
     path = "/track-info/search/by-fio",
     summary = "Поиск отслеживаний",
     description = "Поиск отслеживаний по ФИО пользователя",
+    params(PageQuery),
     request_body = SearchByFIORequest,
     responses(
         (status = StatusCode::OK, description = "Информация об отслеживании успешно найдена", body = TrackInfoSearcherResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["search", "track-info"]
 )]
 pub async fn handle_search_track_info_by_fio(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(paging): Query<PageQuery>,
     ExtractJson(payload): ExtractJson<SearchByFIORequest>,
 ) -> Result<Json<TrackInfoSearcherResponse>, StatusCode> {
-    let status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+    let mut status = StatusResponse::new();
+    log::info!(
+        "Received request from {} by {} ({:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        paging,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("searcher").await {
-        Some(BLServices::SearchService(s)) => s,
-        _ => {
-            log::warn!("Can't get SearchService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.searcher;
 
     let response = match service
-        .search_track_info_by_owner_fio(payload.name, payload.surname, payload.lastname)
+        .search_track_info_by_owner_fio(
+            payload.name,
+            payload.surname,
+            payload.lastname,
+            &paging.page(),
+        )
         .await
     {
-        Ok(track_info) => TrackInfoSearcherResponse { status, track_info },
-        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+        Ok(track_info) => TrackInfoSearcherResponse {
+            status,
+            track_info: track_info.items,
+            total: track_info.total,
+        },
+        Err(e) => match e {
+            ServiceError::InvalidDataError(e) => {
+                status.code =
+                    ResponseStatusCode::from(&e, ResponseStatusCodeType::INVALID_DATA) as isize;
+                status.message = format!("Invalid {e}");
+                TrackInfoSearcherResponse {
+                    status,
+                    track_info: vec![],
+                    total: 0,
+                }
+            }
+            _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
     };
 
     log::info!("Sended response {:#?}", response);
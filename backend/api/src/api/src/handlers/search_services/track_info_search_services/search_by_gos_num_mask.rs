@@ -1,9 +1,15 @@
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::CurrentUser;
+use super::PageQuery;
+use super::{BusinessServices, ServiceError};
 use super::{ResponseStatusCode, ResponseStatusCodeType};
 use super::{SearchByGosNumRequest, TrackInfoSearcherResponse};
 use crate::paths::TRACK_INFO_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH as PATH;
 
-use axum::{extract::Json as ExtractJson, http::StatusCode, Json};
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    Json,
+};
 
 use super::StatusResponse;
 
@@ -12,32 +18,43 @@ use super::StatusResponse;
     path = "/track-info/search/by-gos-num-mask",
     summary = "Поиск отслеживаний",
     description = "Поиск отслеживаний по маске гос.номера",
+    params(PageQuery),
     request_body = SearchByGosNumRequest,
     responses(
         (status = StatusCode::OK, description = "Информация об отслеживании успешно найдена", body = TrackInfoSearcherResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["search", "track-info"]
 )]
 pub async fn handle_search_track_info_by_gos_num_mask(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(paging): Query<PageQuery>,
     ExtractJson(payload): ExtractJson<SearchByGosNumRequest>,
 ) -> Result<Json<TrackInfoSearcherResponse>, StatusCode> {
     let mut status = StatusResponse::new();
-    log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
+    log::info!(
+        "Received request from {} by {} ({:?}): {:?}",
+        PATH.as_str(),
+        user.email,
+        paging,
+        payload
+    );
 
-    let service = match BUSINESS_SERVICES::get("searcher").await {
-        Some(BLServices::SearchService(s)) => s,
-        _ => {
-            log::warn!("Can't get SearchService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let service = services.searcher;
 
     let response = match service
-        .search_track_info_by_gos_num_mask(&payload.gos_num)
+        .search_track_info_by_gos_num_mask(&payload.gos_num, &paging.page())
         .await
     {
-        Ok(track_info) => TrackInfoSearcherResponse { status, track_info },
+        Ok(track_info) => TrackInfoSearcherResponse {
+            status,
+            track_info: track_info.items,
+            total: track_info.total,
+        },
         Err(e) => match e {
             ServiceError::InvalidDataError(e) => {
                 status.code =
@@ -46,6 +63,7 @@ pub async fn handle_search_track_info_by_gos_num_mask(
                 TrackInfoSearcherResponse {
                     status,
                     track_info: vec![],
+                    total: 0,
                 }
             }
             _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
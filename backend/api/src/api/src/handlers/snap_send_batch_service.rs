@@ -0,0 +1,173 @@
+use super::snap_send_service::SnapSendRequest;
+use super::ResponseWithoutData;
+use super::{status_response, BusinessServices, ServiceError};
+use crate::paths::SNAP_SEND_BATCH_SERVICE_PATH as PATH;
+use axum::{
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{ResponseStatusCode, ResponseStatusCodeType, StatusResponse};
+
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+const MAX_BATCH_SIZE: usize = 1000;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct SnapBatchItemResult {
+    #[schema(example = 0)]
+    pub index: usize,
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct SnapSendBatchResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    #[schema(example = 1)]
+    pub accepted: usize,
+    #[schema(example = 0)]
+    pub rejected: usize,
+    pub results: Vec<SnapBatchItemResult>,
+}
+
+// Пустые строки NDJSON пропускаются и не учитываются в индексах
+fn parse_items(
+    body: &str,
+    is_ndjson: bool,
+) -> Result<Vec<Result<SnapSendRequest, String>>, String> {
+    if is_ndjson {
+        return Ok(body
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| e.to_string()))
+            .collect());
+    }
+
+    let values: Vec<serde_json::Value> = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    Ok(values
+        .into_iter()
+        .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+        .collect())
+}
+
+fn invalid_batch_response(message: String) -> Response {
+    status_response(
+        StatusCode::BAD_REQUEST,
+        StatusResponse {
+            code: ResponseStatusCode::INVALID_SNAP_BATCH as isize,
+            message,
+        },
+    )
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/snap/send-batch",
+    summary = "Пакетная отправка снимков",
+    description = "Отправка пакета снимков автомобилей JSON-массивом или NDJSON (Content-Type: application/x-ndjson). \
+        Каждый снимок проверяется отдельно, корректные снимки сохраняются независимо от ошибок в остальных. \
+        Снимки незарегистрированных и выведенных из эксплуатации камер, с расхождением данных камеры с реестром \
+        и со скоростью от камеры без радара отклоняются",
+    request_body(content = Vec<SnapSendRequest>, content_type = "application/json"),
+    responses(
+        (status = StatusCode::OK, description = "Пакет обработан, результат по каждому снимку", body = SnapSendBatchResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректный формат или размер пакета", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["snap"]
+)]
+pub async fn handle_snap_send_batch(
+    State(services): State<BusinessServices>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, StatusCode> {
+    let is_ndjson = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(NDJSON_CONTENT_TYPE));
+    log::info!(
+        "Received request from {} ({} bytes, ndjson: {})",
+        PATH.as_str(),
+        body.len(),
+        is_ndjson
+    );
+
+    let items = match parse_items(&body, is_ndjson) {
+        Ok(items) => items,
+        Err(e) => return Ok(invalid_batch_response(format!("Invalid snap batch: {e}"))),
+    };
+
+    if items.len() > MAX_BATCH_SIZE {
+        return Ok(invalid_batch_response(format!(
+            "Invalid snap batch: more than {MAX_BATCH_SIZE} items"
+        )));
+    }
+
+    let mut results: Vec<Option<StatusResponse>> = Vec::with_capacity(items.len());
+    let mut snaps = Vec::new();
+    for item in items {
+        match item.and_then(SnapSendRequest::into_snap) {
+            Ok(snap) => {
+                snaps.push(snap);
+                results.push(None);
+            }
+            Err(e) => results.push(Some(StatusResponse {
+                code: ResponseStatusCode::INVALID_SNAP as isize,
+                message: format!("Invalid snap: {e}"),
+            })),
+        }
+    }
+
+    let service = services.snap_sender;
+
+    let mut inserted = match service.insert_snaps(&snaps).await {
+        Ok(inserted) => inserted.into_iter(),
+        Err(e) => {
+            log::error!("Failed to insert snap batch: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let results: Vec<SnapBatchItemResult> = results
+        .into_iter()
+        .enumerate()
+        .map(|(index, parsed)| {
+            let status = parsed.unwrap_or_else(|| match inserted.next() {
+                Some(Err(ServiceError::InvalidDataError(e))) => StatusResponse {
+                    code: ResponseStatusCode::from(&e, ResponseStatusCodeType::INVALID_DATA)
+                        as isize,
+                    message: format!("Invalid {e}"),
+                },
+                Some(Err(e)) => StatusResponse {
+                    code: ResponseStatusCode::UNKNOWN_ERROR as isize,
+                    message: e.to_string(),
+                },
+                _ => StatusResponse::new(),
+            });
+            SnapBatchItemResult { index, status }
+        })
+        .collect();
+
+    let accepted = results
+        .iter()
+        .filter(|r| r.status.code == ResponseStatusCode::OK as isize)
+        .count();
+    let response = SnapSendBatchResponse {
+        status: StatusResponse::new(),
+        accepted,
+        rejected: results.len() - accepted,
+        results,
+    };
+    log::info!("Sended response {:#?}", response);
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
@@ -1,13 +1,13 @@
 use super::ResponseWithoutData;
-use super::{BLServices, ServiceError, BUSINESS_SERVICES};
+use super::{BusinessServices, ServiceError};
 use crate::paths::SNAP_SEND_SERVICE_PATH as PATH;
 use axum::{
-    extract::Json as ExtractJson,
+    extract::{Json as ExtractJson, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
-use models::Camera;
+use models::{Camera, IncomingSnap};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -23,14 +23,42 @@ pub struct SnapSendRequest {
     date: String,
     #[schema(example = "А777МР77")]
     gos_num: String,
-    #[schema(example = json!(
+    // Данные камеры берутся из реестра по id
+    #[schema(example = 1)]
+    #[serde(default)]
+    camera_id: Option<usize>,
+    // Устаревший формат: данные камеры сверяются с реестром
+    #[schema(example = json!({
         "id": 1,
-        "location": { 
-            "longitude": 54.98989, 
-            "latitude": 56.89882 
+        "is_radar": true,
+        "location": {
+            "longitude": 54.98989,
+            "latitude": 56.89882
         }
-    ))]
-    camera: Camera,
+    }))]
+    #[serde(default)]
+    camera: Option<Camera>,
+    // Повторная отправка с тем же ключом не создает новый снимок
+    #[schema(example = "cam1-000042")]
+    #[serde(default)]
+    idempotency_key: Option<String>,
+}
+
+impl SnapSendRequest {
+    pub(crate) fn into_snap(self) -> Result<IncomingSnap, String> {
+        let Some(camera_id) = self.camera_id.or(self.camera.as_ref().map(|c| c.id)) else {
+            return Err("camera id is missing".to_string());
+        };
+        Ok(IncomingSnap {
+            camera_id,
+            reported_camera: self.camera,
+            speed: self.speed,
+            time: self.time,
+            date: self.date,
+            gos_num: self.gos_num,
+            idempotency_key: self.idempotency_key,
+        })
+    }
 }
 
 #[axum::debug_handler]
@@ -38,39 +66,43 @@ pub struct SnapSendRequest {
     put,
     path = "/snap/send",
     summary = "Отравление снимка",
-    description = "Отравление снимка автомобиля",
+    description = "Отравление снимка автомобиля. Повторная отправка снимка с тем же ключом идемпотентности \
+        или с той же камеры, номером и временем подтверждается без создания нового снимка. \
+        Снимок принимается только от зарегистрированной камеры в эксплуатации, данные камеры берутся из реестра по camera_id. \
+        Переданные данные камеры должны совпадать с реестром, скорость принимается только от камеры с радаром",
     request_body = SnapSendRequest,
     responses(
         (status = StatusCode::CREATED, description = "Снимок успешно добавлен", body = ResponseWithoutData),
         (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
     ),
+    security(("bearer_auth" = [])),
     tags = ["snap"]
 )]
 pub async fn handle_snap_send(
+    State(services): State<BusinessServices>,
     ExtractJson(payload): ExtractJson<SnapSendRequest>,
 ) -> Result<Response, StatusCode> {
     let mut status = StatusResponse::new();
     log::info!("Received request from {}: {:?}", PATH.as_str(), payload);
 
-    let service = match BUSINESS_SERVICES::get("snap_sender").await {
-        Some(BLServices::SnapSendService(s)) => s,
-        _ => {
-            log::warn!("Can't get SnapSendService");
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    let service = services.snap_sender;
+
+    let snap = match payload.into_snap() {
+        Ok(snap) => snap,
+        Err(e) => {
+            status.code = ResponseStatusCode::INVALID_SNAP as isize;
+            status.message = format!("Invalid snap: {e}");
+            let response = ResponseWithoutData { status };
+            log::warn!("Sended error response {:#?}", response);
+
+            return Ok((StatusCode::BAD_REQUEST, Json(response)).into_response());
         }
     };
 
-    match service
-        .insert_snap(
-            &payload.camera,
-            payload.speed,
-            &payload.time,
-            &payload.date,
-            &payload.gos_num,
-        )
-        .await
-    {
+    match service.insert_snap(&snap).await {
         Ok(_) => {}
         Err(e) => match e {
             ServiceError::InvalidDataError(e) => {
@@ -0,0 +1,85 @@
+use super::error_status;
+use super::BusinessServices;
+use super::CurrentUser;
+use crate::paths::SPEED_VIOLATION_SEARCH_SERVICE_PATH as PATH;
+use axum::{
+    extract::{Json as ExtractJson, State},
+    http::StatusCode,
+    Json,
+};
+use models::SpeedViolation;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::StatusResponse;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct SpeedViolationSearchRequest {
+    /// Гос.номер. Без него возвращаются нарушения всех автомобилей
+    #[schema(example = "А123АА777")]
+    pub gos_num: Option<String>,
+    /// id камеры. Без него возвращаются нарушения на всех камерах
+    #[schema(example = 3)]
+    pub camera_id: Option<usize>,
+    #[schema(example = "01.01.2025 00:00")]
+    pub from: String,
+    #[schema(example = "07.01.2025 23:59")]
+    pub to: String,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct SpeedViolationsResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub violations: Option<Vec<SpeedViolation>>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/violation/search",
+    summary = "Нарушения скоростного режима",
+    description = "Нарушения скоростного режима за период (не более 31 дня) \
+        с фильтрацией по гос.номеру и камере. Нарушение фиксируется, когда скорость \
+        превышает ограничение камеры больше чем на допустимое превышение",
+    request_body = SpeedViolationSearchRequest,
+    responses(
+        (status = StatusCode::OK, description = "Нарушения успешно получены", body = SpeedViolationsResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["violation"]
+)]
+pub async fn handle_search_speed_violations(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<SpeedViolationSearchRequest>,
+) -> Result<Json<SpeedViolationsResponse>, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.speed_controller;
+
+    let (status, violations) = match service
+        .get_violations(
+            payload.gos_num.as_deref(),
+            payload.camera_id,
+            &payload.from,
+            &payload.to,
+        )
+        .await
+    {
+        Ok(violations) => (StatusResponse::new(), Some(violations)),
+        Err(e) => (error_status(e)?, None),
+    };
+
+    let response = SpeedViolationsResponse { status, violations };
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response))
+}
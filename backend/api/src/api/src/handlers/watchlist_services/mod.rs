@@ -0,0 +1,7 @@
+pub mod watchlist_alert_services;
+pub mod watchlist_entry_services;
+
+use super::error_response;
+use super::BusinessServices;
+use super::CurrentUser;
+use super::{ResponseWithoutData, StatusResponse};
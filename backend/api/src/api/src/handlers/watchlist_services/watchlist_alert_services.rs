@@ -0,0 +1,137 @@
+use super::error_response;
+use super::BusinessServices;
+use super::CurrentUser;
+use super::{ResponseWithoutData, StatusResponse};
+use crate::handlers::live_track_service::LiveLaggedEvent;
+use crate::paths::{WATCHLIST_ALERTS_LIVE_SERVICE_PATH, WATCHLIST_ALERTS_SERVICE_PATH};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Response, Sse,
+    },
+    Json,
+};
+use di_container::alert_hub::{AlertEvent, AlertSubscription};
+use futures_util::stream::{self, Stream};
+use models::WatchlistAlert;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use utoipa::{IntoParams, ToSchema};
+
+const ALERT_EVENT: &str = "alert";
+const LAGGED_EVENT: &str = "lagged";
+
+#[derive(IntoParams, Deserialize, Debug)]
+pub struct WatchlistAlertsQuery {
+    /// Дата фиксаций
+    #[param(example = "01.01.2025")]
+    date: String,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct WatchlistAlertsResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub alerts: Option<Vec<WatchlistAlert>>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/watchlist/alerts",
+    summary = "Фиксации разыскиваемых автомобилей",
+    description = "Фиксации автомобилей из списка розыска за дату с камерой и местом фиксации",
+    params(WatchlistAlertsQuery),
+    responses(
+        (status = StatusCode::OK, description = "Фиксации успешно получены", body = WatchlistAlertsResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["watchlist"]
+)]
+pub async fn handle_get_watchlist_alerts(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<WatchlistAlertsQuery>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        WATCHLIST_ALERTS_SERVICE_PATH.as_str(),
+        user.email,
+        query
+    );
+
+    let service = services.watchlist;
+
+    match service.get_alerts_by_date(&query.date).await {
+        Ok(alerts) => {
+            let response = WatchlistAlertsResponse {
+                status: StatusResponse::new(),
+                alerts: Some(alerts),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+fn to_sse_event(event: AlertEvent) -> Event {
+    let res = match event {
+        AlertEvent::Item(alert) => Event::default().event(ALERT_EVENT).json_data(&*alert),
+        AlertEvent::Lagged(skipped) => Event::default()
+            .event(LAGGED_EVENT)
+            .json_data(LiveLaggedEvent { skipped }),
+    };
+
+    res.unwrap_or_else(|e| {
+        log::error!("Failed to serialize alert event: {}", e);
+        Event::default().comment("serialization error")
+    })
+}
+
+fn alerts_stream(subscription: AlertSubscription) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.next().await?;
+        Some((Ok(to_sse_event(event)), subscription))
+    })
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/watchlist/alerts/live",
+    summary = "Фиксации разыскиваемых автомобилей в реальном времени",
+    description = "Поток Server-Sent Events с фиксациями, записанными после подписки. \
+        Событие alert содержит фиксацию (WatchlistAlert), событие lagged (LiveLaggedEvent) - \
+        количество фиксаций, пропущенных медленным клиентом. Поток завершается при остановке сервера",
+    responses(
+        (status = StatusCode::OK, description = "Поток событий", content_type = "text/event-stream"),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["watchlist"]
+)]
+pub async fn handle_watchlist_alerts_live(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+) -> Response {
+    log::info!(
+        "Received request from {} by {}",
+        WATCHLIST_ALERTS_LIVE_SERVICE_PATH.as_str(),
+        user.email
+    );
+
+    let subscription = services.watchlist.subscribe_alerts();
+    log::info!("Started watchlist alerts stream for {}", user.email);
+
+    Sse::new(alerts_stream(subscription))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
@@ -0,0 +1,230 @@
+use super::error_response;
+use super::BusinessServices;
+use super::CurrentUser;
+use super::{ResponseWithoutData, StatusResponse};
+use crate::paths::{WATCHLIST_ENTRY_SERVICE_PATH, WATCHLIST_PATH};
+use axum::{
+    extract::{Json as ExtractJson, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::WatchlistEntry;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct WatchlistEntryRequest {
+    #[schema(example = "А***МР77")]
+    pub gos_num_mask: String,
+    #[schema(example = "Угон")]
+    pub reason: String,
+    #[schema(example = "31.12.2025 23:59")]
+    pub expires_at: Option<String>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct WatchlistEntryResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub entry: Option<WatchlistEntry>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug)]
+pub struct WatchlistResponse {
+    #[schema(example = json!({ "code": 0, "message": "OK" }))]
+    pub status: StatusResponse,
+    pub entries: Option<Vec<WatchlistEntry>>,
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/watchlist",
+    summary = "Добавление в список розыска",
+    description = "Добавление гос.номера или маски гос.номера в список розыска. \
+        Без срока действия запись активна до удаления",
+    request_body = WatchlistEntryRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Запись добавлена", body = WatchlistEntryResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["watchlist"]
+)]
+pub async fn handle_add_watchlist_entry(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    ExtractJson(payload): ExtractJson<WatchlistEntryRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {:?}",
+        WATCHLIST_PATH.as_str(),
+        user.email,
+        payload
+    );
+
+    let service = services.watchlist;
+
+    match service
+        .add_entry(
+            &payload.gos_num_mask,
+            &payload.reason,
+            payload.expires_at.as_deref(),
+            &user.email,
+        )
+        .await
+    {
+        Ok(entry) => {
+            let response = WatchlistEntryResponse {
+                status: StatusResponse::new(),
+                entry: Some(entry),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::CREATED, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/watchlist",
+    summary = "Список розыска",
+    description = "Получение всех записей списка розыска, включая истекшие",
+    responses(
+        (status = StatusCode::OK, description = "Записи успешно получены", body = WatchlistResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["watchlist"]
+)]
+pub async fn handle_get_watchlist(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+) -> Result<Json<WatchlistResponse>, StatusCode> {
+    log::info!(
+        "Received request from {} by {}",
+        WATCHLIST_PATH.as_str(),
+        user.email
+    );
+
+    let service = services.watchlist;
+
+    let entries = service
+        .get_entries()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let response = WatchlistResponse {
+        status: StatusResponse::new(),
+        entries: Some(entries),
+    };
+    log::info!("Sended response {:#?}", response);
+    Ok(Json(response))
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/watchlist/{id}",
+    summary = "Изменение записи списка розыска",
+    description = "Замена маски гос.номера, причины и срока действия записи",
+    params(("id" = usize, Path, description = "id записи")),
+    request_body = WatchlistEntryRequest,
+    responses(
+        (status = StatusCode::OK, description = "Запись изменена", body = WatchlistEntryResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Некорректные входные параметры", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Запись не найдена", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["watchlist"]
+)]
+pub async fn handle_update_watchlist_entry(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Path(id): Path<usize>,
+    ExtractJson(payload): ExtractJson<WatchlistEntryRequest>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {{ id: {} }} {:?}",
+        WATCHLIST_ENTRY_SERVICE_PATH.as_str(),
+        user.email,
+        id,
+        payload
+    );
+
+    let service = services.watchlist;
+
+    match service
+        .update_entry(
+            id,
+            &payload.gos_num_mask,
+            &payload.reason,
+            payload.expires_at.as_deref(),
+        )
+        .await
+    {
+        Ok(entry) => {
+            let response = WatchlistEntryResponse {
+                status: StatusResponse::new(),
+                entry: Some(entry),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/watchlist/{id}",
+    summary = "Удаление из списка розыска",
+    description = "Удаление записи списка розыска вместе с ее фиксациями",
+    params(("id" = usize, Path, description = "id записи")),
+    responses(
+        (status = StatusCode::OK, description = "Запись удалена", body = ResponseWithoutData),
+        (status = StatusCode::NOT_FOUND, description = "Запись не найдена", body = ResponseWithoutData),
+        (status = StatusCode::UNAUTHORIZED, description = "Пользователь не авторизован"),
+        (status = StatusCode::FORBIDDEN, description = "Недостаточно прав"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Внутренняя ошибка сервера"),
+    ),
+    security(("bearer_auth" = [])),
+    tags = ["watchlist"]
+)]
+pub async fn handle_delete_watchlist_entry(
+    State(services): State<BusinessServices>,
+    CurrentUser(user): CurrentUser,
+    Path(id): Path<usize>,
+) -> Result<Response, StatusCode> {
+    log::info!(
+        "Received request from {} by {}: {{ id: {} }}",
+        WATCHLIST_ENTRY_SERVICE_PATH.as_str(),
+        user.email,
+        id
+    );
+
+    let service = services.watchlist;
+
+    match service.delete_entry(id).await {
+        Ok(_) => {
+            let response = ResponseWithoutData {
+                status: StatusResponse::new(),
+            };
+            log::info!("Sended response {:#?}", response);
+            Ok((StatusCode::OK, Json(response)).into_response())
+        }
+        Err(e) => error_response(e),
+    }
+}
@@ -1,2 +1,4 @@
 pub mod handlers;
 pub mod paths;
+pub mod permissions;
+pub mod routes;
@@ -1,38 +1,34 @@
-mod handlers;
-mod paths;
-
-use axum::{
-    routing::{get, post, put},
-    Router,
-};
+use api::{handlers::ApiDoc, handlers::BusinessServices, paths, permissions, routes};
+use axum::middleware;
+use di_container::DATA_ACCESSES;
+use tokio::signal;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use handlers::{
-    auth_services::{
-        auth_service::handle_auth, passport_confirm_service::handle_passport_conf,
-        registration_service::handle_reg,
-    },
-    camera_service::{
-        camera_get_by_cords_service::handle_get_camera_by_cords,
-        camera_get_by_id_service::handle_get_camera_by_id,
-        get_avg_speed_service::handle_get_avg_speed_for_car_on_camera,
-    },
-    route_get_service::handle_route,
-    search_services::{
-        car_search_services::{
-            handle_search_car_by_fio, handle_search_car_by_gos_num_mask,
-            handle_search_car_by_passport, handle_search_cars_by_filters,
-        },
-        track_info_search_services::{
-            handle_search_track_info_by_date, handle_search_track_info_by_filters,
-            handle_search_track_info_by_fio, handle_search_track_info_by_gos_num_mask,
-            handle_search_track_info_by_passport,
-        },
-    },
-    snap_send_service::handle_snap_send,
-    ApiDoc,
-};
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("Can't install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Can't install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    log::info!("Shutdown signal received, stopping server");
+}
 
 #[tokio::main]
 async fn main() {
@@ -41,70 +37,35 @@ async fn main() {
         true,
     );
 
-    let app = Router::new()
-        .route(&paths::ROUTE_GET_SERVICE_PATH, post(handle_route))
-        .route(&paths::AUTH_SERVICE_PATH, post(handle_auth))
-        .route(&paths::REG_SERVICE_PATH, post(handle_reg))
-        .route(
-            &paths::PASSPORT_CONF_SERVICE_PATH,
-            post(handle_passport_conf),
-        )
-        .route(&paths::SNAP_SEND_SERVICE_PATH, put(handle_snap_send))
-        .route(
-            &paths::CAMERA_GET_BY_ID_SERVICE_PATH,
-            get(handle_get_camera_by_id),
-        )
-        .route(
-            &paths::CAMERA_GET_BY_CORDS_SERVICE_PATH,
-            post(handle_get_camera_by_cords),
-        )
-        .route(
-            &paths::GET_AVG_SPEED_ON_CAMERA_PATH,
-            post(handle_get_avg_speed_for_car_on_camera),
-        )
-        .route(
-            &paths::CAR_SEARCH_SERVICE_PATH,
-            post(handle_search_cars_by_filters),
-        )
-        .route(
-            &paths::CAR_SEARCH_BY_FIO_SERVICE_PATH,
-            post(handle_search_car_by_fio),
-        )
-        .route(
-            &paths::CAR_SEARCH_BY_PASSPORT_SERVICE_PATH,
-            post(handle_search_car_by_passport),
-        )
-        .route(
-            &paths::CAR_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH,
-            post(handle_search_car_by_gos_num_mask),
-        )
-        .route(
-            &paths::TRACK_INFO_SEARCH_SERVICE_PATH,
-            post(handle_search_track_info_by_filters),
-        )
-        .route(
-            &paths::TRACK_INFO_SEARCH_BY_FIO_SERVICE_PATH,
-            post(handle_search_track_info_by_fio),
-        )
-        .route(
-            &paths::TRACK_INFO_SEARCH_BY_DATE_SERVICE_PATH,
-            post(handle_search_track_info_by_date),
-        )
-        .route(
-            &paths::TRACK_INFO_SEARCH_BY_PASSPORT_SERVICE_PATH,
-            post(handle_search_track_info_by_passport),
-        )
-        .route(
-            &paths::TRACK_INFO_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH,
-            post(handle_search_track_info_by_gos_num_mask),
-        )
+    let services = BusinessServices::build()
+        .await
+        .expect("Can't create business services");
+
+    let app = routes::api_router()
+        .route_layer(middleware::from_fn_with_state(
+            services.clone(),
+            permissions::check_permissions,
+        ))
         .merge(
             SwaggerUi::new(paths::DOCS_PATH.as_str())
                 .url(paths::OPENAPI_DOCS_PATH.as_str(), ApiDoc::openapi()),
-        );
+        )
+        .with_state(services.clone());
 
     let addr = cfg::var("server.api_url");
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     log::info!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            // Потоки SSE завершаются только так, иначе сервер ждал бы истечения токенов
+            services.close_live_streams();
+        })
+        .await
+        .unwrap();
+
+    if let Some(repos) = DATA_ACCESSES::shared().await {
+        repos.shutdown().await;
+    }
+    log::info!("Server stopped");
 }
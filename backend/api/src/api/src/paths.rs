@@ -11,19 +11,56 @@ lazy_static! {
     pub static ref CAMERA_PATH: String = "/camera".to_string();
     pub static ref SEARCH_PATH: String = "/search".to_string();
     pub static ref TRACK_INFO_PATH: String = "/track-info".to_string();
+    pub static ref WATCHLIST_PATH: String = "/watchlist".to_string();
+    pub static ref VIOLATION_PATH: String = "/violation".to_string();
+    pub static ref ROAD_SECTION_PATH: String = "/road-section".to_string();
 
     // Route path
     pub static ref ROUTE_GET_SERVICE_PATH: String = format!("{}/route", CAR_PATH.as_str());
+    pub static ref ROUTE_EXPORT_SERVICE_PATH: String =
+        format!("{}/export", ROUTE_GET_SERVICE_PATH.as_str());
+    pub static ref ROUTE_BY_PERIOD_SERVICE_PATH: String =
+        format!("{}/by-period", ROUTE_GET_SERVICE_PATH.as_str());
+    pub static ref ROUTE_TRIPS_SERVICE_PATH: String =
+        format!("{}/trips", ROUTE_GET_SERVICE_PATH.as_str());
+
+    // Car path
+    pub static ref CAR_GET_BY_VIN_SERVICE_PATH: String = format!("{}/{{vin}}", CAR_PATH.as_str());
+
+    // Owner history path
+    pub static ref CAR_OWNER_HISTORY_SERVICE_PATH: String =
+        format!("{}/owner-history", CAR_PATH.as_str());
+
+    // Live tracking path
+    pub static ref LIVE_TRACK_SERVICE_PATH: String = format!("{}/live", CAR_PATH.as_str());
+
+    // Watchlist path
+    pub static ref WATCHLIST_ENTRY_SERVICE_PATH: String =
+        format!("{}/{{id}}", WATCHLIST_PATH.as_str());
+    pub static ref WATCHLIST_ALERTS_SERVICE_PATH: String =
+        format!("{}/alerts", WATCHLIST_PATH.as_str());
+    pub static ref WATCHLIST_ALERTS_LIVE_SERVICE_PATH: String =
+        format!("{}/live", WATCHLIST_ALERTS_SERVICE_PATH.as_str());
+
+    // Speed violation path
+    pub static ref SPEED_VIOLATION_SEARCH_SERVICE_PATH: String =
+        format!("{}/search", VIOLATION_PATH.as_str());
+    pub static ref ROAD_SECTION_SERVICE_PATH: String =
+        format!("{}/{{id}}", ROAD_SECTION_PATH.as_str());
 
     // Auth path
     pub static ref AUTH_SERVICE_PATH: String = format!("{}/auth", USER_PATH.as_str());
     pub static ref REG_SERVICE_PATH: String = format!("{}/registr", USER_PATH.as_str());
+    pub static ref REFRESH_SERVICE_PATH: String = format!("{}/refresh", USER_PATH.as_str());
+    pub static ref LOGOUT_SERVICE_PATH: String = format!("{}/logout", USER_PATH.as_str());
     pub static ref PASSPORT_CONF_SERVICE_PATH: String =
         format!("{}/passport-confirm", USER_PATH.as_str());
 
     // Snap path
     pub static ref SNAP_SEND_SERVICE_PATH: String =
         format!("{}/send", SNAP_PATH.as_str());
+    pub static ref SNAP_SEND_BATCH_SERVICE_PATH: String =
+        format!("{}/send-batch", SNAP_PATH.as_str());
 
     // Camera path
     pub static ref CAMERA_GET_BY_ID_SERVICE_PATH: String =
@@ -32,6 +69,12 @@ lazy_static! {
         format!("{}/by-location", CAMERA_PATH.as_str());
     pub static ref GET_AVG_SPEED_ON_CAMERA_PATH: String =
         format!("{}/avg-speed", CAMERA_PATH.as_str());
+    pub static ref CAMERA_NEAREST_SERVICE_PATH: String =
+        format!("{}/nearest", CAMERA_PATH.as_str());
+    pub static ref CAMERA_WITHIN_SERVICE_PATH: String =
+        format!("{}/within", CAMERA_PATH.as_str());
+    pub static ref CAMERA_IN_BBOX_SERVICE_PATH: String =
+        format!("{}/in-bbox", CAMERA_PATH.as_str());
 
     // Search path
     pub static ref CAR_SEARCH_SERVICE_PATH: String = format!("{}/search", CAR_PATH.as_str());
@@ -0,0 +1,153 @@
+use crate::handlers::{BusinessServices, CurrentUser, ResponseStatusCode};
+use crate::paths;
+use axum::{
+    extract::{FromRequestParts, MatchedPath, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use lazy_static::lazy_static;
+use models::Role;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Access {
+    // Доступен без авторизации
+    Public,
+    // Доступен авторизованным пользователям с указанными ролями
+    Roles(Vec<Role>),
+}
+
+lazy_static! {
+    // Маршрут, не указанный здесь, закрыт для всех: доступ только по явной записи
+    pub static ref ROUTE_PERMISSIONS: HashMap<(Method, String), Access> = HashMap::from([
+        // Auth
+        ((Method::POST, paths::AUTH_SERVICE_PATH.to_string()), Access::Public),
+        ((Method::POST, paths::REFRESH_SERVICE_PATH.to_string()), Access::Public),
+        ((Method::POST, paths::LOGOUT_SERVICE_PATH.to_string()), Access::Public),
+        ((Method::POST, paths::REG_SERVICE_PATH.to_string()), Access::Public),
+        ((Method::POST, paths::PASSPORT_CONF_SERVICE_PATH.to_string()), Access::Roles(vec![Role::user, Role::operator, Role::audit])),
+
+        // Route
+        ((Method::POST, paths::ROUTE_GET_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::ROUTE_EXPORT_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::ROUTE_BY_PERIOD_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::ROUTE_TRIPS_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Car
+        ((Method::GET, paths::CAR_GET_BY_VIN_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Owner history
+        ((Method::POST, paths::CAR_OWNER_HISTORY_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Live tracking
+        ((Method::GET, paths::LIVE_TRACK_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Watchlist
+        ((Method::POST, paths::WATCHLIST_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::GET, paths::WATCHLIST_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::PUT, paths::WATCHLIST_ENTRY_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::DELETE, paths::WATCHLIST_ENTRY_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::GET, paths::WATCHLIST_ALERTS_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::GET, paths::WATCHLIST_ALERTS_LIVE_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Speed violation
+        ((Method::POST, paths::SPEED_VIOLATION_SEARCH_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::ROAD_SECTION_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::GET, paths::ROAD_SECTION_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::DELETE, paths::ROAD_SECTION_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Snap
+        ((Method::PUT, paths::SNAP_SEND_SERVICE_PATH.to_string()), Access::Roles(vec![Role::camera])),
+        ((Method::PUT, paths::SNAP_SEND_BATCH_SERVICE_PATH.to_string()), Access::Roles(vec![Role::camera])),
+
+        // Camera
+        ((Method::POST, paths::CAMERA_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::GET, paths::CAMERA_GET_BY_ID_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::PUT, paths::CAMERA_GET_BY_ID_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::DELETE, paths::CAMERA_GET_BY_ID_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAMERA_GET_BY_CORDS_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::GET_AVG_SPEED_ON_CAMERA_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAMERA_NEAREST_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAMERA_WITHIN_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAMERA_IN_BBOX_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Car search
+        ((Method::POST, paths::CAR_SEARCH_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAR_SEARCH_BY_FIO_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAR_SEARCH_BY_PASSPORT_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+        ((Method::POST, paths::CAR_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH.to_string()), Access::Roles(vec![Role::operator])),
+
+        // Track Info search
+        ((Method::POST, paths::TRACK_INFO_SEARCH_SERVICE_PATH.to_string()), Access::Roles(vec![Role::audit])),
+        ((Method::POST, paths::TRACK_INFO_SEARCH_BY_FIO_SERVICE_PATH.to_string()), Access::Roles(vec![Role::audit])),
+        ((Method::POST, paths::TRACK_INFO_SEARCH_BY_DATE_SERVICE_PATH.to_string()), Access::Roles(vec![Role::audit])),
+        ((Method::POST, paths::TRACK_INFO_SEARCH_BY_PASSPORT_SERVICE_PATH.to_string()), Access::Roles(vec![Role::audit])),
+        ((Method::POST, paths::TRACK_INFO_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH.to_string()), Access::Roles(vec![Role::audit])),
+    ]);
+}
+
+pub fn route_access(method: &Method, path: &str) -> Option<&'static Access> {
+    ROUTE_PERMISSIONS.get(&(method.clone(), path.to_string()))
+}
+
+pub fn is_allowed(method: &Method, path: &str, role: &Role) -> bool {
+    match route_access(method, path) {
+        Some(Access::Public) => true,
+        Some(Access::Roles(roles)) => roles.contains(role),
+        None => false,
+    }
+}
+
+pub async fn check_permissions(
+    State(services): State<BusinessServices>,
+    matched_path: MatchedPath,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = matched_path.as_str();
+    let method = req.method().clone();
+
+    match route_access(&method, path) {
+        Some(Access::Public) => return next.run(req).await,
+        Some(Access::Roles(_)) => {}
+        None => {
+            log::error!(
+                "No permissions entry for {} {}, access denied",
+                method,
+                path
+            );
+            return CurrentUser::rejection(
+                StatusCode::FORBIDDEN,
+                ResponseStatusCode::ACCESS_DENIED,
+                "Access denied",
+            )
+            .into_response();
+        }
+    }
+
+    let (mut parts, body) = req.into_parts();
+    let user = match CurrentUser::from_request_parts(&mut parts, &services).await {
+        Ok(CurrentUser(user)) => user,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    if !is_allowed(&method, path, &user.role) {
+        log::warn!(
+            "Access denied to {} {} for user {} with role {:?}",
+            method,
+            path,
+            user.email,
+            user.role
+        );
+        return CurrentUser::rejection(
+            StatusCode::FORBIDDEN,
+            ResponseStatusCode::ACCESS_DENIED,
+            "Access denied",
+        )
+        .into_response();
+    }
+
+    parts.extensions.insert(user);
+    next.run(Request::from_parts(parts, body)).await
+}
@@ -0,0 +1,285 @@
+use crate::handlers::{
+    auth_services::{
+        auth_service::handle_auth, logout_service::handle_logout,
+        passport_confirm_service::handle_passport_conf, refresh_service::handle_refresh,
+        registration_service::handle_reg,
+    },
+    camera_service::{
+        camera_get_by_cords_service::handle_get_camera_by_cords,
+        camera_get_by_id_service::handle_get_camera_by_id,
+        camera_manage_service::{
+            handle_add_camera, handle_decommission_camera, handle_update_camera,
+        },
+        camera_search_service::{
+            handle_find_cameras_in_bbox, handle_find_cameras_within, handle_find_nearest_cameras,
+        },
+        get_avg_speed_service::handle_get_avg_speed_for_car_on_camera,
+    },
+    car_get_by_vin_service::handle_get_car_by_vin,
+    live_track_service::handle_live_track,
+    owner_history_service::handle_get_owner_history,
+    road_section_service::{
+        handle_add_road_section, handle_delete_road_section, handle_get_road_sections,
+    },
+    route_export_service::handle_route_export,
+    route_get_service::handle_route,
+    route_period_service::handle_route_by_period,
+    route_trips_service::handle_route_trips,
+    search_services::{
+        car_search_services::{
+            handle_search_car_by_fio, handle_search_car_by_gos_num_mask,
+            handle_search_car_by_passport, handle_search_cars_by_filters,
+        },
+        track_info_search_services::{
+            handle_search_track_info_by_date, handle_search_track_info_by_filters,
+            handle_search_track_info_by_fio, handle_search_track_info_by_gos_num_mask,
+            handle_search_track_info_by_passport,
+        },
+    },
+    snap_send_batch_service::handle_snap_send_batch,
+    snap_send_service::handle_snap_send,
+    speed_violation_service::handle_search_speed_violations,
+    watchlist_services::{
+        watchlist_alert_services::{handle_get_watchlist_alerts, handle_watchlist_alerts_live},
+        watchlist_entry_services::{
+            handle_add_watchlist_entry, handle_delete_watchlist_entry, handle_get_watchlist,
+            handle_update_watchlist_entry,
+        },
+    },
+    BusinessServices,
+};
+use crate::paths;
+use axum::{
+    handler::Handler,
+    http::Method,
+    routing::{on, MethodFilter, MethodRouter},
+    Router,
+};
+
+pub struct ApiRoute {
+    pub method: Method,
+    pub path: &'static str,
+    pub handler: MethodRouter<BusinessServices>,
+}
+
+impl ApiRoute {
+    fn from<H, T>(method: Method, path: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, BusinessServices>,
+        T: 'static,
+    {
+        let filter = MethodFilter::try_from(method.clone()).expect("Unsupported route method");
+        Self {
+            method,
+            path,
+            handler: on(filter, handler),
+        }
+    }
+}
+
+// Все маршруты API: по этому списку строится роутер и проверяется покрытие прав доступа
+pub fn api_routes() -> Vec<ApiRoute> {
+    vec![
+        // Route
+        ApiRoute::from(Method::POST, &paths::ROUTE_GET_SERVICE_PATH, handle_route),
+        ApiRoute::from(
+            Method::POST,
+            &paths::ROUTE_EXPORT_SERVICE_PATH,
+            handle_route_export,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::ROUTE_BY_PERIOD_SERVICE_PATH,
+            handle_route_by_period,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::ROUTE_TRIPS_SERVICE_PATH,
+            handle_route_trips,
+        ),
+        // Car
+        ApiRoute::from(
+            Method::GET,
+            &paths::CAR_GET_BY_VIN_SERVICE_PATH,
+            handle_get_car_by_vin,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAR_OWNER_HISTORY_SERVICE_PATH,
+            handle_get_owner_history,
+        ),
+        // Live tracking
+        ApiRoute::from(
+            Method::GET,
+            &paths::LIVE_TRACK_SERVICE_PATH,
+            handle_live_track,
+        ),
+        // Watchlist
+        ApiRoute::from(
+            Method::POST,
+            &paths::WATCHLIST_PATH,
+            handle_add_watchlist_entry,
+        ),
+        ApiRoute::from(Method::GET, &paths::WATCHLIST_PATH, handle_get_watchlist),
+        ApiRoute::from(
+            Method::PUT,
+            &paths::WATCHLIST_ENTRY_SERVICE_PATH,
+            handle_update_watchlist_entry,
+        ),
+        ApiRoute::from(
+            Method::DELETE,
+            &paths::WATCHLIST_ENTRY_SERVICE_PATH,
+            handle_delete_watchlist_entry,
+        ),
+        ApiRoute::from(
+            Method::GET,
+            &paths::WATCHLIST_ALERTS_SERVICE_PATH,
+            handle_get_watchlist_alerts,
+        ),
+        ApiRoute::from(
+            Method::GET,
+            &paths::WATCHLIST_ALERTS_LIVE_SERVICE_PATH,
+            handle_watchlist_alerts_live,
+        ),
+        // Speed violation
+        ApiRoute::from(
+            Method::POST,
+            &paths::SPEED_VIOLATION_SEARCH_SERVICE_PATH,
+            handle_search_speed_violations,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::ROAD_SECTION_PATH,
+            handle_add_road_section,
+        ),
+        ApiRoute::from(
+            Method::GET,
+            &paths::ROAD_SECTION_PATH,
+            handle_get_road_sections,
+        ),
+        ApiRoute::from(
+            Method::DELETE,
+            &paths::ROAD_SECTION_SERVICE_PATH,
+            handle_delete_road_section,
+        ),
+        // Auth
+        ApiRoute::from(Method::POST, &paths::AUTH_SERVICE_PATH, handle_auth),
+        ApiRoute::from(Method::POST, &paths::REFRESH_SERVICE_PATH, handle_refresh),
+        ApiRoute::from(Method::POST, &paths::LOGOUT_SERVICE_PATH, handle_logout),
+        ApiRoute::from(Method::POST, &paths::REG_SERVICE_PATH, handle_reg),
+        ApiRoute::from(
+            Method::POST,
+            &paths::PASSPORT_CONF_SERVICE_PATH,
+            handle_passport_conf,
+        ),
+        // Snap
+        ApiRoute::from(
+            Method::PUT,
+            &paths::SNAP_SEND_SERVICE_PATH,
+            handle_snap_send,
+        ),
+        ApiRoute::from(
+            Method::PUT,
+            &paths::SNAP_SEND_BATCH_SERVICE_PATH,
+            handle_snap_send_batch,
+        ),
+        // Camera
+        ApiRoute::from(Method::POST, &paths::CAMERA_PATH, handle_add_camera),
+        ApiRoute::from(
+            Method::GET,
+            &paths::CAMERA_GET_BY_ID_SERVICE_PATH,
+            handle_get_camera_by_id,
+        ),
+        ApiRoute::from(
+            Method::PUT,
+            &paths::CAMERA_GET_BY_ID_SERVICE_PATH,
+            handle_update_camera,
+        ),
+        ApiRoute::from(
+            Method::DELETE,
+            &paths::CAMERA_GET_BY_ID_SERVICE_PATH,
+            handle_decommission_camera,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAMERA_GET_BY_CORDS_SERVICE_PATH,
+            handle_get_camera_by_cords,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::GET_AVG_SPEED_ON_CAMERA_PATH,
+            handle_get_avg_speed_for_car_on_camera,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAMERA_NEAREST_SERVICE_PATH,
+            handle_find_nearest_cameras,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAMERA_WITHIN_SERVICE_PATH,
+            handle_find_cameras_within,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAMERA_IN_BBOX_SERVICE_PATH,
+            handle_find_cameras_in_bbox,
+        ),
+        // Car search
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAR_SEARCH_SERVICE_PATH,
+            handle_search_cars_by_filters,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAR_SEARCH_BY_FIO_SERVICE_PATH,
+            handle_search_car_by_fio,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAR_SEARCH_BY_PASSPORT_SERVICE_PATH,
+            handle_search_car_by_passport,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::CAR_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH,
+            handle_search_car_by_gos_num_mask,
+        ),
+        // Track Info search
+        ApiRoute::from(
+            Method::POST,
+            &paths::TRACK_INFO_SEARCH_SERVICE_PATH,
+            handle_search_track_info_by_filters,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::TRACK_INFO_SEARCH_BY_FIO_SERVICE_PATH,
+            handle_search_track_info_by_fio,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::TRACK_INFO_SEARCH_BY_DATE_SERVICE_PATH,
+            handle_search_track_info_by_date,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::TRACK_INFO_SEARCH_BY_PASSPORT_SERVICE_PATH,
+            handle_search_track_info_by_passport,
+        ),
+        ApiRoute::from(
+            Method::POST,
+            &paths::TRACK_INFO_SEARCH_BY_GOS_NUM_MASK_SERVICE_PATH,
+            handle_search_track_info_by_gos_num_mask,
+        ),
+    ]
+}
+
+// Роутер API; маршруты с одним путём и разными методами объединяются
+pub fn api_router() -> Router<BusinessServices> {
+    api_routes()
+        .into_iter()
+        .fold(Router::new(), |router, route| {
+            router.route(route.path, route.handler)
+        })
+}
@@ -0,0 +1,87 @@
+use api::paths;
+use api::permissions::{is_allowed, route_access, Access, ROUTE_PERMISSIONS};
+use api::routes::{api_router, api_routes};
+use axum::http::Method;
+use models::Role;
+
+const ALL_ROLES: [Role; 4] = [Role::user, Role::operator, Role::audit, Role::camera];
+
+#[test]
+fn test_route_permissions_table() {
+    let public_routes = [
+        paths::AUTH_SERVICE_PATH.as_str(),
+        paths::REFRESH_SERVICE_PATH.as_str(),
+        paths::LOGOUT_SERVICE_PATH.as_str(),
+        paths::REG_SERVICE_PATH.as_str(),
+    ];
+    let camera_routes = [
+        paths::SNAP_SEND_SERVICE_PATH.as_str(),
+        paths::SNAP_SEND_BATCH_SERVICE_PATH.as_str(),
+    ];
+
+    for ((method, path), access) in ROUTE_PERMISSIONS.iter() {
+        match access {
+            Access::Public => assert!(
+                public_routes.contains(&path.as_str()),
+                "{method} {path} must not be public"
+            ),
+            Access::Roles(roles) => {
+                assert!(!roles.is_empty(), "{method} {path} has no roles");
+                assert_eq!(
+                    roles.contains(&Role::camera),
+                    camera_routes.contains(&path.as_str()),
+                    "{method} {path} camera access mismatch"
+                );
+            }
+        }
+
+        for role in ALL_ROLES.iter() {
+            let expected = match access {
+                Access::Public => true,
+                Access::Roles(roles) => roles.contains(role),
+            };
+            assert_eq!(
+                is_allowed(method, path, role),
+                expected,
+                "{method} {path} for {role:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_every_route_has_permissions() {
+    // Роутер собирается из того же списка и не должен падать на маршрутах с общим путём
+    let _ = api_router();
+    let routes = api_routes();
+
+    for route in routes.iter() {
+        assert!(
+            route_access(&route.method, route.path).is_some(),
+            "{} {} has no permissions entry",
+            route.method,
+            route.path
+        );
+    }
+
+    for (method, path) in ROUTE_PERMISSIONS.keys() {
+        assert!(
+            routes
+                .iter()
+                .any(|route| route.method == *method && route.path == path),
+            "{method} {path} has permissions entry but no route"
+        );
+    }
+}
+
+#[test]
+fn test_unlisted_route_is_denied() {
+    for role in ALL_ROLES.iter() {
+        assert!(!is_allowed(&Method::GET, "/unknown", role));
+        assert!(!is_allowed(
+            &Method::DELETE,
+            &paths::ROUTE_GET_SERVICE_PATH,
+            role
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use api::handlers::route_export_service::{render_geojson, render_gpx, render_kml, ExportFormat};
+use models::{DetailedRoute, Location, RoutePoint, RouteStats};
+
+fn point(time: &str, camera_id: usize, speed: Option<u16>) -> RoutePoint {
+    RoutePoint {
+        date: "01.01.2025".to_string(),
+        time: time.to_string(),
+        camera_id,
+        is_radar: speed.is_some(),
+        speed,
+        cords: Location {
+            latitude: 55.75,
+            longitude: 37.61,
+        },
+    }
+}
+
+fn route() -> DetailedRoute {
+    DetailedRoute {
+        points: vec![point("8:10", 1, Some(60)), point("8:25", 2, None)],
+        segments: vec![],
+        stats: RouteStats {
+            distance_km: 0.0,
+            duration_secs: 900,
+            max_speed: Some(60),
+            avg_speed: None,
+            cameras_count: 2,
+        },
+    }
+}
+
+#[test]
+fn test_export_format_parsing() {
+    assert_eq!(ExportFormat::from("GeoJSON"), Some(ExportFormat::GeoJson));
+    assert_eq!(ExportFormat::from("gpx"), Some(ExportFormat::Gpx));
+    assert_eq!(ExportFormat::from("kml"), Some(ExportFormat::Kml));
+    assert_eq!(ExportFormat::from("csv"), None);
+    assert_eq!(ExportFormat::Gpx.content_type(), "application/gpx+xml");
+}
+
+#[test]
+fn test_render_geojson_line_with_properties() {
+    let geojson: serde_json::Value =
+        serde_json::from_str(&render_geojson("А777МР77", &route())).unwrap();
+
+    let line = &geojson["features"][0];
+    assert_eq!(geojson["type"], "FeatureCollection");
+    assert_eq!(line["geometry"]["type"], "LineString");
+    assert_eq!(line["geometry"]["coordinates"][0][0], 37.61);
+    assert_eq!(line["properties"]["times"][0], "2025-01-01T08:10:00+03:00");
+    assert_eq!(line["properties"]["speeds"][0], 60);
+    assert!(line["properties"]["speeds"][1].is_null());
+    assert_eq!(geojson["features"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_render_gpx_track_points() {
+    let gpx = render_gpx("А777МР77", &route());
+
+    assert!(gpx.contains("<gpx version=\"1.1\""));
+    assert_eq!(
+        gpx.matches("<trkpt lat=\"55.75\" lon=\"37.61\">").count(),
+        2
+    );
+    assert!(gpx.contains("<time>2025-01-01T08:25:00+03:00</time>"));
+    assert_eq!(gpx.matches("<at:speed>").count(), 1);
+}
+
+#[test]
+fn test_render_kml_track_keeps_point_order() {
+    let kml = render_kml("А777МР77", &route());
+
+    assert_eq!(kml.matches("<when>").count(), 2);
+    assert_eq!(kml.matches("<gx:coord>37.61 55.75 0</gx:coord>").count(), 2);
+    assert!(kml.contains("<gx:value>60</gx:value>\n<gx:value/>"));
+}
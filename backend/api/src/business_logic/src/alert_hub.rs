@@ -0,0 +1,9 @@
+use crate::hub::{Hub, HubEvent, Subscription};
+use models::WatchlistAlert;
+
+// Рассылка фиксаций разыскиваемых автомобилей
+pub type AlertHub = Hub<WatchlistAlert>;
+
+pub type AlertEvent = HubEvent<WatchlistAlert>;
+
+pub type AlertSubscription = Subscription<WatchlistAlert>;
@@ -10,4 +10,6 @@ pub enum ServiceError {
     IsExistError(String),
     #[error("{0} is not found")]
     NotFoundError(String),
+    #[error("Internal error: {0}")]
+    InternalError(String),
 }
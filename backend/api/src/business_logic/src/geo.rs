@@ -0,0 +1,25 @@
+use models::Location;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+pub struct Geo;
+
+impl Geo {
+    // Расстояние по дуге большого круга (км)
+    pub fn haversine_km(from: &Location, to: &Location) -> f64 {
+        let (lat1, lat2) = (from.latitude.to_radians(), to.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (to.longitude - from.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    // Средняя скорость (км/ч), нет при нулевом или отрицательном времени
+    pub fn speed_kmh(distance_km: f64, duration_secs: i64) -> Option<f64> {
+        if duration_secs <= 0 {
+            return None;
+        }
+        Some(distance_km / (duration_secs as f64 / 3600.0))
+    }
+}
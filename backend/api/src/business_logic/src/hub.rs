@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::watch;
+
+const DEFAULT_HUB_CAPACITY: usize = 1024;
+
+// Рассылка событий подписчикам внутри процесса.
+// Очередь ограничена: медленный подписчик теряет самые старые события
+// и получает HubEvent::Lagged, не задерживая публикацию
+pub struct Hub<T> {
+    sender: broadcast::Sender<Arc<T>>,
+    closed: watch::Sender<bool>,
+}
+
+#[derive(Debug)]
+pub enum HubEvent<T> {
+    Item(Arc<T>),
+    // Количество пропущенных событий
+    Lagged(u64),
+}
+
+pub struct Subscription<T> {
+    receiver: broadcast::Receiver<Arc<T>>,
+    closed: watch::Receiver<bool>,
+}
+
+impl<T: Clone> Hub<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_HUB_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        let (closed, _) = watch::channel(false);
+        Hub { sender, closed }
+    }
+
+    pub fn publish(&self, item: &T) {
+        // Ошибка означает только отсутствие подписчиков
+        match self.sender.send(Arc::new(item.clone())) {
+            Ok(receivers) => log::debug!("Published event to {} subscribers", receivers),
+            Err(_) => log::debug!("No subscribers for event"),
+        }
+    }
+
+    pub fn subscribe(&self) -> Subscription<T> {
+        Subscription {
+            receiver: self.sender.subscribe(),
+            closed: self.closed.subscribe(),
+        }
+    }
+
+    // Завершает все подписки, в том числе оформленные после закрытия.
+    // Нужно при остановке сервера, чтобы долгие потоки не задерживали ее
+    pub fn close(&self) {
+        log::info!("Closing hub with {} subscribers", self.subscribers_count());
+        self.closed.send_replace(true);
+    }
+
+    pub fn subscribers_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl<T: Clone> Default for Hub<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Subscription<T> {
+    // None - хаб закрыт
+    pub async fn next(&mut self) -> Option<HubEvent<T>> {
+        let received = tokio::select! {
+            biased;
+            _ = self.closed.wait_for(|closed| *closed) => return None,
+            received = self.receiver.recv() => received,
+        };
+
+        match received {
+            Ok(item) => Some(HubEvent::Item(item)),
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("Slow subscriber skipped {} events", skipped);
+                Some(HubEvent::Lagged(skipped))
+            }
+            Err(RecvError::Closed) => None,
+        }
+    }
+}
@@ -1,5 +1,10 @@
+pub mod alert_hub;
 pub mod error;
+pub mod hub;
 pub mod services;
 pub mod services_traits;
+pub mod snap_hub;
 
+mod geo;
+mod pswd_hasher;
 mod validator;
@@ -0,0 +1,49 @@
+use crate::error::ServiceError;
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, SaltString};
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+
+const ARGON2_PREFIX: &str = "$argon2";
+
+pub struct PswdHasher;
+
+impl PswdHasher {
+    pub fn hash(pswd: &str) -> Result<String, ServiceError> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        Argon2::default()
+            .hash_password(pswd.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| {
+                log::error!("Failed to hash password: {}", e);
+                ServiceError::InternalError("password hashing".to_string())
+            })
+    }
+
+    // Пароли, сохранённые до перехода на Argon2, лежат в открытом виде
+    pub fn is_hashed(stored: &str) -> bool {
+        stored.starts_with(ARGON2_PREFIX)
+    }
+
+    pub fn verify(pswd: &str, stored: &str) -> bool {
+        if !Self::is_hashed(stored) {
+            return Self::constant_time_eq(pswd.as_bytes(), stored.as_bytes());
+        }
+
+        match PasswordHash::new(stored) {
+            Ok(hash) => Argon2::default()
+                .verify_password(pswd.as_bytes(), &hash)
+                .is_ok(),
+            Err(e) => {
+                log::error!("Stored password hash is malformed: {}", e);
+                false
+            }
+        }
+    }
+
+    fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
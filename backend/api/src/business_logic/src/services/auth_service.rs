@@ -4,18 +4,39 @@ use async_trait::async_trait;
 use data_access::repositories_traits::UserRepository;
 use models::{Document, Role, User};
 
+use super::pswd_hasher::PswdHasher;
 use super::validator::Validator;
+use std::sync::Arc;
 
 pub struct AuthService {
-    user_repo: Box<dyn UserRepository>,
+    user_repo: Arc<dyn UserRepository>,
 }
 
 impl AuthService {
-    pub fn from(user_repo: Box<dyn UserRepository>) -> Self {
+    pub fn from(user_repo: Arc<dyn UserRepository>) -> Self {
         AuthService { user_repo }
     }
 }
 
+impl AuthService {
+    async fn rehash_legacy_pswd(&self, email: &str, pswd: &str) {
+        log::info!("Upgrading legacy plaintext password for email: {}", email);
+
+        let res = match PswdHasher::hash(pswd) {
+            Ok(pswd_hash) => self
+                .user_repo
+                .update_user_pswd_hash(email, &pswd_hash)
+                .await
+                .map_err(ServiceError::from),
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = res {
+            log::warn!("Failed to upgrade password for email {}: {}", email, e);
+        }
+    }
+}
+
 unsafe impl Send for AuthService {}
 unsafe impl Sync for AuthService {}
 
@@ -38,14 +59,17 @@ impl Authorizer for AuthService {
             ));
         }
 
-        let res = self.user_repo.get_user_by_auth_info(email, pswd).await?;
+        let res = self.user_repo.get_user_with_pswd_hash(email).await?;
 
         match res {
-            Some(user) => {
+            Some((user, stored_pswd)) if PswdHasher::verify(pswd, &stored_pswd) => {
+                if !PswdHasher::is_hashed(&stored_pswd) {
+                    self.rehash_legacy_pswd(email, pswd).await;
+                }
                 log::info!("Successful authentication for email: {}", email);
                 Ok(user)
             }
-            None => {
+            _ => {
                 log::warn!("Failed authentication attempt for email: {}", email);
                 Err(ServiceError::InvalidDataError(
                     "email or password".to_string(),
@@ -101,7 +125,8 @@ impl Authorizer for AuthService {
                     passport: None,
                 };
 
-                self.user_repo.insert_user(&user, pswd).await?;
+                let pswd_hash = PswdHasher::hash(pswd)?;
+                self.user_repo.insert_user(&user, &pswd_hash).await?;
                 log::info!("Successfully registered new user: {}", email);
                 Ok(())
             }
@@ -2,16 +2,21 @@ use crate::error::ServiceError;
 use crate::services_traits::CameraDataGetter;
 use async_trait::async_trait;
 use data_access::{error::DataAccessError, repositories_traits::CameraRepository};
-use models::{Camera, Location};
+use models::{BoundingBox, Camera, Location, NearbyCamera};
 
+use super::geo::Geo;
 use super::validator::Validator;
+use std::sync::Arc;
+
+const MAX_NEAREST_CAMERAS: usize = 50;
+const MAX_SEARCH_RADIUS_M: f64 = 50_000.;
 
 pub struct CameraDataGetService {
-    cam_repo: Box<dyn CameraRepository>,
+    cam_repo: Arc<dyn CameraRepository>,
 }
 
 impl CameraDataGetService {
-    pub fn from(cam_repo: Box<dyn CameraRepository>) -> Self {
+    pub fn from(cam_repo: Arc<dyn CameraRepository>) -> Self {
         CameraDataGetService { cam_repo }
     }
 }
@@ -19,6 +24,16 @@ impl CameraDataGetService {
 unsafe impl Send for CameraDataGetService {}
 unsafe impl Sync for CameraDataGetService {}
 
+impl CameraDataGetService {
+    fn validate_location(location: &Location) -> Result<(), ServiceError> {
+        if !Validator::is_valid_location(location) {
+            log::warn!("Invalid location: {:?}", location);
+            return Err(ServiceError::InvalidDataError("location".to_string()));
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl CameraDataGetter for CameraDataGetService {
     async fn get_camera_by_id(&self, id: usize) -> Result<Camera, ServiceError> {
@@ -92,4 +107,87 @@ impl CameraDataGetter for CameraDataGetService {
         log::info!("Getted average speed: {}", avg_speed);
         Ok(avg_speed)
     }
+
+    async fn find_nearest_cameras(
+        &self,
+        location: &Location,
+        count: usize,
+    ) -> Result<Vec<NearbyCamera>, ServiceError> {
+        log::info!(
+            "Attempting getting {} nearest cameras to location: {:?}",
+            count,
+            location
+        );
+
+        Self::validate_location(location)?;
+        if !(1..=MAX_NEAREST_CAMERAS).contains(&count) {
+            log::warn!("Invalid cameras count: {}", count);
+            return Err(ServiceError::InvalidDataError("cameras count".to_string()));
+        }
+
+        let cameras = self.cam_repo.find_nearest_cameras(location, count).await?;
+        log::info!("Getted {} nearest cameras", cameras.len());
+        Ok(cameras)
+    }
+
+    async fn find_cameras_within(
+        &self,
+        location: &Location,
+        radius_m: f64,
+    ) -> Result<Vec<NearbyCamera>, ServiceError> {
+        log::info!(
+            "Attempting getting cameras within {} m of location: {:?}",
+            radius_m,
+            location
+        );
+
+        Self::validate_location(location)?;
+        if !(radius_m > 0. && radius_m <= MAX_SEARCH_RADIUS_M) {
+            log::warn!("Invalid search radius: {}", radius_m);
+            return Err(ServiceError::InvalidDataError("radius".to_string()));
+        }
+
+        let cameras = self
+            .cam_repo
+            .find_cameras_within(location, radius_m)
+            .await?;
+        log::info!("Getted {} cameras within radius", cameras.len());
+        Ok(cameras)
+    }
+
+    async fn find_cameras_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<NearbyCamera>, ServiceError> {
+        log::info!("Attempting getting cameras in bounding box: {:?}", bbox);
+
+        let (sw, ne) = (&bbox.south_west, &bbox.north_east);
+        if !Validator::is_valid_location(sw)
+            || !Validator::is_valid_location(ne)
+            || sw.latitude > ne.latitude
+            || sw.longitude > ne.longitude
+        {
+            log::warn!("Invalid bounding box: {:?}", bbox);
+            return Err(ServiceError::InvalidDataError("bounding box".to_string()));
+        }
+
+        let center = Location {
+            longitude: (sw.longitude + ne.longitude) / 2.,
+            latitude: (sw.latitude + ne.latitude) / 2.,
+        };
+        let mut cameras: Vec<NearbyCamera> = self
+            .cam_repo
+            .find_cameras_in_bbox(bbox)
+            .await?
+            .into_iter()
+            .map(|camera| NearbyCamera {
+                distance: Geo::haversine_km(&center, &camera.location) * 1000.,
+                camera,
+            })
+            .collect();
+        cameras.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        log::info!("Getted {} cameras in bounding box", cameras.len());
+        Ok(cameras)
+    }
 }
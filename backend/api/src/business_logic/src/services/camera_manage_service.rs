@@ -0,0 +1,113 @@
+use crate::error::ServiceError;
+use crate::services_traits::CameraManager;
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate};
+use data_access::{error::DataAccessError, repositories_traits::CameraRepository};
+use models::Camera;
+
+use super::validator::Validator;
+use std::sync::Arc;
+
+const DATE_FORMAT: &str = "%d.%m.%Y";
+
+pub struct CameraManageService {
+    cam_repo: Arc<dyn CameraRepository>,
+}
+
+impl CameraManageService {
+    pub fn from(cam_repo: Arc<dyn CameraRepository>) -> Self {
+        CameraManageService { cam_repo }
+    }
+}
+
+unsafe impl Send for CameraManageService {}
+unsafe impl Sync for CameraManageService {}
+
+impl CameraManageService {
+    fn validate_camera(camera: &Camera) -> Result<(), ServiceError> {
+        if !Validator::is_valid_location(&camera.location) {
+            log::warn!("Invalid location: {:?}", camera.location);
+            return Err(ServiceError::InvalidDataError("location".to_string()));
+        }
+
+        if let Some(date) = &camera.install_date {
+            let is_valid = Validator::is_valid_date(date)
+                && NaiveDate::parse_from_str(date, DATE_FORMAT)
+                    .is_ok_and(|date| date <= Local::now().date_naive());
+            if !is_valid {
+                log::warn!("Invalid install date: {}", date);
+                return Err(ServiceError::InvalidDataError("date".to_string()));
+            }
+        }
+
+        if camera.speed_limit == Some(0) {
+            log::warn!("Invalid speed limit: 0");
+            return Err(ServiceError::InvalidDataError("speed limit".to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn map_not_found(e: DataAccessError) -> ServiceError {
+        match e {
+            DataAccessError::NotFoundError(e) => ServiceError::NotFoundError(e),
+            e => ServiceError::DataAccessError(e),
+        }
+    }
+}
+
+#[async_trait]
+impl CameraManager for CameraManageService {
+    async fn add_camera(&self, camera: &Camera) -> Result<Camera, ServiceError> {
+        log::info!("Adding camera at location: {:?}", camera.location);
+
+        Self::validate_camera(camera)?;
+
+        let mut camera = Camera {
+            install_date: camera
+                .install_date
+                .clone()
+                .or_else(|| Some(Local::now().date_naive().format(DATE_FORMAT).to_string())),
+            ..camera.clone()
+        };
+        camera.id = self.cam_repo.insert_camera(&camera).await?;
+
+        log::info!("Added camera {}", camera.id);
+        Ok(camera)
+    }
+
+    async fn update_camera(&self, camera: &Camera) -> Result<Camera, ServiceError> {
+        log::info!("Updating camera {}", camera.id);
+
+        Self::validate_camera(camera)?;
+
+        let stored = self
+            .cam_repo
+            .get_camera_by_id(camera.id)
+            .await
+            .map_err(Self::map_not_found)?;
+        let camera = Camera {
+            install_date: camera.install_date.clone().or(stored.install_date),
+            ..camera.clone()
+        };
+        self.cam_repo
+            .update_camera(&camera)
+            .await
+            .map_err(Self::map_not_found)?;
+
+        log::info!("Updated camera {}", camera.id);
+        Ok(camera)
+    }
+
+    async fn decommission_camera(&self, id: usize) -> Result<(), ServiceError> {
+        log::info!("Decommissioning camera {}", id);
+
+        self.cam_repo
+            .decommission_camera(id)
+            .await
+            .map_err(Self::map_not_found)?;
+
+        log::info!("Decommissioned camera {}", id);
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use super::validator::Validator;
+use crate::error::ServiceError;
+use crate::services_traits::LiveTracker;
+use crate::snap_hub::{SnapHub, SnapSubscription};
+use async_trait::async_trait;
+use chrono::Local;
+use data_access::repositories_traits::{TrackInfoRepository, UserRepository};
+use std::sync::Arc;
+
+// Ограничение числа номеров в одной подписке
+const MAX_SUBSCRIPTION_GOS_NUMS: usize = 20;
+
+pub struct LiveTrackService {
+    user_repo: Arc<dyn UserRepository>,
+    track_info_repo: Arc<dyn TrackInfoRepository>,
+    hub: Arc<SnapHub>,
+}
+
+impl LiveTrackService {
+    pub fn from(
+        user_repo: Arc<dyn UserRepository>,
+        track_info_repo: Arc<dyn TrackInfoRepository>,
+        hub: Arc<SnapHub>,
+    ) -> Self {
+        LiveTrackService {
+            user_repo,
+            track_info_repo,
+            hub,
+        }
+    }
+}
+
+unsafe impl Send for LiveTrackService {}
+unsafe impl Sync for LiveTrackService {}
+
+#[async_trait]
+impl LiveTracker for LiveTrackService {
+    async fn subscribe(
+        &self,
+        gos_nums: &[String],
+        user_login: &str,
+    ) -> Result<SnapSubscription, ServiceError> {
+        log::info!(
+            "Starting live tracking subscription for {:?} by user {}",
+            gos_nums,
+            user_login
+        );
+
+        if gos_nums.is_empty() || gos_nums.len() > MAX_SUBSCRIPTION_GOS_NUMS {
+            log::warn!("Invalid subscription size: {}", gos_nums.len());
+            return Err(ServiceError::InvalidDataError("subscription".to_string()));
+        }
+        if let Some(gos_num) = gos_nums.iter().find(|g| !Validator::is_valid_gos_num(g)) {
+            log::warn!("Invalid vehicle number format: {}", gos_num);
+            return Err(ServiceError::InvalidDataError("gos number".to_string()));
+        }
+        if !Validator::is_valid_email(user_login) {
+            log::warn!("Invalid user login format: {}", user_login);
+            return Err(ServiceError::InvalidDataError("email".to_string()));
+        }
+
+        if self
+            .user_repo
+            .get_user_by_email(user_login)
+            .await?
+            .is_none()
+        {
+            log::warn!("User with login: {} not founded", user_login);
+            return Err(ServiceError::NotFoundError("email".to_string()));
+        }
+
+        // Отслеживание в реальном времени фиксируется как просмотр маршрута за текущую дату
+        let date = Local::now().format("%d.%m.%Y").to_string();
+        for gos_num in gos_nums {
+            self.track_info_repo
+                .insert_track_info(gos_num, user_login, &date)
+                .await?;
+        }
+
+        let subscription = SnapSubscription::from(&self.hub, gos_nums);
+        log::info!(
+            "User {} subscribed to {} vehicles ({} subscribers)",
+            user_login,
+            subscription.gos_nums().len(),
+            self.hub.subscribers_count()
+        );
+        Ok(subscription)
+    }
+}
@@ -1,7 +1,14 @@
 pub mod auth_service;
 pub mod camera_data_get_service;
+pub mod camera_manage_service;
+pub mod live_track_service;
 pub mod route_service;
 pub mod search_service;
+pub mod session_service;
 pub mod snap_send_service;
+pub mod speed_control_service;
+pub mod watchlist_service;
 
+use crate::geo;
+use crate::pswd_hasher;
 use crate::validator;
@@ -1,42 +1,65 @@
+use super::geo::Geo;
 use super::validator::Validator;
 use crate::error::ServiceError;
 use crate::services_traits::RouteGetter;
 use async_trait::async_trait;
-use models::PointData;
+use chrono::{Duration, NaiveDateTime};
+use models::{
+    DetailedRoute, PointData, RoutePoint, RouteSegment, RouteStats, RouteStop, RouteTrips, Snap,
+    Trip,
+};
+use std::collections::HashSet;
 
 use data_access::repositories_traits::{SnapRepository, TrackInfoRepository, UserRepository};
+use std::sync::Arc;
+
+const DATETIME_FORMAT: &str = "%d.%m.%Y %H:%M";
+// Ограничение периода маршрута, чтобы один запрос не выбирал всю историю снимков
+const MAX_ROUTE_PERIOD_DAYS: i64 = 7;
+const DEFAULT_TRIP_GAP_MINUTES: u32 = 15;
 
 pub struct RouteService {
-    user_repo: Box<dyn UserRepository>,
-    snap_repo: Box<dyn SnapRepository>,
-    track_info_repo: Box<dyn TrackInfoRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    snap_repo: Arc<dyn SnapRepository>,
+    track_info_repo: Arc<dyn TrackInfoRepository>,
+    trip_gap_minutes: u32,
 }
 
 impl RouteService {
     pub fn from(
-        user_repo: Box<dyn UserRepository>,
-        snap_repo: Box<dyn SnapRepository>,
-        track_info_repo: Box<dyn TrackInfoRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        snap_repo: Arc<dyn SnapRepository>,
+        track_info_repo: Arc<dyn TrackInfoRepository>,
     ) -> Self {
         RouteService {
             user_repo,
             snap_repo,
             track_info_repo,
+            trip_gap_minutes: DEFAULT_TRIP_GAP_MINUTES,
         }
     }
+
+    // Перерыв между соседними фиксациями, после которого начинается новая поездка
+    pub fn with_trip_gap_minutes(mut self, minutes: u32) -> Self {
+        self.trip_gap_minutes = minutes;
+        self
+    }
 }
 
 unsafe impl Send for RouteService {}
 unsafe impl Sync for RouteService {}
 
-#[async_trait]
-impl RouteGetter for RouteService {
-    async fn get_car_route(
+impl RouteService {
+    fn snap_datetime(snap: &Snap) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(&format!("{} {}", snap.date, snap.time), DATETIME_FORMAT).ok()
+    }
+
+    async fn get_route_snaps(
         &self,
-        gos_num: &String,
-        user_login: &String,
-        date: &String,
-    ) -> Result<Option<Vec<PointData>>, ServiceError> {
+        gos_num: &str,
+        user_login: &str,
+        date: &str,
+    ) -> Result<Vec<Snap>, ServiceError> {
         log::info!(
             "Starting route request for vehicle {} by user {} on date {}",
             gos_num,
@@ -52,6 +75,85 @@ impl RouteGetter for RouteService {
             log::warn!("Invalid date format: {}", date);
             return Err(ServiceError::InvalidDataError("date".to_string()));
         }
+        self.check_user(user_login).await?;
+
+        log::debug!(
+            "Fetching snap data for vehicle {} on date {}",
+            gos_num,
+            date
+        );
+        let mut snaps = self.snap_repo.get_car_snaps_by_date(gos_num, date).await?;
+        log::debug!("Found {} snap points", snaps.len());
+
+        // Время без ведущего нуля ("8:10") нельзя сравнивать как строку
+        snaps.sort_by_key(Self::snap_datetime);
+        log::debug!("Snap points sorted by time");
+
+        log::info!("Recording track info request for user {}", user_login);
+        self.track_info_repo
+            .insert_track_info(gos_num, user_login, date)
+            .await?;
+
+        Ok(snaps)
+    }
+
+    fn parse_datetime(datetime: &str) -> Result<NaiveDateTime, ServiceError> {
+        NaiveDateTime::parse_from_str(datetime, DATETIME_FORMAT).map_err(|_| {
+            log::warn!("Invalid datetime format: {}", datetime);
+            ServiceError::InvalidDataError("datetime".to_string())
+        })
+    }
+
+    async fn get_route_snaps_by_period(
+        &self,
+        gos_num: &str,
+        user_login: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<Snap>, ServiceError> {
+        log::info!(
+            "Starting route request for vehicle {} by user {} from {} to {}",
+            gos_num,
+            user_login,
+            from,
+            to
+        );
+
+        if !Validator::is_valid_gos_num(gos_num) {
+            log::warn!("Invalid vehicle number format: {}", gos_num);
+            return Err(ServiceError::InvalidDataError("gos number".to_string()));
+        }
+        let (from, to) = (Self::parse_datetime(from)?, Self::parse_datetime(to)?);
+        if from > to || to - from > Duration::days(MAX_ROUTE_PERIOD_DAYS) {
+            log::warn!("Invalid route period: {} - {}", from, to);
+            return Err(ServiceError::InvalidDataError("period".to_string()));
+        }
+        self.check_user(user_login).await?;
+
+        log::debug!(
+            "Fetching snap data for vehicle {} from {} to {}",
+            gos_num,
+            from,
+            to
+        );
+        let snaps = self
+            .snap_repo
+            .get_car_snaps_by_period(gos_num, &from, &to)
+            .await?;
+        log::debug!("Found {} snap points", snaps.len());
+
+        // Отслеживание фиксируется за каждые сутки периода
+        log::info!("Recording track info request for user {}", user_login);
+        for day in from.date().iter_days().take_while(|day| *day <= to.date()) {
+            self.track_info_repo
+                .insert_track_info(gos_num, user_login, &day.format("%d.%m.%Y").to_string())
+                .await?;
+        }
+
+        Ok(snaps)
+    }
+
+    async fn check_user(&self, user_login: &str) -> Result<(), ServiceError> {
         if !Validator::is_valid_email(user_login) {
             log::warn!("Invalid user login format: {}", user_login);
             return Err(ServiceError::InvalidDataError("email".to_string()));
@@ -68,22 +170,137 @@ impl RouteGetter for RouteService {
             log::warn!("User with login: {} not founded", user_login);
             return Err(ServiceError::NotFoundError("email".to_string()));
         }
+        Ok(())
+    }
 
-        log::debug!(
-            "Fetching snap data for vehicle {} on date {}",
-            gos_num,
-            date
-        );
-        let mut snaps = self.snap_repo.get_car_snaps_by_date(gos_num, date).await?;
-        log::debug!("Found {} snap points", snaps.len());
+    fn build_detailed_route(snaps: &[Snap]) -> DetailedRoute {
+        let points: Vec<RoutePoint> = snaps
+            .iter()
+            .map(|x| RoutePoint {
+                date: x.date.clone(),
+                time: x.time.clone(),
+                camera_id: x.camera.id,
+                is_radar: x.camera.is_radar,
+                speed: x.speed,
+                cords: x.camera.location,
+            })
+            .collect();
 
-        snaps.sort_by(|x, y| x.time.cmp(&y.time));
-        log::debug!("Snap points sorted by time");
+        let segments: Vec<RouteSegment> = snaps
+            .windows(2)
+            .map(|pair| {
+                let distance_km =
+                    Geo::haversine_km(&pair[0].camera.location, &pair[1].camera.location);
+                let duration_secs =
+                    match (Self::snap_datetime(&pair[0]), Self::snap_datetime(&pair[1])) {
+                        (Some(from), Some(to)) => (to - from).num_seconds(),
+                        _ => 0,
+                    };
+                RouteSegment {
+                    from_camera_id: pair[0].camera.id,
+                    to_camera_id: pair[1].camera.id,
+                    distance_km,
+                    duration_secs,
+                    avg_speed: Geo::speed_kmh(distance_km, duration_secs),
+                }
+            })
+            .collect();
 
-        log::info!("Recording track info request for user {}", user_login);
-        self.track_info_repo
-            .insert_track_info(gos_num, user_login, date)
-            .await?;
+        let distance_km = segments.iter().map(|s| s.distance_km).sum();
+        let duration_secs = segments.iter().map(|s| s.duration_secs).sum();
+        let stats = RouteStats {
+            distance_km,
+            duration_secs,
+            max_speed: snaps.iter().filter_map(|x| x.speed).max(),
+            avg_speed: Geo::speed_kmh(distance_km, duration_secs),
+            cameras_count: snaps
+                .iter()
+                .map(|x| x.camera.id)
+                .collect::<HashSet<_>>()
+                .len(),
+        };
+
+        DetailedRoute {
+            points,
+            segments,
+            stats,
+        }
+    }
+
+    fn build_trips(snaps: &[Snap], gap_minutes: u32) -> RouteTrips {
+        let gap = Duration::minutes(gap_minutes as i64);
+
+        let mut parts: Vec<&[Snap]> = Vec::new();
+        let mut start = 0;
+        for i in 1..snaps.len() {
+            let is_gap = match (
+                Self::snap_datetime(&snaps[i - 1]),
+                Self::snap_datetime(&snaps[i]),
+            ) {
+                (Some(prev), Some(next)) => next - prev > gap,
+                _ => false,
+            };
+            if is_gap {
+                parts.push(&snaps[start..i]);
+                start = i;
+            }
+        }
+        if start < snaps.len() {
+            parts.push(&snaps[start..]);
+        }
+
+        let trips: Vec<Trip> = parts
+            .iter()
+            .map(|part| {
+                let (first, last) = (&part[0], &part[part.len() - 1]);
+                let route = Self::build_detailed_route(part);
+                Trip {
+                    start_camera_id: first.camera.id,
+                    end_camera_id: last.camera.id,
+                    start_date: first.date.clone(),
+                    start_time: first.time.clone(),
+                    end_date: last.date.clone(),
+                    end_time: last.time.clone(),
+                    points: route.points,
+                    stats: route.stats,
+                }
+            })
+            .collect();
+
+        let stops = parts
+            .windows(2)
+            .map(|pair| {
+                let (arrival, departure) = (&pair[0][pair[0].len() - 1], &pair[1][0]);
+                let dwell_secs =
+                    match (Self::snap_datetime(arrival), Self::snap_datetime(departure)) {
+                        (Some(from), Some(to)) => (to - from).num_seconds(),
+                        _ => 0,
+                    };
+                RouteStop {
+                    camera_id: arrival.camera.id,
+                    cords: arrival.camera.location,
+                    arrival_date: arrival.date.clone(),
+                    arrival_time: arrival.time.clone(),
+                    departure_date: departure.date.clone(),
+                    departure_time: departure.time.clone(),
+                    dwell_secs,
+                }
+            })
+            .collect();
+
+        RouteTrips { trips, stops }
+    }
+}
+
+#[async_trait]
+impl RouteGetter for RouteService {
+    async fn get_car_route(
+        &self,
+        gos_num: &String,
+        user_login: &String,
+        date: &String,
+    ) -> Result<Option<Vec<PointData>>, ServiceError> {
+        let snaps = self.get_route_snaps(gos_num, user_login, date).await?;
 
         let data: Vec<_> = snaps
             .iter()
@@ -109,4 +326,99 @@ impl RouteGetter for RouteService {
             Ok(Some(data))
         }
     }
+
+    async fn get_car_route_detailed(
+        &self,
+        gos_num: &String,
+        user_login: &String,
+        date: &String,
+    ) -> Result<Option<DetailedRoute>, ServiceError> {
+        let snaps = self.get_route_snaps(gos_num, user_login, date).await?;
+
+        if snaps.is_empty() {
+            log::warn!(
+                "No location data found for vehicle {} on date {}",
+                gos_num,
+                date
+            );
+            return Ok(None);
+        }
+
+        let route = Self::build_detailed_route(&snaps);
+        log::info!(
+            "Successfully generated detailed route with {} points ({:.2} km) for vehicle {}",
+            route.points.len(),
+            route.stats.distance_km,
+            gos_num
+        );
+        Ok(Some(route))
+    }
+
+    async fn get_car_route_by_period(
+        &self,
+        gos_num: &str,
+        user_login: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<DetailedRoute>, ServiceError> {
+        let snaps = self
+            .get_route_snaps_by_period(gos_num, user_login, from, to)
+            .await?;
+
+        if snaps.is_empty() {
+            log::warn!(
+                "No location data found for vehicle {} from {} to {}",
+                gos_num,
+                from,
+                to
+            );
+            return Ok(None);
+        }
+
+        let route = Self::build_detailed_route(&snaps);
+        log::info!(
+            "Successfully generated route with {} points ({:.2} km) for vehicle {} from {} to {}",
+            route.points.len(),
+            route.stats.distance_km,
+            gos_num,
+            from,
+            to
+        );
+        Ok(Some(route))
+    }
+
+    async fn get_car_trips(
+        &self,
+        gos_num: &str,
+        user_login: &str,
+        date: &str,
+        gap_minutes: Option<u32>,
+    ) -> Result<Option<RouteTrips>, ServiceError> {
+        let gap_minutes = gap_minutes.unwrap_or(self.trip_gap_minutes);
+        if gap_minutes == 0 {
+            log::warn!("Invalid trip gap: {}", gap_minutes);
+            return Err(ServiceError::InvalidDataError("trip gap".to_string()));
+        }
+
+        let snaps = self.get_route_snaps(gos_num, user_login, date).await?;
+
+        if snaps.is_empty() {
+            log::warn!(
+                "No location data found for vehicle {} on date {}",
+                gos_num,
+                date
+            );
+            return Ok(None);
+        }
+
+        let trips = Self::build_trips(&snaps, gap_minutes);
+        log::info!(
+            "Split route of vehicle {} into {} trips with {} stops (gap: {} min)",
+            gos_num,
+            trips.trips.len(),
+            trips.stops.len(),
+            gap_minutes
+        );
+        Ok(Some(trips))
+    }
 }
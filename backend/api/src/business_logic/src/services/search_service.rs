@@ -1,21 +1,32 @@
+use data_access::error::DataAccessError;
 use data_access::repositories_traits::{CarRepository, TrackInfoRepository};
 
 use crate::error::ServiceError;
 use crate::services_traits::{CarSearcher, Searcher, TrackInfoSearcher};
 use async_trait::async_trait;
-use models::{Car, Document, TrackInfo};
+use chrono::Datelike;
+use models::{
+    Car, CarFilter, DetailedCar, Document, OwnershipRecord, Page, Paged, SortKey, TrackInfo,
+};
 
 use super::validator::Validator;
+use std::sync::Arc;
+
+const MAX_PAGE_SIZE: usize = 100;
+// Допустимые годы выпуска - от этого года до следующего за текущим
+const MIN_RELEASE_YEAR: u16 = 1900;
+// Смещение передается в функции поиска БД как INT
+const MAX_PAGE_OFFSET: usize = i32::MAX as usize;
 
 pub struct SearchService {
-    car_repo: Box<dyn CarRepository>,
-    track_info_repo: Box<dyn TrackInfoRepository>,
+    car_repo: Arc<dyn CarRepository>,
+    track_info_repo: Arc<dyn TrackInfoRepository>,
 }
 
 impl SearchService {
     pub fn from(
-        car_repo: Box<dyn CarRepository>,
-        track_info_repo: Box<dyn TrackInfoRepository>,
+        car_repo: Arc<dyn CarRepository>,
+        track_info_repo: Arc<dyn TrackInfoRepository>,
     ) -> Self {
         SearchService {
             car_repo,
@@ -24,6 +35,33 @@ impl SearchService {
     }
 }
 
+impl SearchService {
+    fn check_page(page: &Page) -> Result<(), ServiceError> {
+        if page.limit == 0 || page.limit > MAX_PAGE_SIZE {
+            log::warn!("Invalid page size: {}", page.limit);
+            return Err(ServiceError::InvalidDataError("page size".to_string()));
+        }
+        if page.offset > MAX_PAGE_OFFSET {
+            log::warn!("Invalid page offset: {}", page.offset);
+            return Err(ServiceError::InvalidDataError("page offset".to_string()));
+        }
+        Ok(())
+    }
+
+    // Пустому образцу имени подошла бы любая запись
+    fn check_names(names: [&Option<String>; 3]) -> Result<(), ServiceError> {
+        if names
+            .into_iter()
+            .flatten()
+            .any(|name| name.trim().is_empty())
+        {
+            log::warn!("Empty name pattern in search");
+            return Err(ServiceError::InvalidDataError("name".to_string()));
+        }
+        Ok(())
+    }
+}
+
 impl Searcher for SearchService {}
 
 unsafe impl Send for SearchService {}
@@ -39,17 +77,22 @@ impl TrackInfoSearcher for SearchService {
         passport: Option<Document>,
         gos_num_mask: Option<String>,
         date: Option<String>,
-    ) -> Result<Vec<TrackInfo>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError> {
         log::info!(
-            "Searching track info records by filters: {:?} {:?} {:?} {:?} {:?} {:?}",
+            "Searching track info records by filters: {:?} {:?} {:?} {:?} {:?} {:?} ({:?})",
             firstname.as_deref(),
             surname.as_deref(),
             lastname.as_deref(),
             passport,
             gos_num_mask.as_deref(),
             date.as_deref(),
+            page,
         );
 
+        Self::check_page(page)?;
+        Self::check_names([&firstname, &surname, &lastname])?;
+
         if let Some(gsm) = &gos_num_mask {
             if !Validator::is_valid_gos_num_mask(&gsm) {
                 log::warn!("Invalid gos number mask format: {}", &gsm);
@@ -82,10 +125,15 @@ impl TrackInfoSearcher for SearchService {
                 passport,
                 gos_num_mask.as_deref(),
                 date.as_deref(),
+                page,
             )
             .await?;
 
-        log::debug!("Found {} track info records by filters", track_infos.len());
+        log::debug!(
+            "Found {} track info records by filters, {} on page",
+            track_infos.total,
+            track_infos.items.len()
+        );
         Ok(track_infos)
     }
 
@@ -94,7 +142,8 @@ impl TrackInfoSearcher for SearchService {
         firstname: Option<String>,
         surname: Option<String>,
         lastname: Option<String>,
-    ) -> Result<Vec<TrackInfo>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError> {
         log::info!(
             "Searching track info by owner FIO: {} {} {}",
             firstname.as_deref().unwrap_or(""),
@@ -102,105 +151,65 @@ impl TrackInfoSearcher for SearchService {
             lastname.as_deref().unwrap_or("")
         );
 
-        let track_infos = self
-            .track_info_repo
-            .get_track_info_by_user_fio(
-                firstname.as_deref(),
-                surname.as_deref(),
-                lastname.as_deref(),
-            )
-            .await?;
-
-        log::debug!("Found {} track info records by FIO", track_infos.len());
-        Ok(track_infos)
+        self.search_track_info(firstname, surname, lastname, None, None, None, page)
+            .await
     }
 
     async fn search_track_info_by_owner_passport(
         &self,
         passport: &Document,
-    ) -> Result<Vec<TrackInfo>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError> {
         log::info!("Searching track info by passport: {}", passport.number);
 
-        if !Validator::is_valid_passport(passport) {
-            log::warn!("Invalid passport format: {:#?}", passport);
-            return Err(ServiceError::InvalidDataError("passport".to_string()));
-        }
-
-        let track_infos = self
-            .track_info_repo
-            .get_track_info_by_user_passport(passport)
-            .await?;
-
-        log::debug!("Found {} track info records by passport", track_infos.len());
-        Ok(track_infos)
+        self.search_track_info(None, None, None, Some(passport.clone()), None, None, page)
+            .await
     }
 
     async fn search_track_info_by_gos_num_mask(
         &self,
         gos_num_mask: &String,
-    ) -> Result<Vec<TrackInfo>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError> {
         log::info!("Searching track info by gos number mask: {}", gos_num_mask);
 
-        if !Validator::is_valid_gos_num_mask(gos_num_mask) {
-            log::warn!("Invalid gos number mask format: {}", gos_num_mask);
-            return Err(ServiceError::InvalidDataError(
-                "gos number mask".to_string(),
-            ));
-        }
-
-        let track_infos = self
-            .track_info_repo
-            .get_track_info_by_car_gos_number_mask(gos_num_mask)
-            .await?;
-
-        log::debug!(
-            "Found {} track info records by gos number mask",
-            track_infos.len()
-        );
-        Ok(track_infos)
+        self.search_track_info(
+            None,
+            None,
+            None,
+            None,
+            Some(gos_num_mask.clone()),
+            None,
+            page,
+        )
+        .await
     }
 
     async fn search_track_info_by_date(
         &self,
         date: &String,
-    ) -> Result<Vec<TrackInfo>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError> {
         log::info!("Searching track info by date: {}", date);
 
-        if !Validator::is_valid_date(date) {
-            log::warn!("Invalid date format: {}", date);
-            return Err(ServiceError::InvalidDataError("date".to_string()));
-        }
-
-        let track_infos = self.track_info_repo.get_track_info_by_date(date).await?;
-        log::debug!(
-            "Found {} track info records for date {}",
-            track_infos.len(),
-            date
-        );
-        Ok(track_infos)
+        self.search_track_info(None, None, None, None, None, Some(date.clone()), page)
+            .await
     }
 }
 
 #[async_trait]
 impl CarSearcher for SearchService {
-    async fn search_car(
-        &self,
-        firstname: Option<String>,
-        surname: Option<String>,
-        lastname: Option<String>,
-        passport: Option<Document>,
-        gos_num_mask: Option<String>,
-    ) -> Result<Vec<Car>, ServiceError> {
-        log::info!(
-            "Searching cars by filters: {:?} {:?} {:?} {:?} {:?}",
-            firstname.as_deref(),
-            surname.as_deref(),
-            lastname.as_deref(),
-            passport,
-            gos_num_mask.as_deref()
-        );
+    async fn search_car(&self, filter: CarFilter, page: &Page) -> Result<Paged<Car>, ServiceError> {
+        log::info!("Searching cars by filters: {:?} ({:?})", filter, page);
 
-        if let Some(gsm) = &gos_num_mask {
+        Self::check_page(page)?;
+        if page.sort == SortKey::track_time {
+            log::warn!("Cars can't be sorted by track time");
+            return Err(ServiceError::InvalidDataError("sort key".to_string()));
+        }
+        Self::check_names([&filter.firstname, &filter.surname, &filter.lastname])?;
+
+        if let Some(gsm) = &filter.gos_num_mask {
             if !Validator::is_valid_gos_num_mask(&gsm) {
                 log::warn!("Invalid gos number mask format: {}", &gsm);
                 return Err(ServiceError::InvalidDataError(
@@ -209,25 +218,44 @@ impl CarSearcher for SearchService {
             }
         }
 
-        if let Some(psprt) = &passport {
+        if let Some(psprt) = &filter.passport {
             if !Validator::is_valid_passport(&psprt) {
                 log::warn!("Invalid passport format: {:#?}", &psprt);
                 return Err(ServiceError::InvalidDataError("passport".to_string()));
             }
         }
 
-        let cars = self
-            .car_repo
-            .get_cars_by_filters(
-                firstname.as_deref(),
-                surname.as_deref(),
-                lastname.as_deref(),
-                passport,
-                gos_num_mask.as_deref(),
-            )
-            .await?;
+        if let Some(vin) = &filter.vin {
+            if !Validator::is_valid_vin_prefix(vin) {
+                log::warn!("Invalid VIN format: {}", vin);
+                return Err(ServiceError::InvalidDataError("vin".to_string()));
+            }
+        }
 
-        log::debug!("Found {} cars by filters", cars.len());
+        let max_year = chrono::Local::now().year() as u16 + 1;
+        let years = [filter.year_from, filter.year_to];
+        if let Some(year) = years
+            .into_iter()
+            .flatten()
+            .find(|year| !(MIN_RELEASE_YEAR..=max_year).contains(year))
+        {
+            log::warn!("Invalid release year: {}", year);
+            return Err(ServiceError::InvalidDataError("release year".to_string()));
+        }
+        if let (Some(from), Some(to)) = (filter.year_from, filter.year_to) {
+            if from > to {
+                log::warn!("Invalid release year range: {} - {}", from, to);
+                return Err(ServiceError::InvalidDataError("release year".to_string()));
+            }
+        }
+
+        let cars = self.car_repo.get_cars_by_filters(&filter, page).await?;
+
+        log::debug!(
+            "Found {} cars by filters, {} on page",
+            cars.total,
+            cars.items.len()
+        );
         Ok(cars)
     }
 
@@ -236,7 +264,8 @@ impl CarSearcher for SearchService {
         firstname: Option<String>,
         surname: Option<String>,
         lastname: Option<String>,
-    ) -> Result<Vec<Car>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<Car>, ServiceError> {
         log::info!(
             "Searching cars by owner FIO: {} {} {}",
             firstname.as_deref().unwrap_or(""),
@@ -244,54 +273,118 @@ impl CarSearcher for SearchService {
             lastname.as_deref().unwrap_or("")
         );
 
-        let cars = self
-            .car_repo
-            .get_car_by_owner_fio(
-                firstname.as_deref(),
-                surname.as_deref(),
-                lastname.as_deref(),
-            )
-            .await?;
-
-        log::debug!("Found {} cars by FIO", cars.len());
-        Ok(cars)
+        let filter = CarFilter {
+            firstname,
+            surname,
+            lastname,
+            ..CarFilter::default()
+        };
+        self.search_car(filter, page).await
     }
 
     async fn search_cars_by_owner_passport(
         &self,
         passport: &Document,
-    ) -> Result<Vec<Car>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<Car>, ServiceError> {
         log::info!("Searching cars by passport: {}", passport.number);
 
-        if !Validator::is_valid_passport(passport) {
-            log::warn!("Invalid passport format: {:#?}", passport);
-            return Err(ServiceError::InvalidDataError("passport".to_string()));
-        }
-
-        let cars = self.car_repo.get_car_by_owner_passport(passport).await?;
-        log::debug!("Found {} cars by passport", cars.len());
-        Ok(cars)
+        let filter = CarFilter {
+            passport: Some(passport.clone()),
+            ..CarFilter::default()
+        };
+        self.search_car(filter, page).await
     }
 
     async fn search_cars_by_gos_num_mask(
         &self,
         gos_num_mask: &String,
-    ) -> Result<Vec<Car>, ServiceError> {
+        page: &Page,
+    ) -> Result<Paged<Car>, ServiceError> {
         log::info!("Searching cars by gos number mask: {}", gos_num_mask);
 
-        if !Validator::is_valid_gos_num_mask(gos_num_mask) {
-            log::warn!("Invalid gos number mask format: {}", gos_num_mask);
-            return Err(ServiceError::InvalidDataError(
-                "gos number mask".to_string(),
-            ));
+        let filter = CarFilter {
+            gos_num_mask: Some(gos_num_mask.clone()),
+            ..CarFilter::default()
+        };
+        self.search_car(filter, page).await
+    }
+
+    async fn get_car_by_vin(&self, vin: &str) -> Result<DetailedCar, ServiceError> {
+        log::info!("Getting car by VIN: {}", vin);
+
+        if !Validator::is_valid_vin(vin) {
+            log::warn!("Invalid VIN format: {}", vin);
+            return Err(ServiceError::InvalidDataError("vin".to_string()));
         }
 
-        let cars = self
+        let mut cars = self
             .car_repo
-            .get_car_by_gos_number_mask(gos_num_mask)
+            .get_detailed_cars_by_vins(&[vin.to_string()])
             .await?;
+        if cars.is_empty() {
+            log::warn!("Car with VIN {} not found", vin);
+            return Err(ServiceError::NotFoundError("car".to_string()));
+        }
 
-        log::debug!("Found {} cars by gos number mask", cars.len());
-        Ok(cars)
+        Ok(cars.swap_remove(0))
+    }
+
+    async fn get_detailed_cars(&self, cars: &[Car]) -> Result<Vec<DetailedCar>, ServiceError> {
+        log::info!("Getting registration cards of {} cars", cars.len());
+
+        let mut vins: Vec<String> = cars.iter().map(|car| car.vin.clone()).collect();
+        vins.sort();
+        vins.dedup();
+        if vins.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut detailed = self.car_repo.get_detailed_cars_by_vins(&vins).await?;
+        detailed.sort_by_key(|d| cars.iter().position(|car| car.vin == d.car.vin));
+
+        log::debug!("Found {} registration cards", detailed.len());
+        Ok(detailed)
+    }
+
+    async fn get_owner_history(
+        &self,
+        vin: Option<&str>,
+        gos_num: Option<&str>,
+    ) -> Result<Vec<OwnershipRecord>, ServiceError> {
+        log::info!(
+            "Getting owner history by VIN {:?} or gos number {:?}",
+            vin,
+            gos_num
+        );
+
+        let history = match (vin, gos_num) {
+            (Some(vin), None) => {
+                if !Validator::is_valid_vin(vin) {
+                    log::warn!("Invalid VIN format: {}", vin);
+                    return Err(ServiceError::InvalidDataError("vin".to_string()));
+                }
+                self.car_repo.get_owner_history_by_vin(vin).await
+            }
+            (None, Some(gos_num)) => {
+                if !Validator::is_valid_gos_num(gos_num) {
+                    log::warn!("Invalid gos number format: {}", gos_num);
+                    return Err(ServiceError::InvalidDataError("gos number".to_string()));
+                }
+                self.car_repo.get_owner_history_by_gos_num(gos_num).await
+            }
+            _ => {
+                log::warn!("Owner history requested without a single car identifier");
+                return Err(ServiceError::InvalidDataError("car identifier".to_string()));
+            }
+        };
+
+        let history = history.map_err(|e| match e {
+            DataAccessError::NotFoundError(e) => ServiceError::NotFoundError(e),
+            e => ServiceError::DataAccessError(e),
+        })?;
+
+        log::debug!("Found {} owner history records", history.len());
+        Ok(history)
     }
 }
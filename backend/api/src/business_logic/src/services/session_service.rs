@@ -0,0 +1,183 @@
+use crate::error::ServiceError;
+use crate::services_traits::SessionManager;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use async_trait::async_trait;
+use data_access::repositories_traits::{SessionRepository, UserRepository};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use models::{Session, User};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const DEFAULT_ACCESS_TTL_SECS: u64 = 15 * 60;
+const DEFAULT_REFRESH_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+const REFRESH_TOKEN_BYTES: usize = 32;
+const MIN_JWT_SECRET_BYTES: usize = 32;
+const PLACEHOLDER_JWT_SECRET: &str = "change-me-in-production";
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Claims {
+    sub: String,
+    iat: u64,
+    exp: u64,
+}
+
+pub struct SessionService {
+    user_repo: Arc<dyn UserRepository>,
+    session_repo: Arc<dyn SessionRepository>,
+    jwt_secret: String,
+    access_ttl_secs: u64,
+    refresh_ttl_secs: u64,
+}
+
+impl SessionService {
+    pub fn from(
+        user_repo: Arc<dyn UserRepository>,
+        session_repo: Arc<dyn SessionRepository>,
+        jwt_secret: &str,
+    ) -> Result<Self, ServiceError> {
+        Self::check_jwt_secret(jwt_secret)?;
+
+        Ok(SessionService {
+            user_repo,
+            session_repo,
+            jwt_secret: jwt_secret.to_string(),
+            access_ttl_secs: DEFAULT_ACCESS_TTL_SECS,
+            refresh_ttl_secs: DEFAULT_REFRESH_TTL_SECS,
+        })
+    }
+
+    // Пустой, шаблонный или короткий секрет позволяет подделывать HS256 токены
+    fn check_jwt_secret(jwt_secret: &str) -> Result<(), ServiceError> {
+        if jwt_secret.trim() == PLACEHOLDER_JWT_SECRET || jwt_secret.len() < MIN_JWT_SECRET_BYTES {
+            log::error!(
+                "JWT secret must be a non-default value of at least {} bytes",
+                MIN_JWT_SECRET_BYTES
+            );
+            return Err(ServiceError::InvalidDataError("jwt secret".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn with_access_ttl(mut self, secs: u64) -> Self {
+        self.access_ttl_secs = secs;
+        self
+    }
+
+    pub fn with_refresh_ttl(mut self, secs: u64) -> Self {
+        self.refresh_ttl_secs = secs;
+        self
+    }
+}
+
+unsafe impl Send for SessionService {}
+unsafe impl Sync for SessionService {}
+
+impl SessionService {
+    fn gen_access_token(&self, email: &str) -> Result<String, ServiceError> {
+        let iat = jsonwebtoken::get_current_timestamp();
+        let claims = Claims {
+            sub: email.to_string(),
+            iat,
+            exp: iat + self.access_ttl_secs,
+        };
+
+        jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
+        )
+        .map_err(|e| {
+            log::error!("Failed to sign access token: {}", e);
+            ServiceError::InternalError("access token signing".to_string())
+        })
+    }
+
+    fn gen_refresh_token() -> String {
+        let mut bytes = [0u8; REFRESH_TOKEN_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn decode_access_token(&self, access_token: &str) -> Result<Claims, ServiceError> {
+        jsonwebtoken::decode::<Claims>(
+            access_token,
+            &DecodingKey::from_secret(self.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map(|data| data.claims)
+        .map_err(|e| {
+            log::warn!("Invalid access token: {}", e);
+            ServiceError::InvalidDataError("token".to_string())
+        })
+    }
+}
+
+#[async_trait]
+impl SessionManager for SessionService {
+    async fn open_session(&self, user: &User) -> Result<Session, ServiceError> {
+        log::info!("Opening session for user: {}", user.email);
+
+        let access_token = self.gen_access_token(&user.email)?;
+        let refresh_token = Self::gen_refresh_token();
+
+        self.session_repo
+            .insert_session(&refresh_token, &user.email, self.refresh_ttl_secs)
+            .await?;
+
+        log::info!("Session opened for user: {}", user.email);
+        Ok(Session {
+            access_token,
+            refresh_token,
+            expires_in: self.access_ttl_secs,
+        })
+    }
+
+    async fn refresh_session(&self, refresh_token: &str) -> Result<Session, ServiceError> {
+        log::info!("Refreshing session");
+
+        let email = match self.session_repo.take_session(refresh_token).await? {
+            Some(email) => email,
+            None => {
+                log::warn!("Refresh attempt with unknown or expired token");
+                return Err(ServiceError::InvalidDataError("token".to_string()));
+            }
+        };
+
+        let user = match self.user_repo.get_user_by_email(&email).await? {
+            Some(user) => user,
+            None => {
+                log::warn!("Refresh attempt for non-existent user: {}", email);
+                return Err(ServiceError::InvalidDataError("token".to_string()));
+            }
+        };
+
+        self.open_session(&user).await
+    }
+
+    async fn close_session(&self, refresh_token: &str) -> Result<(), ServiceError> {
+        log::info!("Closing session");
+
+        self.session_repo.delete_session(refresh_token).await?;
+
+        log::info!("Session closed");
+        Ok(())
+    }
+
+    async fn get_session_user(&self, access_token: &str) -> Result<User, ServiceError> {
+        let claims = self.decode_access_token(access_token)?;
+        log::debug!("Resolving user from access token: {}", claims.sub);
+
+        match self.user_repo.get_user_by_email(&claims.sub).await? {
+            Some(user) => Ok(user),
+            None => {
+                log::warn!("Access token for non-existent user: {}", claims.sub);
+                Err(ServiceError::InvalidDataError("token".to_string()))
+            }
+        }
+    }
+
+    async fn get_session_expiry(&self, access_token: &str) -> Result<u64, ServiceError> {
+        let claims = self.decode_access_token(access_token)?;
+        Ok(claims.exp)
+    }
+}
@@ -1,73 +1,244 @@
 use super::validator::Validator;
 use crate::error::ServiceError;
 use async_trait::async_trait;
-use data_access::repositories_traits::SnapRepository;
-use models::{Camera, Snap};
+use chrono::{NaiveDateTime, Utc};
+use data_access::error::DataAccessError;
+use data_access::repositories_traits::{CameraRepository, SnapRepository};
+use models::{Camera, IncomingSnap, Snap};
 
-use crate::services_traits;
+use crate::services_traits::{self, SpeedController, WatchlistManager};
+use crate::snap_hub::SnapHub;
+use std::collections::{hash_map::Entry, HashMap};
+use std::sync::Arc;
+
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+// Допустимое расхождение координат камеры с реестром (градусы)
+const LOCATION_EPS: f64 = 1e-6;
+// Время снимков московское
+const MOSCOW_UTC_OFFSET_HOURS: i64 = 3;
 
 pub struct SnapSendService {
-    snap_repo: Box<dyn SnapRepository>,
+    snap_repo: Arc<dyn SnapRepository>,
+    camera_repo: Arc<dyn CameraRepository>,
+    hub: Option<Arc<SnapHub>>,
+    watchlist: Option<Arc<dyn WatchlistManager>>,
+    speed_controller: Option<Arc<dyn SpeedController>>,
 }
 
 impl SnapSendService {
-    pub fn from(snap_repo: Box<dyn SnapRepository>) -> Self {
-        SnapSendService { snap_repo }
+    pub fn from(
+        snap_repo: Arc<dyn SnapRepository>,
+        camera_repo: Arc<dyn CameraRepository>,
+    ) -> Self {
+        SnapSendService {
+            snap_repo,
+            camera_repo,
+            hub: None,
+            watchlist: None,
+            speed_controller: None,
+        }
+    }
+
+    // Принятые снимки рассылаются подписчикам отслеживания в реальном времени
+    pub fn with_hub(mut self, hub: Arc<SnapHub>) -> Self {
+        self.hub = Some(hub);
+        self
+    }
+
+    // Принятые снимки проверяются по списку розыска
+    pub fn with_watchlist(mut self, watchlist: Arc<dyn WatchlistManager>) -> Self {
+        self.watchlist = Some(watchlist);
+        self
+    }
+
+    // Снимки радаров проверяются на превышение скорости
+    pub fn with_speed_controller(mut self, speed_controller: Arc<dyn SpeedController>) -> Self {
+        self.speed_controller = Some(speed_controller);
+        self
+    }
+
+    fn publish(&self, snaps: &[Snap]) {
+        if let Some(hub) = &self.hub {
+            snaps.iter().for_each(|snap| hub.publish(snap));
+        }
+    }
+
+    // Ошибка проверки не отменяет прием уже сохраненного снимка
+    async fn check_watchlist(&self, snaps: &[Snap]) {
+        let Some(watchlist) = &self.watchlist else {
+            return;
+        };
+        for snap in snaps {
+            if let Err(e) = watchlist.check_snap(snap).await {
+                log::error!(
+                    "Failed to check snap of {} by watchlist: {}",
+                    snap.gos_num,
+                    e
+                );
+            }
+        }
+    }
+
+    async fn check_speed(&self, snaps: &[Snap]) {
+        let Some(speed_controller) = &self.speed_controller else {
+            return;
+        };
+        for snap in snaps {
+            if let Err(e) = speed_controller.check_snap(snap).await {
+                log::error!("Failed to check speed of {}: {}", snap.gos_num, e);
+            }
+            if let Err(e) = speed_controller.check_sections(snap).await {
+                log::error!("Failed to check sections speed of {}: {}", snap.gos_num, e);
+            }
+        }
     }
 }
 
 unsafe impl Send for SnapSendService {}
 unsafe impl Sync for SnapSendService {}
 
-#[async_trait]
-impl services_traits::SnapSender for SnapSendService {
-    async fn insert_snap(
-        &self,
-        camera: &Camera,
-        speed: Option<u16>,
-        time: &String,
-        date: &String,
-        gos_num: &String,
-    ) -> Result<(), ServiceError> {
-        log::info!(
-            "Starting snap insertion for vehicle {} at {} {}",
-            gos_num,
-            date,
-            time
-        );
-
-        if !Validator::is_valid_gos_num(gos_num) {
-            log::warn!("Invalid vehicle number format: {}", gos_num);
+impl SnapSendService {
+    fn validate_snap(snap: &IncomingSnap) -> Result<(), ServiceError> {
+        if !Validator::is_valid_gos_num(&snap.gos_num) {
+            log::warn!("Invalid vehicle number format: {}", snap.gos_num);
             return Err(ServiceError::InvalidDataError("gos number".to_string()));
         }
 
-        if !Validator::is_valid_date(date) {
-            log::warn!("Invalid date format: {}", date);
+        if !Validator::is_valid_date(&snap.date) {
+            log::warn!("Invalid date format: {}", snap.date);
             return Err(ServiceError::InvalidDataError("date".to_string()));
         }
 
-        if !Validator::is_valid_time(time) {
-            log::warn!("Invalid time format: {}", time);
+        if !Validator::is_valid_time(&snap.time) {
+            log::warn!("Invalid time format: {}", snap.time);
             return Err(ServiceError::InvalidDataError("time".to_string()));
         }
 
-        log::debug!("Creating snap object for camera ID: {}", camera.id);
-        let snap = Snap {
-            camera: *camera,
-            speed: speed.clone(),
-            time: time.clone(),
-            date: date.clone(),
-            gos_num: gos_num.clone(),
+        // Снимок из будущего отклонит основное хранилище при переносе из буфера
+        let moscow_now =
+            (Utc::now() + chrono::Duration::hours(MOSCOW_UTC_OFFSET_HOURS)).naive_utc();
+        let is_future = NaiveDateTime::parse_from_str(
+            &format!("{} {}", snap.date, snap.time),
+            "%d.%m.%Y %H:%M",
+        )
+        .is_ok_and(|datetime| datetime > moscow_now);
+        if is_future {
+            log::warn!("Snap from the future: {} {}", snap.date, snap.time);
+            return Err(ServiceError::InvalidDataError("datetime".to_string()));
+        }
+
+        if let Some(key) = &snap.idempotency_key {
+            if key.trim().is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+                log::warn!("Invalid idempotency key: {}", key);
+                return Err(ServiceError::InvalidDataError(
+                    "idempotency key".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stored_camera(&self, camera_id: usize) -> Result<Option<Camera>, ServiceError> {
+        match self.camera_repo.get_camera_by_id(camera_id).await {
+            Ok(camera) => Ok(Some(camera)),
+            Err(DataAccessError::NotFoundError(_)) => Ok(None),
+            Err(e) => Err(ServiceError::DataAccessError(e)),
+        }
+    }
+
+    fn is_same_camera(reported: &Camera, stored: &Camera) -> bool {
+        reported.id == stored.id
+            && reported.is_radar == stored.is_radar
+            && (reported.location.latitude - stored.location.latitude).abs() <= LOCATION_EPS
+            && (reported.location.longitude - stored.location.longitude).abs() <= LOCATION_EPS
+    }
+
+    // Данные камеры берутся из реестра, а не из запроса
+    fn check_camera(snap: &IncomingSnap, camera: Option<&Camera>) -> Result<Camera, ServiceError> {
+        let Some(camera) = camera else {
+            log::warn!("Snap from unknown camera {}", snap.camera_id);
+            return Err(ServiceError::InvalidDataError("camera".to_string()));
         };
 
+        if !camera.is_active {
+            log::warn!("Snap from decommissioned camera {}", snap.camera_id);
+            return Err(ServiceError::InvalidDataError(
+                "decommissioned camera".to_string(),
+            ));
+        }
+
+        if let Some(reported) = &snap.reported_camera {
+            if !Self::is_same_camera(reported, camera) {
+                log::warn!(
+                    "Camera {} data doesn't match registry: {:?}",
+                    snap.camera_id,
+                    reported
+                );
+                return Err(ServiceError::InvalidDataError(
+                    "camera mismatch".to_string(),
+                ));
+            }
+        }
+
+        if snap.speed.is_some() && !camera.is_radar {
+            log::warn!("Speed from camera {} without radar", snap.camera_id);
+            return Err(ServiceError::InvalidDataError(
+                "speed without radar".to_string(),
+            ));
+        }
+
+        Ok(camera.clone())
+    }
+
+    fn into_snap(snap: &IncomingSnap, camera: Camera) -> Snap {
+        Snap {
+            camera,
+            speed: snap.speed,
+            time: snap.time.clone(),
+            date: snap.date.clone(),
+            gos_num: snap.gos_num.clone(),
+            idempotency_key: snap.idempotency_key.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl services_traits::SnapSender for SnapSendService {
+    async fn insert_snap(&self, snap: &IncomingSnap) -> Result<(), ServiceError> {
+        log::info!(
+            "Starting snap insertion for vehicle {} at {} {}",
+            snap.gos_num,
+            snap.date,
+            snap.time
+        );
+
+        Self::validate_snap(snap)?;
+        log::debug!("Resolving camera ID: {}", snap.camera_id);
+        let camera = Self::check_camera(snap, self.stored_camera(snap.camera_id).await?.as_ref())?;
+        let snap = Self::into_snap(snap, camera);
+
         log::debug!("Inserting snap into repository");
         match self.snap_repo.insert_snap(&snap).await {
-            Ok(_) => {
+            Ok(true) => {
                 log::info!(
                     "Successfully inserted snap for vehicle {} at {} {}",
-                    gos_num,
-                    date,
-                    time
+                    snap.gos_num,
+                    snap.date,
+                    snap.time
+                );
+                self.publish(std::slice::from_ref(&snap));
+                self.check_watchlist(std::slice::from_ref(&snap)).await;
+                self.check_speed(std::slice::from_ref(&snap)).await;
+                Ok(())
+            }
+            // Повтор уже принятого снимка подтверждается без повторной рассылки и проверок
+            Ok(false) => {
+                log::info!(
+                    "Snap for vehicle {} at {} {} already accepted",
+                    snap.gos_num,
+                    snap.date,
+                    snap.time
                 );
                 Ok(())
             }
@@ -77,4 +248,69 @@ impl services_traits::SnapSender for SnapSendService {
             }
         }
     }
+
+    async fn insert_snaps(
+        &self,
+        snaps: &[IncomingSnap],
+    ) -> Result<Vec<Result<(), ServiceError>>, ServiceError> {
+        log::info!("Starting batch insertion of {} snaps", snaps.len());
+
+        let mut results: Vec<Result<(), ServiceError>> = Vec::with_capacity(snaps.len());
+        let mut valid_snaps: Vec<Snap> = Vec::with_capacity(snaps.len());
+        // Ошибка чтения камеры отклоняет только снимки этой камеры, а не весь пакет
+        let mut cameras: HashMap<usize, Option<Option<Camera>>> = HashMap::new();
+        for snap in snaps {
+            if let Err(e) = Self::validate_snap(snap) {
+                results.push(Err(e));
+                continue;
+            }
+            if let Entry::Vacant(entry) = cameras.entry(snap.camera_id) {
+                let camera = match self.stored_camera(snap.camera_id).await {
+                    Ok(camera) => Some(camera),
+                    Err(e) => {
+                        log::error!("Failed to get camera {}: {}", snap.camera_id, e);
+                        None
+                    }
+                };
+                entry.insert(camera);
+            }
+            let Some(camera) = &cameras[&snap.camera_id] else {
+                results.push(Err(ServiceError::InternalError(
+                    "camera lookup".to_string(),
+                )));
+                continue;
+            };
+            match Self::check_camera(snap, camera.as_ref()) {
+                Ok(camera) => {
+                    valid_snaps.push(Self::into_snap(snap, camera));
+                    results.push(Ok(()));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+        log::debug!(
+            "{} of {} snaps passed validation",
+            valid_snaps.len(),
+            snaps.len()
+        );
+
+        let inserted = match self.snap_repo.insert_snap_batch(&valid_snaps).await {
+            Ok(inserted) => inserted,
+            Err(e) => {
+                log::error!("Failed to insert snap batch: {}", e);
+                return Err(ServiceError::DataAccessError(e));
+            }
+        };
+        self.publish(&inserted);
+        self.check_watchlist(&inserted).await;
+        self.check_speed(&inserted).await;
+
+        log::info!(
+            "Successfully inserted {} snaps, replayed {}, rejected {}",
+            inserted.len(),
+            valid_snaps.len() - inserted.len(),
+            snaps.len() - valid_snaps.len()
+        );
+        Ok(results)
+    }
 }
@@ -0,0 +1,349 @@
+use super::validator::Validator;
+use crate::error::ServiceError;
+use crate::services_traits::SpeedController;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+use data_access::error::DataAccessError;
+use data_access::repositories_traits::{
+    CameraRepository, RoadSectionRepository, SnapRepository, SpeedViolationRepository,
+};
+use models::{RoadSection, Snap, SpeedViolation, ViolationSeverity};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+const DATETIME_FORMAT: &str = "%d.%m.%Y %H:%M";
+// Превышение, не считающееся нарушением (км/ч)
+pub const DEFAULT_SPEED_TOLERANCE: u16 = 20;
+const MAX_VIOLATIONS_PERIOD_DAYS: i64 = 31;
+const SECTIONS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+// Проезд участка дольше не учитывается
+const MAX_SECTION_TRAVEL_HOURS: i64 = 2;
+
+struct SectionsCache {
+    loaded_at: Instant,
+    sections: Arc<Vec<RoadSection>>,
+}
+
+pub struct SpeedControlService {
+    camera_repo: Arc<dyn CameraRepository>,
+    violation_repo: Arc<dyn SpeedViolationRepository>,
+    section_repo: Arc<dyn RoadSectionRepository>,
+    snap_repo: Arc<dyn SnapRepository>,
+    tolerance: u16,
+    sections: Mutex<Option<SectionsCache>>,
+}
+
+impl SpeedControlService {
+    pub fn from(
+        camera_repo: Arc<dyn CameraRepository>,
+        violation_repo: Arc<dyn SpeedViolationRepository>,
+        section_repo: Arc<dyn RoadSectionRepository>,
+        snap_repo: Arc<dyn SnapRepository>,
+    ) -> Self {
+        SpeedControlService {
+            camera_repo,
+            violation_repo,
+            section_repo,
+            snap_repo,
+            tolerance: DEFAULT_SPEED_TOLERANCE,
+            sections: Mutex::new(None),
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: u16) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+unsafe impl Send for SpeedControlService {}
+unsafe impl Sync for SpeedControlService {}
+
+impl SpeedControlService {
+    // Категория считается по полному превышению ограничения (скорость минус
+    // ограничение), а не по превышению сверх допуска: границы 40/60/80 км/ч не
+    // зависят от speed.violation_tolerance. Превышение в пределах допуска сюда не
+    // попадает, поэтому low фактически начинается с tolerance + 1
+    fn severity(overspeed: u16) -> ViolationSeverity {
+        match overspeed {
+            0..=40 => ViolationSeverity::low,
+            41..=60 => ViolationSeverity::medium,
+            61..=80 => ViolationSeverity::high,
+            _ => ViolationSeverity::critical,
+        }
+    }
+
+    fn snap_datetime(snap: &Snap) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(&format!("{} {}", snap.date, snap.time), DATETIME_FORMAT).ok()
+    }
+
+    fn parse_datetime(datetime: &str) -> Result<NaiveDateTime, ServiceError> {
+        NaiveDateTime::parse_from_str(datetime, DATETIME_FORMAT).map_err(|_| {
+            log::warn!("Invalid datetime format: {}", datetime);
+            ServiceError::InvalidDataError("datetime".to_string())
+        })
+    }
+
+    async fn cached_sections(&self) -> Result<Arc<Vec<RoadSection>>, ServiceError> {
+        if let Some(cache) = self.sections.lock().unwrap().as_ref() {
+            if cache.loaded_at.elapsed() < SECTIONS_CACHE_TTL {
+                return Ok(cache.sections.clone());
+            }
+        }
+
+        let sections = Arc::new(self.section_repo.get_sections().await?);
+        log::debug!("Loaded {} road sections into cache", sections.len());
+        *self.sections.lock().unwrap() = Some(SectionsCache {
+            loaded_at: Instant::now(),
+            sections: sections.clone(),
+        });
+        Ok(sections)
+    }
+
+    fn invalidate_sections(&self) {
+        *self.sections.lock().unwrap() = None;
+    }
+
+    // Время снимка с точностью до минуты: берется наибольшее возможное время
+    // проезда, чтобы не завышать среднюю скорость
+    fn section_speed(section: &RoadSection, travel: Duration) -> u16 {
+        let minutes = (travel.num_minutes() + 1) as f64;
+        (section.distance as f64 / 1000.0 / (minutes / 60.0)).round() as u16
+    }
+
+    async fn check_camera_exist(&self, camera_id: usize) -> Result<(), ServiceError> {
+        match self.camera_repo.get_camera_by_id(camera_id).await {
+            Ok(_) => Ok(()),
+            Err(DataAccessError::NotFoundError(e)) => Err(ServiceError::NotFoundError(e)),
+            Err(e) => Err(ServiceError::DataAccessError(e)),
+        }
+    }
+
+    async fn record_violation(
+        &self,
+        mut violation: SpeedViolation,
+    ) -> Result<Option<SpeedViolation>, ServiceError> {
+        // Повторно доставленный снимок не создает новое нарушение
+        let Some(id) = self.violation_repo.insert_violation(&violation).await? else {
+            return Ok(None);
+        };
+        violation.id = id;
+
+        log::info!(
+            "Speed violation: {} at camera {} ({} km/h, limit {} km/h, section {:?})",
+            violation.gos_num,
+            violation.camera_id,
+            violation.speed,
+            violation.speed_limit,
+            violation.section_id
+        );
+        Ok(Some(violation))
+    }
+}
+
+#[async_trait]
+impl SpeedController for SpeedControlService {
+    async fn check_snap(&self, snap: &Snap) -> Result<Option<SpeedViolation>, ServiceError> {
+        let Some(speed) = snap.speed else {
+            return Ok(None);
+        };
+        // Камера снимка уже взята из хранилища при приеме, поэтому
+        // ограничение учитывает последние изменения камеры
+        let Some(speed_limit) = snap.camera.speed_limit else {
+            return Ok(None);
+        };
+        if speed <= speed_limit.saturating_add(self.tolerance) {
+            return Ok(None);
+        }
+
+        self.record_violation(SpeedViolation {
+            id: 0,
+            gos_num: snap.gos_num.clone(),
+            camera_id: snap.camera.id,
+            cords: snap.camera.location,
+            date: snap.date.clone(),
+            time: snap.time.clone(),
+            speed,
+            speed_limit,
+            severity: Self::severity(speed - speed_limit),
+            section_id: None,
+        })
+        .await
+    }
+
+    // Снимки пакетной выгрузки приходят не по порядку: участок проверяется и по
+    // снимку выезда (въезд ищется раньше), и по снимку въезда (выезд ищется позже)
+    async fn check_sections(&self, snap: &Snap) -> Result<Vec<SpeedViolation>, ServiceError> {
+        let sections = self.cached_sections().await?;
+        let related: Vec<&RoadSection> = sections
+            .iter()
+            .filter(|section| {
+                section.end_camera_id == snap.camera.id || section.start_camera_id == snap.camera.id
+            })
+            .collect();
+        if related.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some(snapped_at) = Self::snap_datetime(snap) else {
+            log::warn!("Invalid snap datetime: {} {}", snap.date, snap.time);
+            return Ok(Vec::new());
+        };
+
+        let max_travel = Duration::hours(MAX_SECTION_TRAVEL_HOURS);
+        let snaps: Vec<(Snap, NaiveDateTime)> = self
+            .snap_repo
+            .get_car_snaps_by_period(
+                &snap.gos_num,
+                &(snapped_at - max_travel),
+                &(snapped_at + max_travel),
+            )
+            .await?
+            .into_iter()
+            .filter_map(|s| {
+                let at = Self::snap_datetime(&s)?;
+                Some((s, at))
+            })
+            .collect();
+
+        let mut violations = Vec::new();
+        for section in related {
+            let passes = snaps.iter().filter(|(s, _)| {
+                s.camera.id == section.start_camera_id || s.camera.id == section.end_camera_id
+            });
+
+            // Въезд - последний проезд камер участка до выезда, выезд - первый после въезда
+            let pass = if section.end_camera_id == snap.camera.id {
+                passes
+                    .filter(|(_, at)| *at < snapped_at)
+                    .max_by_key(|(_, at)| *at)
+                    .filter(|(s, _)| s.camera.id == section.start_camera_id)
+                    .map(|(_, entered_at)| (snap, *entered_at, snapped_at))
+            } else {
+                passes
+                    .filter(|(_, at)| *at > snapped_at)
+                    .min_by_key(|(_, at)| *at)
+                    .filter(|(s, _)| s.camera.id == section.end_camera_id)
+                    .map(|(exit, exited_at)| (exit, snapped_at, *exited_at))
+            };
+            let Some((exit, entered_at, exited_at)) = pass else {
+                continue;
+            };
+
+            let speed = Self::section_speed(section, exited_at - entered_at);
+            if speed <= section.speed_limit.saturating_add(self.tolerance) {
+                continue;
+            }
+
+            // Нарушение фиксируется на выезде, поэтому проверка с обеих сторон не создаст дубль
+            let violation = self
+                .record_violation(SpeedViolation {
+                    id: 0,
+                    gos_num: exit.gos_num.clone(),
+                    camera_id: exit.camera.id,
+                    cords: exit.camera.location,
+                    date: exit.date.clone(),
+                    time: exit.time.clone(),
+                    speed,
+                    speed_limit: section.speed_limit,
+                    severity: Self::severity(speed - section.speed_limit),
+                    section_id: Some(section.id),
+                })
+                .await?;
+            violations.extend(violation);
+        }
+        Ok(violations)
+    }
+
+    async fn add_section(
+        &self,
+        start_camera_id: usize,
+        end_camera_id: usize,
+        distance: u32,
+        speed_limit: u16,
+    ) -> Result<RoadSection, ServiceError> {
+        log::info!(
+            "Adding road section from camera {} to camera {} ({} m, {} km/h)",
+            start_camera_id,
+            end_camera_id,
+            distance,
+            speed_limit
+        );
+
+        if start_camera_id == end_camera_id || distance == 0 || speed_limit == 0 {
+            log::warn!("Invalid road section");
+            return Err(ServiceError::InvalidDataError("road section".to_string()));
+        }
+        self.check_camera_exist(start_camera_id).await?;
+        self.check_camera_exist(end_camera_id).await?;
+
+        let mut section = RoadSection {
+            id: 0,
+            start_camera_id,
+            end_camera_id,
+            distance,
+            speed_limit,
+        };
+        let Some(id) = self.section_repo.insert_section(&section).await? else {
+            return Err(ServiceError::IsExistError("road section".to_string()));
+        };
+        section.id = id;
+        self.invalidate_sections();
+
+        log::info!("Road section {} added", id);
+        Ok(section)
+    }
+
+    async fn delete_section(&self, id: usize) -> Result<(), ServiceError> {
+        log::info!("Deleting road section {}", id);
+        match self.section_repo.delete_section(id).await {
+            Ok(_) => {
+                self.invalidate_sections();
+                Ok(())
+            }
+            Err(DataAccessError::NotFoundError(e)) => Err(ServiceError::NotFoundError(e)),
+            Err(e) => Err(ServiceError::DataAccessError(e)),
+        }
+    }
+
+    async fn get_sections(&self) -> Result<Vec<RoadSection>, ServiceError> {
+        log::info!("Getting road sections");
+        Ok(self.section_repo.get_sections().await?)
+    }
+
+    async fn get_violations(
+        &self,
+        gos_num: Option<&str>,
+        camera_id: Option<usize>,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<SpeedViolation>, ServiceError> {
+        log::info!(
+            "Getting speed violations by filters: {:?} {:?} {} - {}",
+            gos_num,
+            camera_id,
+            from,
+            to
+        );
+
+        if let Some(gos_num) = gos_num {
+            if !Validator::is_valid_gos_num(gos_num) {
+                log::warn!("Invalid vehicle number format: {}", gos_num);
+                return Err(ServiceError::InvalidDataError("gos number".to_string()));
+            }
+        }
+
+        let from = Self::parse_datetime(from)?;
+        let to = Self::parse_datetime(to)?;
+        if from > to || to - from > Duration::days(MAX_VIOLATIONS_PERIOD_DAYS) {
+            log::warn!("Invalid violations period: {} - {}", from, to);
+            return Err(ServiceError::InvalidDataError("period".to_string()));
+        }
+
+        let violations = self
+            .violation_repo
+            .get_violations_by_filters(gos_num, camera_id, &from, &to)
+            .await?;
+        log::info!("Getted {} speed violations", violations.len());
+        Ok(violations)
+    }
+}
@@ -0,0 +1,292 @@
+use super::validator::Validator;
+use crate::alert_hub::{AlertHub, AlertSubscription};
+use crate::error::ServiceError;
+use crate::services_traits::WatchlistManager;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use data_access::{error::DataAccessError, repositories_traits::WatchlistRepository};
+use models::{Snap, WatchlistAlert, WatchlistEntry};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DATETIME_FORMAT: &str = "%d.%m.%Y %H:%M";
+const MAX_REASON_LEN: usize = 256;
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+// Снимок записей списка для проверки потока снимков
+struct EntriesCache {
+    loaded_at: Instant,
+    entries: Arc<Vec<WatchlistEntry>>,
+}
+
+pub struct WatchlistService {
+    watchlist_repo: Arc<dyn WatchlistRepository>,
+    hub: Arc<AlertHub>,
+    cache: Mutex<Option<EntriesCache>>,
+    cache_ttl: Duration,
+}
+
+impl WatchlistService {
+    pub fn from(watchlist_repo: Arc<dyn WatchlistRepository>, hub: Arc<AlertHub>) -> Self {
+        WatchlistService {
+            watchlist_repo,
+            hub,
+            cache: Mutex::new(None),
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    // Записи, измененные другим экземпляром сервиса, применяются не позже чем через ttl
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+}
+
+unsafe impl Send for WatchlistService {}
+unsafe impl Sync for WatchlistService {}
+
+impl WatchlistService {
+    fn parse_datetime(datetime: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(datetime, DATETIME_FORMAT).ok()
+    }
+
+    fn validate_entry(
+        gos_num_mask: &str,
+        reason: &str,
+        expires_at: Option<&str>,
+    ) -> Result<(), ServiceError> {
+        if !Validator::is_valid_gos_num_mask(gos_num_mask) {
+            log::warn!("Invalid gos number mask format: {}", gos_num_mask);
+            return Err(ServiceError::InvalidDataError(
+                "gos number mask".to_string(),
+            ));
+        }
+
+        if reason.trim().is_empty() || reason.chars().count() > MAX_REASON_LEN {
+            log::warn!("Invalid watchlist reason: {}", reason);
+            return Err(ServiceError::InvalidDataError("reason".to_string()));
+        }
+
+        if let Some(expires_at) = expires_at {
+            if Self::parse_datetime(expires_at).is_none() {
+                log::warn!("Invalid expiration datetime format: {}", expires_at);
+                return Err(ServiceError::InvalidDataError("datetime".to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    // '*' заменяет один символ, маска с '*' вместо региона подходит для любого региона
+    fn matches_mask(mask: &str, gos_num: &str) -> bool {
+        let mask: Vec<char> = mask.chars().collect();
+        let gos_num: Vec<char> = gos_num.chars().collect();
+
+        let (mask, gos_num) = if mask.len() == 7 && mask[6] == '*' {
+            if gos_num.len() < 8 {
+                return false;
+            }
+            (&mask[..6], &gos_num[..6])
+        } else {
+            if mask.len() != gos_num.len() {
+                return false;
+            }
+            (&mask[..], &gos_num[..])
+        };
+
+        mask.iter().zip(gos_num).all(|(m, g)| *m == '*' || m == g)
+    }
+
+    fn is_active(entry: &WatchlistEntry, at: &NaiveDateTime) -> bool {
+        match entry.expires_at.as_deref().map(Self::parse_datetime) {
+            None => true,
+            Some(Some(expires_at)) => expires_at > *at,
+            Some(None) => {
+                log::warn!("Watchlist entry {} has invalid expiration", entry.id);
+                false
+            }
+        }
+    }
+
+    async fn cached_entries(&self) -> Result<Arc<Vec<WatchlistEntry>>, ServiceError> {
+        if let Some(cache) = self.cache.lock().unwrap().as_ref() {
+            if cache.loaded_at.elapsed() < self.cache_ttl {
+                return Ok(cache.entries.clone());
+            }
+        }
+
+        let entries = Arc::new(self.watchlist_repo.get_entries().await?);
+        log::debug!("Loaded {} watchlist entries into cache", entries.len());
+        *self.cache.lock().unwrap() = Some(EntriesCache {
+            loaded_at: Instant::now(),
+            entries: entries.clone(),
+        });
+        Ok(entries)
+    }
+
+    fn invalidate_cache(&self) {
+        *self.cache.lock().unwrap() = None;
+    }
+
+    fn map_not_found(e: DataAccessError) -> ServiceError {
+        match e {
+            DataAccessError::NotFoundError(e) => ServiceError::NotFoundError(e),
+            e => ServiceError::DataAccessError(e),
+        }
+    }
+}
+
+#[async_trait]
+impl WatchlistManager for WatchlistService {
+    async fn add_entry(
+        &self,
+        gos_num_mask: &str,
+        reason: &str,
+        expires_at: Option<&str>,
+        user_login: &str,
+    ) -> Result<WatchlistEntry, ServiceError> {
+        log::info!(
+            "Adding watchlist entry {} by user {}",
+            gos_num_mask,
+            user_login
+        );
+
+        Self::validate_entry(gos_num_mask, reason, expires_at)?;
+        if !Validator::is_valid_email(user_login) {
+            log::warn!("Invalid user login format: {}", user_login);
+            return Err(ServiceError::InvalidDataError("email".to_string()));
+        }
+
+        let mut entry = WatchlistEntry {
+            id: 0,
+            gos_num_mask: gos_num_mask.to_string(),
+            reason: reason.trim().to_string(),
+            expires_at: expires_at.map(str::to_string),
+            created_by: user_login.to_string(),
+        };
+        entry.id = self
+            .watchlist_repo
+            .insert_entry(&entry)
+            .await
+            .map_err(Self::map_not_found)?;
+        self.invalidate_cache();
+
+        log::info!("Added watchlist entry {}", entry.id);
+        Ok(entry)
+    }
+
+    async fn update_entry(
+        &self,
+        id: usize,
+        gos_num_mask: &str,
+        reason: &str,
+        expires_at: Option<&str>,
+    ) -> Result<WatchlistEntry, ServiceError> {
+        log::info!("Updating watchlist entry {}", id);
+
+        Self::validate_entry(gos_num_mask, reason, expires_at)?;
+
+        let Some(mut entry) = self.watchlist_repo.get_entry(id).await? else {
+            log::warn!("Watchlist entry {} not founded", id);
+            return Err(ServiceError::NotFoundError("watchlist entry".to_string()));
+        };
+        entry.gos_num_mask = gos_num_mask.to_string();
+        entry.reason = reason.trim().to_string();
+        entry.expires_at = expires_at.map(str::to_string);
+
+        self.watchlist_repo
+            .update_entry(&entry)
+            .await
+            .map_err(Self::map_not_found)?;
+        self.invalidate_cache();
+
+        log::info!("Updated watchlist entry {}", id);
+        Ok(entry)
+    }
+
+    async fn delete_entry(&self, id: usize) -> Result<(), ServiceError> {
+        log::info!("Deleting watchlist entry {}", id);
+
+        self.watchlist_repo
+            .delete_entry(id)
+            .await
+            .map_err(Self::map_not_found)?;
+        self.invalidate_cache();
+
+        log::info!("Deleted watchlist entry {}", id);
+        Ok(())
+    }
+
+    async fn get_entries(&self) -> Result<Vec<WatchlistEntry>, ServiceError> {
+        log::info!("Getting watchlist entries");
+
+        let entries = self.watchlist_repo.get_entries().await?;
+        log::info!("Getted {} watchlist entries", entries.len());
+        Ok(entries)
+    }
+
+    async fn get_alerts_by_date(&self, date: &str) -> Result<Vec<WatchlistAlert>, ServiceError> {
+        log::info!("Getting watchlist alerts for date {}", date);
+
+        if !Validator::is_valid_date(date) {
+            log::warn!("Invalid date format: {}", date);
+            return Err(ServiceError::InvalidDataError("date".to_string()));
+        }
+
+        let alerts = self.watchlist_repo.get_alerts_by_date(date).await?;
+        log::info!("Getted {} watchlist alerts", alerts.len());
+        Ok(alerts)
+    }
+
+    async fn check_snap(&self, snap: &Snap) -> Result<Vec<WatchlistAlert>, ServiceError> {
+        let Some(snap_datetime) = Self::parse_datetime(&format!("{} {}", snap.date, snap.time))
+        else {
+            log::warn!("Invalid snap datetime: {} {}", snap.date, snap.time);
+            return Err(ServiceError::InvalidDataError("datetime".to_string()));
+        };
+
+        let entries = self.cached_entries().await?;
+        let mut alerts = Vec::new();
+        for entry in entries.iter().filter(|e| {
+            Self::matches_mask(&e.gos_num_mask, &snap.gos_num) && Self::is_active(e, &snap_datetime)
+        }) {
+            let mut alert = WatchlistAlert {
+                id: 0,
+                entry_id: entry.id,
+                reason: entry.reason.clone(),
+                gos_num: snap.gos_num.clone(),
+                camera_id: snap.camera.id,
+                cords: snap.camera.location,
+                date: snap.date.clone(),
+                time: snap.time.clone(),
+            };
+
+            // Повторно доставленный снимок не создает новую фиксацию
+            let Some(id) = self.watchlist_repo.insert_alert(&alert).await? else {
+                continue;
+            };
+            alert.id = id;
+
+            log::info!(
+                "Watchlist hit: {} by entry {} at camera {}",
+                alert.gos_num,
+                alert.entry_id,
+                alert.camera_id
+            );
+            self.hub.publish(&alert);
+            alerts.push(alert);
+        }
+
+        Ok(alerts)
+    }
+
+    fn subscribe_alerts(&self) -> AlertSubscription {
+        let subscription = self.hub.subscribe();
+        log::info!(
+            "New watchlist alerts subscriber ({} subscribers)",
+            self.hub.subscribers_count()
+        );
+        subscription
+    }
+}
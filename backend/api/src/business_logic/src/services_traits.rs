@@ -1,7 +1,13 @@
+use super::alert_hub::AlertSubscription;
 use super::error::ServiceError;
+use super::snap_hub::SnapSubscription;
 use async_trait::async_trait;
 
-use models::{Camera, Car, Document, Location, PointData, TrackInfo, User};
+use models::{
+    BoundingBox, Camera, Car, CarFilter, DetailedCar, DetailedRoute, Document, IncomingSnap,
+    Location, NearbyCamera, OwnershipRecord, Page, Paged, PointData, RoadSection, RouteTrips,
+    Session, Snap, SpeedViolation, TrackInfo, User, WatchlistAlert, WatchlistEntry,
+};
 
 // # Сервис авторизации
 // ===========================================
@@ -25,37 +31,54 @@ pub trait Authorizer: Send + Sync {
     ) -> Result<(), ServiceError>;
 }
 
+#[async_trait]
+pub trait SessionManager: Send + Sync {
+    async fn open_session(&self, user: &User) -> Result<Session, ServiceError>;
+    async fn refresh_session(&self, refresh_token: &str) -> Result<Session, ServiceError>;
+    async fn close_session(&self, refresh_token: &str) -> Result<(), ServiceError>;
+    async fn get_session_user(&self, access_token: &str) -> Result<User, ServiceError>;
+    // Момент истечения токена доступа (Unix-время в секундах)
+    async fn get_session_expiry(&self, access_token: &str) -> Result<u64, ServiceError>;
+}
+
 // # Сервисы поиска
 // ===========================================
 
 #[async_trait]
 pub trait CarSearcher: Send + Sync {
-    async fn search_car(
-        &self,
-        firstname: Option<String>,
-        surname: Option<String>,
-        lastname: Option<String>,
-        passport: Option<Document>,
-        gos_num_mask: Option<String>,
-    ) -> Result<Vec<Car>, ServiceError>;
+    // Размер страницы ограничен, сортировка по track_time недоступна
+    async fn search_car(&self, filter: CarFilter, page: &Page) -> Result<Paged<Car>, ServiceError>;
     async fn search_cars_by_owner_fio(
         &self,
         firstname: Option<String>,
         surname: Option<String>,
         lastname: Option<String>,
-    ) -> Result<Vec<Car>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<Car>, ServiceError>;
     async fn search_cars_by_owner_passport(
         &self,
         passport: &Document,
-    ) -> Result<Vec<Car>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<Car>, ServiceError>;
     async fn search_cars_by_gos_num_mask(
         &self,
         gos_num_mask: &String,
-    ) -> Result<Vec<Car>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<Car>, ServiceError>;
+    async fn get_car_by_vin(&self, vin: &str) -> Result<DetailedCar, ServiceError>;
+    // Карточки возвращаются в порядке найденных автомобилей
+    async fn get_detailed_cars(&self, cars: &[Car]) -> Result<Vec<DetailedCar>, ServiceError>;
+    // Автомобиль задается ровно одним из VIN или гос.номера
+    async fn get_owner_history(
+        &self,
+        vin: Option<&str>,
+        gos_num: Option<&str>,
+    ) -> Result<Vec<OwnershipRecord>, ServiceError>;
 }
 
 #[async_trait]
 pub trait TrackInfoSearcher: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
     async fn search_track_info(
         &self,
         firstname: Option<String>,
@@ -64,25 +87,30 @@ pub trait TrackInfoSearcher: Send + Sync {
         passport: Option<Document>,
         gos_num_mask: Option<String>,
         date: Option<String>,
-    ) -> Result<Vec<TrackInfo>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError>;
     async fn search_track_info_by_owner_fio(
         &self,
         firstname: Option<String>,
         surname: Option<String>,
         lastname: Option<String>,
-    ) -> Result<Vec<TrackInfo>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError>;
     async fn search_track_info_by_owner_passport(
         &self,
         passport: &Document,
-    ) -> Result<Vec<TrackInfo>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError>;
     async fn search_track_info_by_gos_num_mask(
         &self,
         gos_num_mask: &String,
-    ) -> Result<Vec<TrackInfo>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError>;
     async fn search_track_info_by_date(
         &self,
         date: &String,
-    ) -> Result<Vec<TrackInfo>, ServiceError>;
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, ServiceError>;
 }
 
 pub trait Searcher: CarSearcher + TrackInfoSearcher {}
@@ -98,6 +126,29 @@ pub trait RouteGetter: Send + Sync {
         user_login: &String,
         date: &String,
     ) -> Result<Option<Vec<PointData>>, ServiceError>;
+    // Маршрут со временем и камерой каждой точки и статистикой по участкам
+    async fn get_car_route_detailed(
+        &self,
+        gos_num: &String,
+        user_login: &String,
+        date: &String,
+    ) -> Result<Option<DetailedRoute>, ServiceError>;
+    // Маршрут за период между датами и временем в формате "dd.mm.yyyy HH:MM"
+    async fn get_car_route_by_period(
+        &self,
+        gos_num: &str,
+        user_login: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<DetailedRoute>, ServiceError>;
+    // Поездки и стоянки за дату, без перерыва берется значение из настроек сервиса
+    async fn get_car_trips(
+        &self,
+        gos_num: &str,
+        user_login: &str,
+        date: &str,
+        gap_minutes: Option<u32>,
+    ) -> Result<Option<RouteTrips>, ServiceError>;
 }
 
 // # Сервис отправки изображений
@@ -105,14 +156,80 @@ pub trait RouteGetter: Send + Sync {
 
 #[async_trait]
 pub trait SnapSender: Send + Sync {
-    async fn insert_snap(
+    async fn insert_snap(&self, snap: &IncomingSnap) -> Result<(), ServiceError>;
+    // Результат проверки каждого снимка возвращается в порядке входных данных
+    async fn insert_snaps(
         &self,
-        camera: &Camera,
-        speed: Option<u16>,
-        time: &String,
-        date: &String,
-        gos_num: &String,
-    ) -> Result<(), ServiceError>;
+        snaps: &[IncomingSnap],
+    ) -> Result<Vec<Result<(), ServiceError>>, ServiceError>;
+}
+
+// # Сервис отслеживания в реальном времени
+// ===========================================
+
+#[async_trait]
+pub trait LiveTracker: Send + Sync {
+    // Подписка на снимки, принятые после ее создания
+    async fn subscribe(
+        &self,
+        gos_nums: &[String],
+        user_login: &str,
+    ) -> Result<SnapSubscription, ServiceError>;
+}
+
+// # Сервис списка розыска
+// ===========================================
+
+#[async_trait]
+pub trait WatchlistManager: Send + Sync {
+    async fn add_entry(
+        &self,
+        gos_num_mask: &str,
+        reason: &str,
+        expires_at: Option<&str>,
+        user_login: &str,
+    ) -> Result<WatchlistEntry, ServiceError>;
+    async fn update_entry(
+        &self,
+        id: usize,
+        gos_num_mask: &str,
+        reason: &str,
+        expires_at: Option<&str>,
+    ) -> Result<WatchlistEntry, ServiceError>;
+    async fn delete_entry(&self, id: usize) -> Result<(), ServiceError>;
+    async fn get_entries(&self) -> Result<Vec<WatchlistEntry>, ServiceError>;
+    async fn get_alerts_by_date(&self, date: &str) -> Result<Vec<WatchlistAlert>, ServiceError>;
+    // Проверка принятого снимка, возвращает новые фиксации
+    async fn check_snap(&self, snap: &Snap) -> Result<Vec<WatchlistAlert>, ServiceError>;
+    // Подписка на фиксации, записанные после ее создания
+    fn subscribe_alerts(&self) -> AlertSubscription;
+}
+
+// # Сервис контроля скорости
+// ===========================================
+
+#[async_trait]
+pub trait SpeedController: Send + Sync {
+    // Проверка принятого снимка, возвращает новое нарушение
+    async fn check_snap(&self, snap: &Snap) -> Result<Option<SpeedViolation>, ServiceError>;
+    // Контроль средней скорости на участках, начинающихся или заканчивающихся камерой снимка
+    async fn check_sections(&self, snap: &Snap) -> Result<Vec<SpeedViolation>, ServiceError>;
+    async fn add_section(
+        &self,
+        start_camera_id: usize,
+        end_camera_id: usize,
+        distance: u32,
+        speed_limit: u16,
+    ) -> Result<RoadSection, ServiceError>;
+    async fn delete_section(&self, id: usize) -> Result<(), ServiceError>;
+    async fn get_sections(&self) -> Result<Vec<RoadSection>, ServiceError>;
+    async fn get_violations(
+        &self,
+        gos_num: Option<&str>,
+        camera_id: Option<usize>,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<SpeedViolation>, ServiceError>;
 }
 
 // # Сервис получения данных с камеры
@@ -127,4 +244,30 @@ pub trait CameraDataGetter: Send + Sync {
         gos_num: &String,
         location: &Location,
     ) -> Result<f64, ServiceError>;
+    async fn find_nearest_cameras(
+        &self,
+        location: &Location,
+        count: usize,
+    ) -> Result<Vec<NearbyCamera>, ServiceError>;
+    async fn find_cameras_within(
+        &self,
+        location: &Location,
+        radius_m: f64,
+    ) -> Result<Vec<NearbyCamera>, ServiceError>;
+    // Расстояние считается от центра области
+    async fn find_cameras_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<NearbyCamera>, ServiceError>;
+}
+
+// # Сервис управления камерами
+// ===========================================
+
+#[async_trait]
+pub trait CameraManager: Send + Sync {
+    // id камеры назначается хранилищем
+    async fn add_camera(&self, camera: &Camera) -> Result<Camera, ServiceError>;
+    async fn update_camera(&self, camera: &Camera) -> Result<Camera, ServiceError>;
+    async fn decommission_camera(&self, id: usize) -> Result<(), ServiceError>;
 }
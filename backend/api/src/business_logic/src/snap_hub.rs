@@ -0,0 +1,37 @@
+use crate::hub::{Hub, HubEvent, Subscription};
+use models::Snap;
+use std::collections::HashSet;
+
+// Рассылка принятых снимков
+pub type SnapHub = Hub<Snap>;
+
+pub type LiveEvent = HubEvent<Snap>;
+
+// Подписка на снимки выбранных автомобилей
+pub struct SnapSubscription {
+    gos_nums: HashSet<String>,
+    subscription: Subscription<Snap>,
+}
+
+impl SnapSubscription {
+    pub fn from(hub: &SnapHub, gos_nums: &[String]) -> Self {
+        SnapSubscription {
+            gos_nums: gos_nums.iter().cloned().collect(),
+            subscription: hub.subscribe(),
+        }
+    }
+
+    pub fn gos_nums(&self) -> &HashSet<String> {
+        &self.gos_nums
+    }
+
+    // None - хаб закрыт
+    pub async fn next(&mut self) -> Option<LiveEvent> {
+        loop {
+            match self.subscription.next().await? {
+                LiveEvent::Item(snap) if !self.gos_nums.contains(&snap.gos_num) => continue,
+                event => return Some(event),
+            }
+        }
+    }
+}
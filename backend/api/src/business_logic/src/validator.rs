@@ -1,15 +1,16 @@
-use models::Document;
+use chrono::NaiveDate;
+use models::{Document, Location};
 use regex::Regex;
 
 pub struct Validator;
 
 impl Validator {
-    pub fn is_valid_email(email: &String) -> bool {
+    pub fn is_valid_email(email: &str) -> bool {
         let re = Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").unwrap();
         re.is_match(email)
     }
 
-    pub fn is_valid_password(pswd: &String) -> bool {
+    pub fn is_valid_password(pswd: &str) -> bool {
         let re = Regex::new(r".{8,}").unwrap();
         re.is_match(pswd)
     }
@@ -28,22 +29,38 @@ impl Validator {
         is_correct
     }
 
-    pub fn is_valid_gos_num(gos_num: &String) -> bool {
+    pub fn is_valid_gos_num(gos_num: &str) -> bool {
         let re = Regex::new(r"^[АВЕКМНОРСТУХ]\d{3}[АВЕКМНОРСТУХ]{2}\d{2,3}$").unwrap();
         re.is_match(gos_num)
     }
 
-    pub fn is_valid_gos_num_mask(gos_num_mask: &String) -> bool {
+    pub fn is_valid_gos_num_mask(gos_num_mask: &str) -> bool {
         let re = Regex::new(r"^([АВЕКМНОРСТУХ*])(\d|\*){3}([АВЕКМНОРСТУХ*]{2})(\d{2,3}|\*{1})$")
             .unwrap();
         re.is_match(gos_num_mask)
     }
 
-    pub fn is_valid_date(date: &String) -> bool {
+    // Полный VIN или его начало, VIN не содержит букв I, O и Q
+    pub fn is_valid_vin_prefix(vin_prefix: &str) -> bool {
+        let re = Regex::new(r"^[A-HJ-NPR-Z0-9]{1,17}$").unwrap();
+        re.is_match(vin_prefix)
+    }
+
+    pub fn is_valid_vin(vin: &str) -> bool {
+        vin.len() == 17 && Self::is_valid_vin_prefix(vin)
+    }
+
+    pub fn is_valid_location(location: &Location) -> bool {
+        (-90.0..=90.0).contains(&location.latitude)
+            && (-180.0..=180.0).contains(&location.longitude)
+    }
+
+    // Формат проверяется регуляркой, а существование дня в календаре - разбором ("31.02.2025")
+    pub fn is_valid_date(date: &str) -> bool {
         let re = Regex::new(r"^\d{2}\.\d{2}\.\d{4}$").unwrap();
-        re.is_match(date)
+        re.is_match(date) && NaiveDate::parse_from_str(date, "%d.%m.%Y").is_ok()
     }
-    pub fn is_valid_time(time: &String) -> bool {
+    pub fn is_valid_time(time: &str) -> bool {
         let re = Regex::new(r"^\d{1,2}\:\d{2}$").unwrap();
         if !re.is_match(time) {
             return false;
@@ -1,10 +1,11 @@
 use business_logic::services::auth_service::AuthService;
 use business_logic::services_traits::Authorizer;
-use data_access::repositories::mocked::MockUserRepo;
+use data_access::repositories::mocked::{MockUserRepo, MOCK_LEGACY_EMAIL};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_auth_success() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .auth(&"email@example.com".to_string(), &"password".to_string())
@@ -15,7 +16,7 @@ async fn test_handle_auth_success() {
 
 #[tokio::test]
 async fn test_handle_auth_invalid_email() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .auth(&"not_email".to_string(), &"password".to_string())
@@ -30,7 +31,7 @@ async fn test_handle_auth_invalid_email() {
 
 #[tokio::test]
 async fn test_handle_auth_invalid_pswd() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .auth(&"email@example.com".to_string(), &"pass".to_string())
@@ -42,3 +43,46 @@ async fn test_handle_auth_invalid_pswd() {
         "Invalid data: email or password"
     );
 }
+
+#[tokio::test]
+async fn test_handle_auth_wrong_pswd() {
+    let service = AuthService::from(Arc::new(MockUserRepo));
+
+    let res = service
+        .auth(
+            &"email@example.com".to_string(),
+            &"wrong_password".to_string(),
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Invalid data: email or password"
+    );
+}
+
+#[tokio::test]
+async fn test_handle_auth_legacy_pswd_success() {
+    let service = AuthService::from(Arc::new(MockUserRepo));
+
+    let res = service
+        .auth(&MOCK_LEGACY_EMAIL.to_string(), &"password".to_string())
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_auth_legacy_wrong_pswd() {
+    let service = AuthService::from(Arc::new(MockUserRepo));
+
+    let res = service
+        .auth(
+            &MOCK_LEGACY_EMAIL.to_string(),
+            &"wrong_password".to_string(),
+        )
+        .await;
+
+    assert!(res.is_err());
+}
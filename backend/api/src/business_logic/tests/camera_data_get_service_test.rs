@@ -1,11 +1,12 @@
 use business_logic::services::camera_data_get_service::CameraDataGetService;
 use business_logic::services_traits::CameraDataGetter;
 use data_access::repositories::mocked::MockCameraRepo;
-use models::Location;
+use models::{BoundingBox, Location};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_search_camera_by_id_success() {
-    let service = CameraDataGetService::from(Box::new(MockCameraRepo));
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
 
     let res = service.get_camera_by_id(1).await;
 
@@ -15,7 +16,7 @@ async fn test_handle_search_camera_by_id_success() {
 
 #[tokio::test]
 async fn test_handle_search_camera_by_cords_success() {
-    let service = CameraDataGetService::from(Box::new(MockCameraRepo));
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
 
     let res = service
         .get_camera_by_location(&Location {
@@ -30,7 +31,7 @@ async fn test_handle_search_camera_by_cords_success() {
 
 #[tokio::test]
 async fn test_handle_get_avg_speed_of_car_on_camera_by_gos_num_success() {
-    let service = CameraDataGetService::from(Box::new(MockCameraRepo));
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
 
     let res = service
         .get_avg_speed_of_car_on_camera_by_gos_num(
@@ -48,7 +49,7 @@ async fn test_handle_get_avg_speed_of_car_on_camera_by_gos_num_success() {
 
 #[tokio::test]
 async fn test_handle_get_avg_speed_of_car_on_camera_by_gos_num_incorrect_gos_num() {
-    let service = CameraDataGetService::from(Box::new(MockCameraRepo));
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
 
     let res = service
         .get_avg_speed_of_car_on_camera_by_gos_num(
@@ -63,3 +64,142 @@ async fn test_handle_get_avg_speed_of_car_on_camera_by_gos_num_incorrect_gos_num
     println!("{:?}", res);
     assert!(res.is_err());
 }
+
+#[tokio::test]
+async fn test_find_nearest_cameras_success() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    let res = service
+        .find_nearest_cameras(
+            &Location {
+                latitude: 55.573816,
+                longitude: 37.566005,
+            },
+            1,
+        )
+        .await;
+
+    println!("{:?}", res);
+    assert_eq!(res.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_find_nearest_cameras_incorrect_count() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    for count in [0, 51] {
+        let res = service
+            .find_nearest_cameras(
+                &Location {
+                    latitude: 55.573816,
+                    longitude: 37.566005,
+                },
+                count,
+            )
+            .await;
+
+        println!("{:?}", res);
+        assert!(res.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_find_cameras_within_success() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    let res = service
+        .find_cameras_within(
+            &Location {
+                latitude: 55.573816,
+                longitude: 37.566005,
+            },
+            1000.,
+        )
+        .await;
+
+    println!("{:?}", res);
+    let cameras = res.unwrap();
+    assert!(cameras.windows(2).all(|w| w[0].distance <= w[1].distance));
+}
+
+#[tokio::test]
+async fn test_find_cameras_within_incorrect_radius() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    for radius in [0., -10., 50_001.] {
+        let res = service
+            .find_cameras_within(
+                &Location {
+                    latitude: 55.573816,
+                    longitude: 37.566005,
+                },
+                radius,
+            )
+            .await;
+
+        println!("{:?}", res);
+        assert!(res.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_find_cameras_within_incorrect_location() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    let res = service
+        .find_cameras_within(
+            &Location {
+                latitude: 95.,
+                longitude: 37.566005,
+            },
+            1000.,
+        )
+        .await;
+
+    println!("{:?}", res);
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_find_cameras_in_bbox_success() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    let res = service
+        .find_cameras_in_bbox(&BoundingBox {
+            south_west: Location {
+                latitude: 55.5,
+                longitude: 37.5,
+            },
+            north_east: Location {
+                latitude: 55.9,
+                longitude: 37.8,
+            },
+        })
+        .await;
+
+    println!("{:?}", res);
+    let cameras = res.unwrap();
+    assert!(!cameras.is_empty());
+    assert!(cameras.windows(2).all(|w| w[0].distance <= w[1].distance));
+}
+
+#[tokio::test]
+async fn test_find_cameras_in_bbox_incorrect_corners() {
+    let service = CameraDataGetService::from(Arc::new(MockCameraRepo));
+
+    let res = service
+        .find_cameras_in_bbox(&BoundingBox {
+            south_west: Location {
+                latitude: 55.9,
+                longitude: 37.8,
+            },
+            north_east: Location {
+                latitude: 55.5,
+                longitude: 37.5,
+            },
+        })
+        .await;
+
+    println!("{:?}", res);
+    assert!(res.is_err());
+}
@@ -0,0 +1,133 @@
+use business_logic::services::camera_manage_service::CameraManageService;
+use business_logic::services_traits::CameraManager;
+use data_access::repositories::mocked::{MockCameraRepo, MOCK_CAMERAS_CNT};
+use models::{Camera, Location};
+use std::sync::Arc;
+
+fn mock_camera(id: usize) -> Camera {
+    Camera {
+        id,
+        is_radar: true,
+        location: Location {
+            latitude: 55.573816,
+            longitude: 37.566005,
+        },
+        speed_limit: Some(60),
+        install_date: Some("04.07.2021".to_string()),
+        is_active: true,
+    }
+}
+
+#[tokio::test]
+async fn test_add_camera_success() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    let res = service.add_camera(&mock_camera(0)).await;
+
+    println!("{:?}", res);
+    assert_eq!(res.unwrap().id, MOCK_CAMERAS_CNT + 1);
+}
+
+#[tokio::test]
+async fn test_add_camera_without_install_date() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    let camera = Camera {
+        install_date: None,
+        ..mock_camera(0)
+    };
+    let res = service.add_camera(&camera).await;
+
+    println!("{:?}", res);
+    assert!(res.unwrap().install_date.is_some());
+}
+
+#[tokio::test]
+async fn test_add_camera_incorrect_location() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    let camera = Camera {
+        location: Location {
+            latitude: 155.,
+            longitude: 37.566005,
+        },
+        ..mock_camera(0)
+    };
+    let res = service.add_camera(&camera).await;
+
+    println!("{:?}", res);
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: location");
+}
+
+#[tokio::test]
+async fn test_add_camera_incorrect_install_date() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    for date in ["2021-07-04", "31.02.2021", "01.01.2999"] {
+        let camera = Camera {
+            install_date: Some(date.to_string()),
+            ..mock_camera(0)
+        };
+        let res = service.add_camera(&camera).await;
+
+        println!("{:?}", res);
+        assert_eq!(res.err().unwrap().to_string(), "Invalid data: date");
+    }
+}
+
+#[tokio::test]
+async fn test_add_camera_incorrect_speed_limit() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    let camera = Camera {
+        speed_limit: Some(0),
+        ..mock_camera(0)
+    };
+    let res = service.add_camera(&camera).await;
+
+    println!("{:?}", res);
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: speed limit");
+}
+
+#[tokio::test]
+async fn test_update_camera_keeps_install_date() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    let camera = Camera {
+        install_date: None,
+        is_active: false,
+        ..mock_camera(1)
+    };
+    let res = service.update_camera(&camera).await.unwrap();
+
+    assert_eq!(res.install_date.as_deref(), Some("04.07.2021"));
+    assert!(!res.is_active);
+}
+
+#[tokio::test]
+async fn test_update_camera_not_found() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    let res = service
+        .update_camera(&mock_camera(MOCK_CAMERAS_CNT + 1))
+        .await;
+
+    println!("{:?}", res);
+    assert_eq!(res.err().unwrap().to_string(), "camera is not found");
+}
+
+#[tokio::test]
+async fn test_decommission_camera() {
+    let service = CameraManageService::from(Arc::new(MockCameraRepo));
+
+    assert!(service.decommission_camera(1).await.is_ok());
+    assert_eq!(
+        service
+            .decommission_camera(MOCK_CAMERAS_CNT + 1)
+            .await
+            .err()
+            .unwrap()
+            .to_string(),
+        "camera is not found"
+    );
+}
@@ -0,0 +1,51 @@
+use business_logic::services::search_service::SearchService;
+use business_logic::services_traits::CarSearcher;
+use data_access::repositories::mocked::{MockCarRepo, MockTrackInfoRepo, MOCK_CAR_VIN};
+use std::sync::Arc;
+
+fn service() -> SearchService {
+    SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo))
+}
+
+#[tokio::test]
+async fn test_get_car_by_vin() {
+    let car = service().get_car_by_vin(MOCK_CAR_VIN).await.unwrap();
+
+    assert_eq!(car.car.vin, MOCK_CAR_VIN);
+    assert_eq!(car.mileage, 120000);
+    assert_eq!(car.import_country, "Германия");
+}
+
+#[tokio::test]
+async fn test_get_car_by_vin_not_found() {
+    let res = service().get_car_by_vin("XTA00000000000000").await;
+
+    assert_eq!(res.err().unwrap().to_string(), "car is not found");
+}
+
+#[tokio::test]
+async fn test_get_car_by_vin_invalid() {
+    for vin in ["", "XTA21099", "XTA210990Y27654321", "XTA210990Y276543I"] {
+        let res = service().get_car_by_vin(vin).await;
+
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "Invalid data: vin",
+            "{}",
+            vin
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_get_detailed_cars() {
+    let service = service();
+    let car = service.get_car_by_vin(MOCK_CAR_VIN).await.unwrap().car;
+
+    let detailed = service.get_detailed_cars(&[car]).await.unwrap();
+    let empty = service.get_detailed_cars(&[]).await.unwrap();
+
+    assert_eq!(detailed.len(), 1);
+    assert_eq!(detailed[0].car.vin, MOCK_CAR_VIN);
+    assert!(empty.is_empty());
+}
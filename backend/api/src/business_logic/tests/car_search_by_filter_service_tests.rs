@@ -0,0 +1,93 @@
+use business_logic::services::search_service::SearchService;
+use business_logic::services_traits::CarSearcher;
+use chrono::Datelike;
+use data_access::repositories::mocked::{MockCarRepo, MockTrackInfoRepo};
+use models::{CarFilter, Page};
+use std::sync::Arc;
+
+fn service() -> SearchService {
+    SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo))
+}
+
+#[tokio::test]
+async fn test_search_car_by_filter_success() {
+    let filter = CarFilter {
+        vin: Some("JTDBR32E".to_string()),
+        mark: Some("toyota".to_string()),
+        model: Some("camry".to_string()),
+        color: Some("white".to_string()),
+        year_from: Some(2015),
+        year_to: Some(2015),
+        engine_type: Some("petrol".to_string()),
+        car_class: Some("B".to_string()),
+        ..CarFilter::default()
+    };
+
+    let res = service().search_car(filter, &Page::default()).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_search_car_by_filter_invalid_vin() {
+    let filter = CarFilter {
+        vin: Some("JTDBR32O".to_string()),
+        ..CarFilter::default()
+    };
+
+    let res = service().search_car(filter, &Page::default()).await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: vin");
+}
+
+#[tokio::test]
+async fn test_search_car_by_filter_too_long_vin() {
+    let filter = CarFilter {
+        vin: Some("JTDBR32E000000000A".to_string()),
+        ..CarFilter::default()
+    };
+
+    let res = service().search_car(filter, &Page::default()).await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: vin");
+}
+
+#[tokio::test]
+async fn test_search_car_by_filter_invalid_year_range() {
+    let filter = CarFilter {
+        year_from: Some(2020),
+        year_to: Some(2010),
+        ..CarFilter::default()
+    };
+
+    let res = service().search_car(filter, &Page::default()).await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: release year");
+}
+
+#[tokio::test]
+async fn test_search_car_by_filter_out_of_range_year() {
+    let next_year = chrono::Local::now().year() as u16 + 1;
+    for (year_from, year_to) in [
+        (Some(0), None),
+        (None, Some(1899)),
+        (None, Some(next_year + 1)),
+    ] {
+        let filter = CarFilter {
+            year_from,
+            year_to,
+            ..CarFilter::default()
+        };
+
+        let res = service().search_car(filter, &Page::default()).await;
+
+        assert_eq!(res.err().unwrap().to_string(), "Invalid data: release year");
+    }
+
+    let filter = CarFilter {
+        year_from: Some(1900),
+        year_to: Some(next_year),
+        ..CarFilter::default()
+    };
+    assert!(service().search_car(filter, &Page::default()).await.is_ok());
+}
@@ -1,16 +1,19 @@
 use business_logic::services::search_service::SearchService;
 use business_logic::services_traits::CarSearcher;
 use data_access::repositories::mocked::{MockCarRepo, MockTrackInfoRepo};
+use models::Page;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_search_car_by_fio_full_fio_success() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
         .search_cars_by_owner_fio(
             Some("firstname".to_string()),
             Some("surname".to_string()),
             Some("lastname".to_string()),
+            &Page::default(),
         )
         .await;
 
@@ -19,13 +22,14 @@ async fn test_handle_search_car_by_fio_full_fio_success() {
 
 #[tokio::test]
 async fn test_handle_search_car_by_fio_without_name_success() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
         .search_cars_by_owner_fio(
             None,
             Some("surname".to_string()),
             Some("lastname".to_string()),
+            &Page::default(),
         )
         .await;
 
@@ -34,13 +38,14 @@ async fn test_handle_search_car_by_fio_without_name_success() {
 
 #[tokio::test]
 async fn test_handle_search_car_by_fio_without_surname_success() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
         .search_cars_by_owner_fio(
             Some("firstname".to_string()),
             None,
             Some("lastname".to_string()),
+            &Page::default(),
         )
         .await;
 
@@ -49,15 +54,32 @@ async fn test_handle_search_car_by_fio_without_surname_success() {
 
 #[tokio::test]
 async fn test_handle_search_car_by_fio_without_lastname_success() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
         .search_cars_by_owner_fio(
             Some("firstname".to_string()),
             Some("surname".to_string()),
             None,
+            &Page::default(),
         )
         .await;
 
     assert!(res.is_ok());
 }
+
+#[tokio::test]
+async fn test_handle_search_car_by_fio_empty_name() {
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
+
+    let res = service
+        .search_cars_by_owner_fio(
+            Some("firstname".to_string()),
+            Some(" ".to_string()),
+            None,
+            &Page::default(),
+        )
+        .await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: name");
+}
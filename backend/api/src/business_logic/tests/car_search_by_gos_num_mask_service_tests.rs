@@ -1,13 +1,15 @@
 use business_logic::services::search_service::SearchService;
 use business_logic::services_traits::CarSearcher;
 use data_access::repositories::mocked::{MockCarRepo, MockTrackInfoRepo};
+use models::Page;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_search_car_by_gos_num_mask_success() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
-        .search_cars_by_gos_num_mask(&"А7**М*77".to_string())
+        .search_cars_by_gos_num_mask(&"А7**М*77".to_string(), &Page::default())
         .await;
 
     assert!(res.is_ok());
@@ -15,10 +17,10 @@ async fn test_handle_search_car_by_gos_num_mask_success() {
 
 #[tokio::test]
 async fn test_handle_search_car_by_gos_num_mask_invalid_gos_num_mask() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
-        .search_cars_by_gos_num_mask(&"А7**М***".to_string())
+        .search_cars_by_gos_num_mask(&"А7**М***".to_string(), &Page::default())
         .await;
 
     assert!(res.is_err());
@@ -1,17 +1,21 @@
 use business_logic::services::search_service::SearchService;
 use business_logic::services_traits::CarSearcher;
 use data_access::repositories::mocked::{MockCarRepo, MockTrackInfoRepo};
-use models::Document;
+use models::{Document, Page};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_search_car_by_passport_success() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
-        .search_cars_by_owner_passport(&Document {
-            serial: "1111".to_string(),
-            number: "111111".to_string(),
-        })
+        .search_cars_by_owner_passport(
+            &Document {
+                serial: "1111".to_string(),
+                number: "111111".to_string(),
+            },
+            &Page::default(),
+        )
         .await;
 
     assert!(res.is_ok());
@@ -19,13 +23,16 @@ async fn test_handle_search_car_by_passport_success() {
 
 #[tokio::test]
 async fn test_handle_search_car_by_passport_invalid_passport_serial() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
-        .search_cars_by_owner_passport(&Document {
-            serial: "111".to_string(),
-            number: "111111".to_string(),
-        })
+        .search_cars_by_owner_passport(
+            &Document {
+                serial: "111".to_string(),
+                number: "111111".to_string(),
+            },
+            &Page::default(),
+        )
         .await;
 
     assert!(res.is_err());
@@ -34,13 +41,16 @@ async fn test_handle_search_car_by_passport_invalid_passport_serial() {
 
 #[tokio::test]
 async fn test_handle_search_car_by_passport_invalid_passport_number() {
-    let service = SearchService::from(Box::new(MockCarRepo), Box::new(MockTrackInfoRepo));
+    let service = SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo));
 
     let res = service
-        .search_cars_by_owner_passport(&Document {
-            serial: "1111".to_string(),
-            number: "11111".to_string(),
-        })
+        .search_cars_by_owner_passport(
+            &Document {
+                serial: "1111".to_string(),
+                number: "11111".to_string(),
+            },
+            &Page::default(),
+        )
         .await;
 
     assert!(res.is_err());
@@ -2,16 +2,14 @@ use business_logic::services::auth_service::AuthService;
 use business_logic::services_traits::Authorizer;
 use data_access::repositories::postgres::{PgUserRepo, PG_URL};
 use models::Document;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_psql_auth_success() {
-    let service = AuthService::from(Box::new(PgUserRepo::from(&PG_URL).await.unwrap()));
+    let service = AuthService::from(Arc::new(PgUserRepo::from(&PG_URL).await.unwrap()));
 
     let res = service
-        .auth(
-            &"nisuev04@mail.ru".to_string(),
-            &"12345678".to_string(),
-        )
+        .auth(&"nisuev04@mail.ru".to_string(), &"12345678".to_string())
         .await;
 
     println!("{:#?}", res);
@@ -20,7 +18,7 @@ async fn test_psql_auth_success() {
 
 #[tokio::test]
 async fn test_psql_auth_invalid_email() {
-    let service = AuthService::from(Box::new(PgUserRepo::from(&PG_URL).await.unwrap()));
+    let service = AuthService::from(Arc::new(PgUserRepo::from(&PG_URL).await.unwrap()));
 
     let res = service
         .auth(&"not_email".to_string(), &"password".to_string())
@@ -35,7 +33,7 @@ async fn test_psql_auth_invalid_email() {
 
 #[tokio::test]
 async fn test_psql_change_passport() {
-    let service = AuthService::from(Box::new(PgUserRepo::from(&PG_URL).await.unwrap()));
+    let service = AuthService::from(Arc::new(PgUserRepo::from(&PG_URL).await.unwrap()));
 
     let res = service
         .passport_confirm(
@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+use business_logic::services::live_track_service::LiveTrackService;
+use business_logic::services::snap_send_service::SnapSendService;
+use business_logic::services_traits::{LiveTracker, SnapSender};
+use business_logic::snap_hub::{LiveEvent, SnapHub};
+use chrono::NaiveDateTime;
+use data_access::error::DataAccessError;
+use data_access::repositories::mocked::{
+    MockCameraRepo, MockSnapRepo, MockTrackInfoRepo, MockUserRepo,
+};
+use data_access::repositories_traits::SnapRepository;
+use models::{IncomingSnap, Snap};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Хранилище, которое как настоящие репозитории подтверждает повторы без записи
+#[derive(Default)]
+struct ReplaySnapRepo {
+    keys: Mutex<HashSet<(usize, String, String, String)>>,
+}
+
+impl ReplaySnapRepo {
+    fn is_new(&self, snap: &Snap) -> bool {
+        self.keys.lock().unwrap().insert((
+            snap.camera.id,
+            snap.gos_num.clone(),
+            snap.date.clone(),
+            snap.time.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl SnapRepository for ReplaySnapRepo {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
+        Ok(self.is_new(snap))
+    }
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(snaps
+            .iter()
+            .filter(|snap| self.is_new(snap))
+            .cloned()
+            .collect())
+    }
+    async fn get_car_snaps_by_date(
+        &self,
+        _gos_number: &str,
+        _date: &str,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(vec![])
+    }
+    async fn get_car_snaps_by_period(
+        &self,
+        _gos_number: &str,
+        _from: &NaiveDateTime,
+        _to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(vec![])
+    }
+}
+
+fn mock_snap(time: &str, gos_num: &str) -> IncomingSnap {
+    IncomingSnap {
+        camera_id: 1,
+        reported_camera: None,
+        speed: Some(70),
+        time: time.to_string(),
+        date: "01.01.2025".to_string(),
+        gos_num: gos_num.to_string(),
+        idempotency_key: None,
+    }
+}
+
+fn services(hub: &Arc<SnapHub>) -> (LiveTrackService, SnapSendService) {
+    (
+        LiveTrackService::from(
+            Arc::new(MockUserRepo),
+            Arc::new(MockTrackInfoRepo),
+            hub.clone(),
+        ),
+        SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo))
+            .with_hub(hub.clone()),
+    )
+}
+
+#[tokio::test]
+async fn test_live_track_receives_accepted_snaps() {
+    let hub = Arc::new(SnapHub::new());
+    let (tracker, sender) = services(&hub);
+
+    let mut subscription = tracker
+        .subscribe(&["А777МР77".to_string()], "exist@exist.com")
+        .await
+        .unwrap();
+
+    let snap = mock_snap("8:10", "А777МР77");
+    sender.insert_snap(&snap).await.unwrap();
+    // Снимок другого автомобиля и отклоненный снимок не доставляются
+    let other = mock_snap("8:11", "В123ОР77");
+    let invalid = mock_snap("25:00", "А777МР77");
+    let batch = sender
+        .insert_snaps(&[other, invalid, mock_snap("8:12", "А777МР77")])
+        .await
+        .unwrap();
+    assert!(batch[1].is_err());
+
+    let times: Vec<_> = [
+        subscription.next().await.unwrap(),
+        subscription.next().await.unwrap(),
+    ]
+    .into_iter()
+    .map(|event| match event {
+        LiveEvent::Item(snap) => snap.time.clone(),
+        LiveEvent::Lagged(_) => panic!("unexpected lag"),
+    })
+    .collect();
+    assert_eq!(times, vec!["8:10", "8:12"]);
+
+    let pending = tokio::time::timeout(Duration::from_millis(50), subscription.next()).await;
+    assert!(pending.is_err());
+}
+
+#[tokio::test]
+async fn test_live_track_skips_replayed_snaps() {
+    let hub = Arc::new(SnapHub::new());
+    let (tracker, _) = services(&hub);
+    let sender = SnapSendService::from(
+        Arc::new(ReplaySnapRepo::default()),
+        Arc::new(MockCameraRepo),
+    )
+    .with_hub(hub.clone());
+
+    let mut subscription = tracker
+        .subscribe(&["А777МР77".to_string()], "exist@exist.com")
+        .await
+        .unwrap();
+
+    let snap = mock_snap("8:10", "А777МР77");
+    sender.insert_snap(&snap).await.unwrap();
+    sender.insert_snap(&snap).await.unwrap();
+    let batch = sender
+        .insert_snaps(&[snap.clone(), mock_snap("8:12", "А777МР77")])
+        .await
+        .unwrap();
+    assert!(batch.iter().all(|r| r.is_ok()));
+
+    let times: Vec<_> = [
+        subscription.next().await.unwrap(),
+        subscription.next().await.unwrap(),
+    ]
+    .into_iter()
+    .map(|event| match event {
+        LiveEvent::Item(snap) => snap.time.clone(),
+        LiveEvent::Lagged(_) => panic!("unexpected lag"),
+    })
+    .collect();
+    assert_eq!(times, vec!["8:10", "8:12"]);
+
+    let pending = tokio::time::timeout(Duration::from_millis(50), subscription.next()).await;
+    assert!(pending.is_err());
+}
+
+#[tokio::test]
+async fn test_live_track_slow_subscriber_lags() {
+    let hub = Arc::new(SnapHub::with_capacity(2));
+    let (tracker, sender) = services(&hub);
+
+    let mut subscription = tracker
+        .subscribe(&["А777МР77".to_string()], "exist@exist.com")
+        .await
+        .unwrap();
+
+    let snaps: Vec<_> = ["8:10", "8:11", "8:12", "8:13"]
+        .iter()
+        .map(|time| mock_snap(time, "А777МР77"))
+        .collect();
+    sender.insert_snaps(&snaps).await.unwrap();
+
+    assert!(matches!(
+        subscription.next().await,
+        Some(LiveEvent::Lagged(2))
+    ));
+    match subscription.next().await {
+        Some(LiveEvent::Item(snap)) => assert_eq!(snap.time, "8:12"),
+        event => panic!("unexpected event {:?}", event),
+    }
+}
+
+#[tokio::test]
+async fn test_live_track_invalid_subscription() {
+    let hub = Arc::new(SnapHub::new());
+    let (tracker, _) = services(&hub);
+
+    let res = tracker.subscribe(&[], "exist@exist.com").await;
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: subscription");
+
+    let res = tracker
+        .subscribe(&["А777Р77".to_string()], "exist@exist.com")
+        .await;
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: gos number");
+    assert_eq!(hub.subscribers_count(), 0);
+}
+
+#[tokio::test]
+async fn test_live_track_unknown_user() {
+    let hub = Arc::new(SnapHub::new());
+    let (tracker, _) = services(&hub);
+
+    let res = tracker
+        .subscribe(&["А777МР77".to_string()], "example@example.com")
+        .await;
+
+    assert_eq!(res.err().unwrap().to_string(), "email is not found");
+}
+
+#[tokio::test]
+async fn test_live_track_ends_when_hub_closed() {
+    let hub = Arc::new(SnapHub::new());
+    let (tracker, _) = services(&hub);
+
+    let mut subscription = tracker
+        .subscribe(&["А777МР77".to_string()], "exist@exist.com")
+        .await
+        .unwrap();
+    hub.close();
+    let mut late = tracker
+        .subscribe(&["А777МР77".to_string()], "exist@exist.com")
+        .await
+        .unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(1), subscription.next()).await;
+    assert!(event.unwrap().is_none());
+    let event = tokio::time::timeout(Duration::from_secs(1), late.next()).await;
+    assert!(event.unwrap().is_none());
+}
@@ -0,0 +1,74 @@
+use business_logic::services::search_service::SearchService;
+use business_logic::services_traits::CarSearcher;
+use data_access::repositories::mocked::{
+    MockCarRepo, MockTrackInfoRepo, MOCK_CAR_GOS_NUM, MOCK_CAR_VIN,
+};
+use std::sync::Arc;
+
+fn service() -> SearchService {
+    SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo))
+}
+
+#[tokio::test]
+async fn test_owner_history_by_vin() {
+    let history = service()
+        .get_owner_history(Some(MOCK_CAR_VIN), None)
+        .await
+        .unwrap();
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].owner_fio.0, "Иванов");
+    assert_eq!(history[0].dereg_date.as_deref(), Some("20.06.2019"));
+    assert!(history[1].dereg_date.is_none());
+    assert_eq!(history[1].mileage, 84000);
+}
+
+#[tokio::test]
+async fn test_owner_history_by_gos_num() {
+    let history = service()
+        .get_owner_history(None, Some(MOCK_CAR_GOS_NUM))
+        .await
+        .unwrap();
+
+    assert_eq!(history.len(), 2);
+}
+
+#[tokio::test]
+async fn test_owner_history_car_not_found() {
+    let by_vin = service()
+        .get_owner_history(Some("XTA00000000000000"), None)
+        .await;
+    let by_gos_num = service().get_owner_history(None, Some("В123ОР77")).await;
+
+    assert_eq!(by_vin.err().unwrap().to_string(), "car is not found");
+    assert_eq!(by_gos_num.err().unwrap().to_string(), "car is not found");
+}
+
+#[tokio::test]
+async fn test_owner_history_requires_single_identifier() {
+    let none = service().get_owner_history(None, None).await;
+    let both = service()
+        .get_owner_history(Some(MOCK_CAR_VIN), Some(MOCK_CAR_GOS_NUM))
+        .await;
+
+    assert_eq!(
+        none.err().unwrap().to_string(),
+        "Invalid data: car identifier"
+    );
+    assert_eq!(
+        both.err().unwrap().to_string(),
+        "Invalid data: car identifier"
+    );
+}
+
+#[tokio::test]
+async fn test_owner_history_invalid_identifier() {
+    let vin = service().get_owner_history(Some(" "), None).await;
+    let gos_num = service().get_owner_history(None, Some("А777Р77")).await;
+
+    assert_eq!(vin.err().unwrap().to_string(), "Invalid data: vin");
+    assert_eq!(
+        gos_num.err().unwrap().to_string(),
+        "Invalid data: gos number"
+    );
+}
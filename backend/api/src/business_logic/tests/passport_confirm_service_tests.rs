@@ -2,10 +2,11 @@ use business_logic::services::auth_service::AuthService;
 use business_logic::services_traits::Authorizer;
 use data_access::repositories::mocked::MockUserRepo;
 use models::Document;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_passport_conf_success() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .passport_confirm(
@@ -22,7 +23,7 @@ async fn test_handle_passport_conf_success() {
 
 #[tokio::test]
 async fn test_handle_passport_conf_invalid_email() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .passport_confirm(
@@ -40,7 +41,7 @@ async fn test_handle_passport_conf_invalid_email() {
 
 #[tokio::test]
 async fn test_handle_passport_conf_invalid_passport_serial() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .passport_confirm(
@@ -58,7 +59,7 @@ async fn test_handle_passport_conf_invalid_passport_serial() {
 
 #[tokio::test]
 async fn test_handle_passport_conf_invalid_passport_number() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .passport_confirm(
@@ -76,7 +77,7 @@ async fn test_handle_passport_conf_invalid_passport_number() {
 
 #[tokio::test]
 async fn test_handle_passport_conf_email_not_founded() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .passport_confirm(
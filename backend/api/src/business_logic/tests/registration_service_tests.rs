@@ -1,10 +1,11 @@
 use business_logic::services::auth_service::AuthService;
 use business_logic::services_traits::Authorizer;
 use data_access::repositories::mocked::MockUserRepo;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_reg_success() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .register(
@@ -22,7 +23,7 @@ async fn test_handle_reg_success() {
 
 #[tokio::test]
 async fn test_handle_reg_invalid_email() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .register(
@@ -41,7 +42,7 @@ async fn test_handle_reg_invalid_email() {
 
 #[tokio::test]
 async fn test_handle_reg_invalid_pswd() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .register(
@@ -60,7 +61,7 @@ async fn test_handle_reg_invalid_pswd() {
 
 #[tokio::test]
 async fn test_handle_reg_invalid_pswds() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .register(
@@ -82,7 +83,7 @@ async fn test_handle_reg_invalid_pswds() {
 
 #[tokio::test]
 async fn test_handle_reg_email_exist() {
-    let service = AuthService::from(Box::new(MockUserRepo));
+    let service = AuthService::from(Arc::new(MockUserRepo));
 
     let res = service
         .register(
@@ -1,13 +1,14 @@
 use business_logic::services::route_service::RouteService;
 use business_logic::services_traits::RouteGetter;
 use data_access::repositories::mocked::{MockSnapRepo, MockTrackInfoRepo, MockUserRepo};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_handle_route_success() {
     let service = RouteService::from(
-        Box::new(MockUserRepo),
-        Box::new(MockSnapRepo),
-        Box::new(MockTrackInfoRepo),
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
     );
 
     let res = service
@@ -24,9 +25,9 @@ async fn test_handle_route_success() {
 #[tokio::test]
 async fn test_handle_route_invalid_date() {
     let service = RouteService::from(
-        Box::new(MockUserRepo),
-        Box::new(MockSnapRepo),
-        Box::new(MockTrackInfoRepo),
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
     );
 
     let res = service
@@ -44,9 +45,9 @@ async fn test_handle_route_invalid_date() {
 #[tokio::test]
 async fn test_handle_route_invalid_gos_num() {
     let service = RouteService::from(
-        Box::new(MockUserRepo),
-        Box::new(MockSnapRepo),
-        Box::new(MockTrackInfoRepo),
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
     );
 
     let res = service
@@ -64,9 +65,9 @@ async fn test_handle_route_invalid_gos_num() {
 #[tokio::test]
 async fn test_handle_route_invalid_email() {
     let service = RouteService::from(
-        Box::new(MockUserRepo),
-        Box::new(MockSnapRepo),
-        Box::new(MockTrackInfoRepo),
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
     );
 
     let res = service
@@ -80,3 +81,247 @@ async fn test_handle_route_invalid_email() {
     assert!(res.is_err());
     assert_eq!(res.err().unwrap().to_string(), "Invalid data: email");
 }
+
+#[tokio::test]
+async fn test_handle_route_sorted_by_time() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_detailed(
+            &"А777МР77".to_string(),
+            &"exist@exist.com".to_string(),
+            &"01.01.2025".to_string(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let times: Vec<_> = res.points.iter().map(|p| p.time.as_str()).collect();
+    assert_eq!(times, vec!["8:10", "9:15", "10:30"]);
+}
+
+#[tokio::test]
+async fn test_handle_route_detailed_stats() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_detailed(
+            &"А777МР77".to_string(),
+            &"exist@exist.com".to_string(),
+            &"01.01.2025".to_string(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(res.points.len(), 3);
+    assert_eq!(res.points[0].camera_id, 1);
+    assert!(res.points[0].is_radar);
+
+    assert_eq!(res.segments.len(), 2);
+    assert_eq!(res.segments[0].from_camera_id, 1);
+    assert_eq!(res.segments[0].to_camera_id, 2);
+    assert_eq!(res.segments[0].duration_secs, 65 * 60);
+    assert_eq!(res.segments[1].duration_secs, 75 * 60);
+    // 22° по широте и 10° по долготе: около 2477 км
+    assert!((res.segments[0].distance_km - res.segments[1].distance_km).abs() < 1e-6);
+    assert!((res.segments[0].distance_km - 2477.0).abs() < 1.0);
+
+    assert_eq!(res.stats.duration_secs, 140 * 60);
+    assert_eq!(res.stats.max_speed, Some(70));
+    assert_eq!(res.stats.cameras_count, 2);
+    let expected_avg = res.stats.distance_km / (140.0 / 60.0);
+    assert!((res.stats.avg_speed.unwrap() - expected_avg).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_handle_route_detailed_invalid_date() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_detailed(
+            &"А777МР77".to_string(),
+            &"exist@exist.com".to_string(),
+            &"0101.2025".to_string(),
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: date");
+}
+
+#[tokio::test]
+async fn test_handle_route_by_period_across_midnight() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_by_period(
+            "А777МР77",
+            "exist@exist.com",
+            "01.01.2025 9:00",
+            "02.01.2025 9:00",
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let points: Vec<_> = res
+        .points
+        .iter()
+        .map(|p| (p.date.as_str(), p.time.as_str()))
+        .collect();
+    assert_eq!(
+        points,
+        vec![
+            ("01.01.2025", "9:15"),
+            ("01.01.2025", "10:30"),
+            ("02.01.2025", "8:10")
+        ]
+    );
+    assert_eq!(res.segments[1].duration_secs, (21 * 60 + 40) * 60);
+}
+
+#[tokio::test]
+async fn test_handle_route_by_period_invalid_datetime() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_by_period(
+            "А777МР77",
+            "exist@exist.com",
+            "01.01.2025",
+            "02.01.2025 9:00",
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: datetime");
+}
+
+#[tokio::test]
+async fn test_handle_route_by_period_reversed() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_by_period(
+            "А777МР77",
+            "exist@exist.com",
+            "02.01.2025 9:00",
+            "01.01.2025 9:00",
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: period");
+}
+
+#[tokio::test]
+async fn test_handle_route_by_period_too_long() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_route_by_period(
+            "А777МР77",
+            "exist@exist.com",
+            "01.01.2025 0:00",
+            "08.01.2025 0:01",
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: period");
+}
+
+#[tokio::test]
+async fn test_handle_route_trips_split_by_gap() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_trips("А777МР77", "exist@exist.com", "01.01.2025", Some(70))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(res.trips.len(), 2);
+    assert_eq!(res.trips[0].start_camera_id, 1);
+    assert_eq!(res.trips[0].end_camera_id, 2);
+    assert_eq!(res.trips[0].start_time, "8:10");
+    assert_eq!(res.trips[0].end_time, "9:15");
+    assert_eq!(res.trips[0].points.len(), 2);
+    assert_eq!(res.trips[0].stats.duration_secs, 65 * 60);
+    assert_eq!(res.trips[1].points.len(), 1);
+
+    assert_eq!(res.stops.len(), 1);
+    assert_eq!(res.stops[0].camera_id, 2);
+    assert_eq!(res.stops[0].arrival_time, "9:15");
+    assert_eq!(res.stops[0].departure_time, "10:30");
+    assert_eq!(res.stops[0].dwell_secs, 75 * 60);
+}
+
+#[tokio::test]
+async fn test_handle_route_trips_default_gap() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    )
+    .with_trip_gap_minutes(120);
+
+    let res = service
+        .get_car_trips("А777МР77", "exist@exist.com", "01.01.2025", None)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(res.trips.len(), 1);
+    assert_eq!(res.trips[0].points.len(), 3);
+    assert!(res.stops.is_empty());
+}
+
+#[tokio::test]
+async fn test_handle_route_trips_invalid_gap() {
+    let service = RouteService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSnapRepo),
+        Arc::new(MockTrackInfoRepo),
+    );
+
+    let res = service
+        .get_car_trips("А777МР77", "exist@exist.com", "01.01.2025", Some(0))
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: trip gap");
+}
@@ -0,0 +1,120 @@
+use business_logic::services::search_service::SearchService;
+use business_logic::services_traits::{CarSearcher, TrackInfoSearcher};
+use data_access::repositories::mocked::{MockCarRepo, MockTrackInfoRepo};
+use models::{CarFilter, Page, SortKey};
+use std::sync::Arc;
+
+fn service() -> SearchService {
+    SearchService::from(Arc::new(MockCarRepo), Arc::new(MockTrackInfoRepo))
+}
+
+#[tokio::test]
+async fn test_search_car_page_success() {
+    let page = Page {
+        offset: 40,
+        limit: 100,
+        sort: SortKey::owner_surname,
+    };
+
+    let res = service()
+        .search_car(
+            CarFilter {
+                gos_num_mask: Some("*******".to_string()),
+                ..CarFilter::default()
+            },
+            &page,
+        )
+        .await;
+
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().total, 0);
+}
+
+#[tokio::test]
+async fn test_search_car_empty_page() {
+    let page = Page {
+        limit: 0,
+        ..Page::default()
+    };
+
+    let res = service().search_car(CarFilter::default(), &page).await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: page size");
+}
+
+#[tokio::test]
+async fn test_search_car_too_large_page() {
+    let page = Page {
+        limit: 101,
+        ..Page::default()
+    };
+
+    let res = service()
+        .search_cars_by_gos_num_mask(&"*******".to_string(), &page)
+        .await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: page size");
+}
+
+#[tokio::test]
+async fn test_search_out_of_range_offset() {
+    let page = Page {
+        offset: i32::MAX as usize + 1,
+        ..Page::default()
+    };
+
+    let cars = service()
+        .search_cars_by_gos_num_mask(&"*******".to_string(), &page)
+        .await;
+    let track_info = service()
+        .search_track_info_by_date(&"01.01.2025".to_string(), &page)
+        .await;
+
+    assert_eq!(cars.err().unwrap().to_string(), "Invalid data: page offset");
+    assert_eq!(
+        track_info.err().unwrap().to_string(),
+        "Invalid data: page offset"
+    );
+}
+
+#[tokio::test]
+async fn test_search_car_by_track_time() {
+    let page = Page {
+        sort: SortKey::track_time,
+        ..Page::default()
+    };
+
+    let res = service()
+        .search_cars_by_owner_fio(None, Some("surname".to_string()), None, &page)
+        .await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: sort key");
+}
+
+#[tokio::test]
+async fn test_search_track_info_by_track_time() {
+    let page = Page {
+        sort: SortKey::track_time,
+        ..Page::default()
+    };
+
+    let res = service()
+        .search_track_info_by_date(&"01.01.2025".to_string(), &page)
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_search_track_info_too_large_page() {
+    let page = Page {
+        limit: 1000,
+        ..Page::default()
+    };
+
+    let res = service()
+        .search_track_info(None, None, None, None, None, None, &page)
+        .await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: page size");
+}
@@ -0,0 +1,165 @@
+use business_logic::services::session_service::SessionService;
+use business_logic::services_traits::SessionManager;
+use data_access::repositories::mocked::{MockSessionRepo, MockUserRepo, MOCK_REFRESH_TOKEN};
+use models::{Role, User};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "mock_secret_mock_secret_mock_secret";
+
+fn exist_user() -> User {
+    User {
+        name: "mock_name".to_string(),
+        surname: "mock_surname".to_string(),
+        lastname: None,
+        email: "exist@exist.com".to_string(),
+        passport: None,
+        role: Role::user,
+        is_verified: false,
+    }
+}
+
+#[tokio::test]
+async fn test_open_session_success() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap()
+    .with_access_ttl(60);
+
+    let res = service.open_session(&exist_user()).await;
+
+    assert!(res.is_ok());
+    let session = res.unwrap();
+    assert!(!session.access_token.is_empty());
+    assert_eq!(session.refresh_token.len(), 64);
+    assert_eq!(session.expires_in, 60);
+}
+
+#[tokio::test]
+async fn test_get_session_user_success() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap();
+
+    let session = service.open_session(&exist_user()).await.unwrap();
+    let res = service.get_session_user(&session.access_token).await;
+
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().email, "exist@exist.com");
+}
+
+#[tokio::test]
+async fn test_get_session_user_invalid_token() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap();
+
+    let res = service.get_session_user("not.a.token").await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: token");
+}
+
+#[tokio::test]
+async fn test_get_session_expiry_success() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap()
+    .with_access_ttl(60);
+
+    let now = jsonwebtoken::get_current_timestamp();
+    let session = service.open_session(&exist_user()).await.unwrap();
+    let exp = service
+        .get_session_expiry(&session.access_token)
+        .await
+        .unwrap();
+
+    assert!(exp >= now + 60 && exp <= now + 61);
+}
+
+#[tokio::test]
+async fn test_get_session_user_foreign_secret() {
+    let foreign_service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        "foreign_secret_foreign_secret_foreign",
+    )
+    .unwrap();
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap();
+
+    let session = foreign_service.open_session(&exist_user()).await.unwrap();
+    let res = service.get_session_user(&session.access_token).await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: token");
+}
+
+#[tokio::test]
+async fn test_refresh_session_success() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap();
+
+    let res = service.refresh_session(MOCK_REFRESH_TOKEN).await;
+
+    assert!(res.is_ok());
+    assert_ne!(res.unwrap().refresh_token, MOCK_REFRESH_TOKEN);
+}
+
+#[tokio::test]
+async fn test_refresh_session_unknown_token() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap();
+
+    let res = service.refresh_session("unknown_refresh_token").await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: token");
+}
+
+#[tokio::test]
+async fn test_close_session_success() {
+    let service = SessionService::from(
+        Arc::new(MockUserRepo),
+        Arc::new(MockSessionRepo),
+        JWT_SECRET,
+    )
+    .unwrap();
+
+    let res = service.close_session(MOCK_REFRESH_TOKEN).await;
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_session_service_rejects_weak_secret() {
+    for secret in ["", "short_secret", "change-me-in-production"] {
+        let res = SessionService::from(Arc::new(MockUserRepo), Arc::new(MockSessionRepo), secret);
+
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().to_string(), "Invalid data: jwt secret");
+    }
+}
@@ -1,52 +1,52 @@
 use business_logic::services::snap_send_service::SnapSendService;
 use business_logic::services_traits::SnapSender;
-use data_access::repositories::mocked::MockSnapRepo;
-use models::{Camera, Location};
+use data_access::repositories::mocked::{
+    MockCameraRepo, MockSnapRepo, MOCK_CAMERAS_CNT, MOCK_DECOMMISSIONED_CAMERA_ID,
+    MOCK_NO_RADAR_CAMERA_ID, MOCK_UNAVAILABLE_CAMERA_ID,
+};
+use models::{Camera, IncomingSnap, Location};
+use std::sync::Arc;
+
+fn mock_snap(time: &str, gos_num: &str) -> IncomingSnap {
+    IncomingSnap {
+        camera_id: 1,
+        reported_camera: None,
+        speed: Some(70),
+        time: time.to_string(),
+        date: "01.01.2025".to_string(),
+        gos_num: gos_num.to_string(),
+        idempotency_key: None,
+    }
+}
+
+fn mock_reported_camera(id: usize) -> Camera {
+    Camera {
+        id,
+        is_radar: true,
+        location: Location {
+            latitude: 55.75222,
+            longitude: 37.61556,
+        },
+        speed_limit: None,
+        install_date: None,
+        is_active: true,
+    }
+}
 
 #[tokio::test]
 async fn test_handle_snap_send_success() {
-    let service = SnapSendService::from(Box::new(MockSnapRepo));
-
-    let res = service
-        .insert_snap(
-            &Camera {
-                id: 1,
-                is_radar: true,
-                location: Location {
-                    longitude: 53.9333,
-                    latitude: 53.9333,
-                },
-            },
-            Some(70),
-            &"8:10".to_string(),
-            &"01.01.2025".to_string(),
-            &"А777МР77".to_string(),
-        )
-        .await;
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let res = service.insert_snap(&mock_snap("8:10", "А777МР77")).await;
 
     assert!(res.is_ok());
 }
 
 #[tokio::test]
 async fn test_handle_snap_send_invalid_gos_num() {
-    let service = SnapSendService::from(Box::new(MockSnapRepo));
-
-    let res = service
-        .insert_snap(
-            &Camera {
-                id: 1,
-                is_radar: true,
-                location: Location {
-                    longitude: 53.9333,
-                    latitude: 53.9333,
-                },
-            },
-            Some(70),
-            &"8:10".to_string(),
-            &"01.01.2025".to_string(),
-            &"А777Р77".to_string(),
-        )
-        .await;
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let res = service.insert_snap(&mock_snap("8:10", "А777Р77")).await;
 
     assert!(res.is_err());
     assert_eq!(res.err().unwrap().to_string(), "Invalid data: gos number");
@@ -54,49 +54,47 @@ async fn test_handle_snap_send_invalid_gos_num() {
 
 #[tokio::test]
 async fn test_handle_snap_send_invalid_date() {
-    let service = SnapSendService::from(Box::new(MockSnapRepo));
-
-    let res = service
-        .insert_snap(
-            &Camera {
-                id: 1,
-                is_radar: true,
-                location: Location {
-                    longitude: 53.9333,
-                    latitude: 53.9333,
-                },
-            },
-            Some(70),
-            &"8:10".to_string(),
-            &"0101.2025".to_string(),
-            &"А777МР77".to_string(),
-        )
-        .await;
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.date = "0101.2025".to_string();
+    let res = service.insert_snap(&snap).await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: date");
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_nonexistent_date() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.date = "31.02.2025".to_string();
+    let res = service.insert_snap(&snap).await;
 
     assert!(res.is_err());
     assert_eq!(res.err().unwrap().to_string(), "Invalid data: date");
 }
 
+#[tokio::test]
+async fn test_handle_snap_send_future_datetime() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.date = (chrono::Utc::now() + chrono::Duration::days(2))
+        .format("%d.%m.%Y")
+        .to_string();
+    let res = service.insert_snap(&snap).await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: datetime");
+}
+
 #[tokio::test]
 async fn test_handle_snap_send_invalid_time_format() {
-    let service = SnapSendService::from(Box::new(MockSnapRepo));
-
-    let res = service
-        .insert_snap(
-            &Camera {
-                id: 1,
-                is_radar: true,
-                location: Location {
-                    longitude: 53.9333,
-                    latitude: 53.9333,
-                },
-            },
-            Some(70),
-            &"8-10".to_string(),
-            &"01.01.2025".to_string(),
-            &"А777МР77".to_string(),
-        )
-        .await;
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let res = service.insert_snap(&mock_snap("8-10", "А777МР77")).await;
 
     assert!(res.is_err());
     assert_eq!(res.err().unwrap().to_string(), "Invalid data: time");
@@ -104,25 +102,226 @@ async fn test_handle_snap_send_invalid_time_format() {
 
 #[tokio::test]
 async fn test_handle_snap_send_time_value() {
-    let service = SnapSendService::from(Box::new(MockSnapRepo));
-
-    let res = service
-        .insert_snap(
-            &Camera {
-                id: 1,
-                is_radar: true,
-                location: Location {
-                    longitude: 53.9333,
-                    latitude: 53.9333,
-                },
-            },
-            Some(70),
-            &"25:10".to_string(),
-            &"01.01.2025".to_string(),
-            &"А777МР77".to_string(),
-        )
-        .await;
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let res = service.insert_snap(&mock_snap("25:10", "А777МР77")).await;
 
     assert!(res.is_err());
     assert_eq!(res.err().unwrap().to_string(), "Invalid data: time");
 }
+
+#[tokio::test]
+async fn test_handle_snap_batch_send_success() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let snaps = vec![mock_snap("8:10", "А777МР77"), mock_snap("8:15", "А777МР77")];
+    let res = service.insert_snaps(&snaps).await.unwrap();
+
+    assert_eq!(res.len(), 2);
+    assert!(res.iter().all(|r| r.is_ok()));
+}
+
+#[tokio::test]
+async fn test_handle_snap_batch_send_partial() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let snaps = vec![
+        mock_snap("8:10", "А777МР77"),
+        mock_snap("8:10", "А777Р77"),
+        mock_snap("25:10", "А777МР77"),
+        mock_snap("8:20", "А777МР77"),
+    ];
+    let res = service.insert_snaps(&snaps).await.unwrap();
+
+    assert_eq!(res.len(), 4);
+    assert!(res[0].is_ok());
+    assert_eq!(
+        res[1].as_ref().err().unwrap().to_string(),
+        "Invalid data: gos number"
+    );
+    assert_eq!(
+        res[2].as_ref().err().unwrap().to_string(),
+        "Invalid data: time"
+    );
+    assert!(res[3].is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_snap_batch_send_empty() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let res = service.insert_snaps(&[]).await.unwrap();
+
+    assert!(res.is_empty());
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_with_idempotency_key() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.idempotency_key = Some("cam1-000042".to_string());
+    let res = service.insert_snap(&snap).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_invalid_idempotency_key() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.idempotency_key = Some(" ".to_string());
+    let res = service.insert_snap(&snap).await;
+
+    assert!(res.is_err());
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Invalid data: idempotency key"
+    );
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_unknown_camera() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.camera_id = MOCK_CAMERAS_CNT + 1;
+    let res = service.insert_snap(&snap).await;
+
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: camera");
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_decommissioned_camera() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.camera_id = MOCK_DECOMMISSIONED_CAMERA_ID;
+    let res = service.insert_snap(&snap).await;
+
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Invalid data: decommissioned camera"
+    );
+}
+
+#[tokio::test]
+async fn test_handle_snap_batch_send_rejects_unregistered_cameras() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut unknown = mock_snap("8:15", "А777МР77");
+    unknown.camera_id = 0;
+    let mut decommissioned = mock_snap("8:20", "А777МР77");
+    decommissioned.camera_id = MOCK_DECOMMISSIONED_CAMERA_ID;
+    let snaps = vec![
+        mock_snap("8:10", "А777МР77"),
+        unknown,
+        decommissioned.clone(),
+        decommissioned,
+    ];
+    let res = service.insert_snaps(&snaps).await.unwrap();
+
+    assert!(res[0].is_ok());
+    assert_eq!(
+        res[1].as_ref().err().unwrap().to_string(),
+        "Invalid data: camera"
+    );
+    assert!(res[2..]
+        .iter()
+        .all(|r| r.as_ref().err().unwrap().to_string() == "Invalid data: decommissioned camera"));
+}
+
+#[tokio::test]
+async fn test_handle_snap_batch_send_camera_lookup_error() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut unavailable = mock_snap("8:15", "А777МР77");
+    unavailable.camera_id = MOCK_UNAVAILABLE_CAMERA_ID;
+    let snaps = vec![
+        mock_snap("8:10", "А777МР77"),
+        unavailable.clone(),
+        unavailable,
+        mock_snap("8:20", "А777МР77"),
+    ];
+    let res = service.insert_snaps(&snaps).await.unwrap();
+
+    assert!(res[0].is_ok());
+    assert!(res[1..3]
+        .iter()
+        .all(|r| r.as_ref().err().unwrap().to_string() == "Internal error: camera lookup"));
+    assert!(res[3].is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_matching_reported_camera() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.reported_camera = Some(mock_reported_camera(1));
+    let res = service.insert_snap(&snap).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_camera_mismatch() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut moved = mock_reported_camera(1);
+    moved.location.latitude = 53.9333;
+    let mut no_radar = mock_reported_camera(1);
+    no_radar.is_radar = false;
+
+    for reported in [moved, no_radar, mock_reported_camera(2)] {
+        let mut snap = mock_snap("8:10", "А777МР77");
+        snap.reported_camera = Some(reported);
+        let res = service.insert_snap(&snap).await;
+
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "Invalid data: camera mismatch"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_handle_snap_send_speed_without_radar() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut snap = mock_snap("8:10", "А777МР77");
+    snap.camera_id = MOCK_NO_RADAR_CAMERA_ID;
+    let res = service.insert_snap(&snap).await;
+
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Invalid data: speed without radar"
+    );
+
+    snap.speed = None;
+    let res = service.insert_snap(&snap).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_handle_snap_batch_send_rejects_camera_mismatch() {
+    let service = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo));
+
+    let mut mismatch = mock_snap("8:15", "А777МР77");
+    mismatch.reported_camera = Some(mock_reported_camera(2));
+    let mut no_radar = mock_snap("8:20", "А777МР77");
+    no_radar.camera_id = MOCK_NO_RADAR_CAMERA_ID;
+    let snaps = vec![mock_snap("8:10", "А777МР77"), mismatch, no_radar];
+    let res = service.insert_snaps(&snaps).await.unwrap();
+
+    assert!(res[0].is_ok());
+    assert_eq!(
+        res[1].as_ref().err().unwrap().to_string(),
+        "Invalid data: camera mismatch"
+    );
+    assert_eq!(
+        res[2].as_ref().err().unwrap().to_string(),
+        "Invalid data: speed without radar"
+    );
+}
@@ -0,0 +1,558 @@
+use async_trait::async_trait;
+use business_logic::services::snap_send_service::SnapSendService;
+use business_logic::services::speed_control_service::SpeedControlService;
+use business_logic::services_traits::{SnapSender, SpeedController};
+use chrono::NaiveDateTime;
+use data_access::error::DataAccessError;
+use data_access::repositories::mocked::{
+    MockCameraRepo, MockRoadSectionRepo, MockSnapRepo, MockSpeedViolationRepo,
+};
+use data_access::repositories_traits::{SnapRepository, SpeedViolationRepository};
+use models::{Camera, IncomingSnap, Location, Snap, SpeedViolation, ViolationSeverity};
+use std::sync::{Arc, Mutex};
+
+// Хранилище нарушений в памяти для проверки записи
+#[derive(Default)]
+struct MemorySpeedViolationRepo {
+    violations: Mutex<Vec<SpeedViolation>>,
+}
+
+#[async_trait]
+impl SpeedViolationRepository for MemorySpeedViolationRepo {
+    async fn insert_violation(
+        &self,
+        violation: &SpeedViolation,
+    ) -> Result<Option<usize>, DataAccessError> {
+        let mut violations = self.violations.lock().unwrap();
+        let is_replay = violations.iter().any(|v| {
+            v.camera_id == violation.camera_id
+                && v.gos_num == violation.gos_num
+                && v.date == violation.date
+                && v.time == violation.time
+        });
+        if is_replay {
+            return Ok(None);
+        }
+        let id = violations.len() + 1;
+        violations.push(SpeedViolation {
+            id,
+            ..violation.clone()
+        });
+        Ok(Some(id))
+    }
+    async fn get_violations_by_filters(
+        &self,
+        gos_num: Option<&str>,
+        camera_id: Option<usize>,
+        _from: &NaiveDateTime,
+        _to: &NaiveDateTime,
+    ) -> Result<Vec<SpeedViolation>, DataAccessError> {
+        Ok(self
+            .violations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|v| gos_num.is_none_or(|g| v.gos_num == g))
+            .filter(|v| camera_id.is_none_or(|id| v.camera_id == id))
+            .cloned()
+            .collect())
+    }
+}
+
+// Хранилище снимков в памяти для проверки проезда участков
+#[derive(Default)]
+struct MemorySnapRepo {
+    snaps: Mutex<Vec<Snap>>,
+}
+
+fn snap_datetime(snap: &Snap) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(&format!("{} {}", snap.date, snap.time), "%d.%m.%Y %H:%M")
+        .unwrap()
+}
+
+#[async_trait]
+impl SnapRepository for MemorySnapRepo {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
+        self.snaps.lock().unwrap().push(snap.clone());
+        Ok(true)
+    }
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        self.snaps.lock().unwrap().extend_from_slice(snaps);
+        Ok(snaps.to_vec())
+    }
+    async fn get_car_snaps_by_date(
+        &self,
+        gos_number: &str,
+        date: &str,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(self
+            .snaps
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.gos_num == gos_number && s.date == date)
+            .cloned()
+            .collect())
+    }
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        let mut snaps: Vec<Snap> = self
+            .snaps
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.gos_num == gos_number)
+            .filter(|s| (*from..=*to).contains(&snap_datetime(s)))
+            .cloned()
+            .collect();
+        snaps.sort_by_key(snap_datetime);
+        Ok(snaps)
+    }
+}
+
+fn camera_snap(camera_id: usize, time: &str, speed: Option<u16>) -> Snap {
+    Snap {
+        camera: Camera {
+            id: camera_id,
+            is_radar: true,
+            location: Location {
+                longitude: 37.61556,
+                latitude: 55.75222,
+            },
+            speed_limit: Some(60),
+            install_date: None,
+            is_active: true,
+        },
+        speed,
+        time: time.to_string(),
+        date: "01.01.2025".to_string(),
+        gos_num: "А777МР77".to_string(),
+        idempotency_key: None,
+    }
+}
+
+fn mock_snap(speed: Option<u16>) -> Snap {
+    camera_snap(3, "8:10", speed)
+}
+
+// Ограничение скорости камер снимков - 60 км/ч,
+// в MockRoadSectionRepo - участок 10 км от камеры 1 до камеры 2 с ограничением 60 км/ч
+fn service(repo: Arc<MemorySpeedViolationRepo>) -> SpeedControlService {
+    SpeedControlService::from(
+        Arc::new(MockCameraRepo),
+        repo,
+        Arc::new(MockRoadSectionRepo),
+        Arc::new(MockSnapRepo),
+    )
+}
+
+fn section_service(
+    repo: Arc<MemorySpeedViolationRepo>,
+    snap_repo: Arc<MemorySnapRepo>,
+) -> SpeedControlService {
+    SpeedControlService::from(
+        Arc::new(MockCameraRepo),
+        repo,
+        Arc::new(MockRoadSectionRepo),
+        snap_repo,
+    )
+}
+
+async fn snap_repo_with(snaps: &[Snap]) -> Arc<MemorySnapRepo> {
+    let repo = Arc::new(MemorySnapRepo::default());
+    repo.insert_snap_batch(snaps).await.unwrap();
+    repo
+}
+
+#[tokio::test]
+async fn test_speed_within_tolerance_not_violation() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let service = service(repo.clone());
+
+    let violation = service.check_snap(&mock_snap(Some(80))).await.unwrap();
+
+    assert!(violation.is_none());
+    assert!(repo.violations.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_speed_over_tolerance_recorded() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let service = service(repo.clone());
+
+    let violation = service
+        .check_snap(&mock_snap(Some(81)))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(violation.id, 1);
+    assert_eq!(violation.camera_id, 3);
+    assert_eq!(violation.speed, 81);
+    assert_eq!(violation.speed_limit, 60);
+    assert_eq!(violation.severity, ViolationSeverity::low);
+    assert_eq!(repo.violations.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_speed_limit_taken_from_snap_camera() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let mut snap = mock_snap(Some(110));
+    snap.camera.speed_limit = Some(90);
+
+    let violation = service(repo.clone()).check_snap(&snap).await.unwrap();
+    assert!(violation.is_none());
+
+    snap.camera.speed_limit = None;
+    let violation = service(repo.clone()).check_snap(&snap).await.unwrap();
+    assert!(violation.is_none());
+    assert!(repo.violations.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_speed_violation_severity_buckets() {
+    for (speed, severity) in [
+        (100, ViolationSeverity::low),
+        (101, ViolationSeverity::medium),
+        (120, ViolationSeverity::medium),
+        (121, ViolationSeverity::high),
+        (140, ViolationSeverity::high),
+        (141, ViolationSeverity::critical),
+    ] {
+        let violation = service(Arc::new(MemorySpeedViolationRepo::default()))
+            .check_snap(&mock_snap(Some(speed)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(violation.severity, severity, "speed {}", speed);
+    }
+}
+
+// Границы категорий - полное превышение ограничения, допуск их не сдвигает
+#[tokio::test]
+async fn test_speed_violation_severity_ignores_tolerance() {
+    for (tolerance, speed, severity) in [
+        (0, 61, ViolationSeverity::low),
+        (0, 100, ViolationSeverity::low),
+        (50, 111, ViolationSeverity::medium),
+        (50, 121, ViolationSeverity::high),
+        (50, 141, ViolationSeverity::critical),
+    ] {
+        let violation = service(Arc::new(MemorySpeedViolationRepo::default()))
+            .with_tolerance(tolerance)
+            .check_snap(&mock_snap(Some(speed)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            violation.severity, severity,
+            "speed {} tolerance {}",
+            speed, tolerance
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_speed_violation_custom_tolerance() {
+    let service = service(Arc::new(MemorySpeedViolationRepo::default())).with_tolerance(0);
+
+    let violation = service.check_snap(&mock_snap(Some(61))).await.unwrap();
+
+    assert!(violation.is_some());
+}
+
+#[tokio::test]
+async fn test_snap_without_speed_not_checked() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+
+    let violation = service(repo.clone())
+        .check_snap(&mock_snap(None))
+        .await
+        .unwrap();
+
+    assert!(violation.is_none());
+    assert!(repo.violations.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_replayed_snap_not_recorded_twice() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let service = service(repo.clone());
+
+    let snap = mock_snap(Some(130));
+    assert!(service.check_snap(&snap).await.unwrap().is_some());
+    assert!(service.check_snap(&snap).await.unwrap().is_none());
+    assert_eq!(repo.violations.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_speed_violation_recorded_on_snap_insert() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let sender = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo))
+        .with_speed_controller(Arc::new(service(repo.clone())));
+
+    let snap = mock_snap(Some(130));
+    sender
+        .insert_snap(&IncomingSnap {
+            camera_id: snap.camera.id,
+            reported_camera: None,
+            speed: snap.speed,
+            time: snap.time,
+            date: snap.date,
+            gos_num: snap.gos_num,
+            idempotency_key: None,
+        })
+        .await
+        .unwrap();
+
+    let violations = repo.violations.lock().unwrap();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].gos_num, "А777МР77");
+    assert_eq!(violations[0].severity, ViolationSeverity::high);
+}
+
+#[tokio::test]
+async fn test_get_violations() {
+    let service = SpeedControlService::from(
+        Arc::new(MockCameraRepo),
+        Arc::new(MockSpeedViolationRepo),
+        Arc::new(MockRoadSectionRepo),
+        Arc::new(MockSnapRepo),
+    );
+
+    let violations = service
+        .get_violations(
+            Some("А123АА777"),
+            Some(3),
+            "01.01.2025 00:00",
+            "07.01.2025 23:59",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].gos_num, "А123АА777");
+    assert_eq!(violations[0].camera_id, 3);
+}
+
+#[tokio::test]
+async fn test_get_violations_invalid_gos_num() {
+    let res = service(Arc::new(MemorySpeedViolationRepo::default()))
+        .get_violations(
+            Some("А12АА777"),
+            None,
+            "01.01.2025 00:00",
+            "07.01.2025 23:59",
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: gos number");
+}
+
+#[tokio::test]
+async fn test_get_violations_invalid_datetime() {
+    let res = service(Arc::new(MemorySpeedViolationRepo::default()))
+        .get_violations(None, None, "01.01.2025", "07.01.2025 23:59")
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: datetime");
+}
+
+#[tokio::test]
+async fn test_get_violations_invalid_period() {
+    let service = service(Arc::new(MemorySpeedViolationRepo::default()));
+
+    let reversed = service
+        .get_violations(None, None, "07.01.2025 00:00", "01.01.2025 00:00")
+        .await;
+    let too_long = service
+        .get_violations(None, None, "01.01.2025 00:00", "01.03.2025 00:00")
+        .await;
+
+    assert_eq!(reversed.err().unwrap().to_string(), "Invalid data: period");
+    assert_eq!(too_long.err().unwrap().to_string(), "Invalid data: period");
+}
+
+#[tokio::test]
+async fn test_section_speed_over_limit_recorded() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let exit = camera_snap(2, "8:14", None);
+    let snap_repo = snap_repo_with(&[camera_snap(1, "8:10", None), exit.clone()]).await;
+
+    let violations = section_service(repo.clone(), snap_repo)
+        .check_sections(&exit)
+        .await
+        .unwrap();
+
+    // 10 км не более чем за 5 минут
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].section_id, Some(1));
+    assert_eq!(violations[0].camera_id, 2);
+    assert_eq!(violations[0].time, "8:14");
+    assert_eq!(violations[0].speed, 120);
+    assert_eq!(violations[0].speed_limit, 60);
+    assert_eq!(violations[0].severity, ViolationSeverity::medium);
+    assert_eq!(repo.violations.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_section_speed_within_limit_not_violation() {
+    let exit = camera_snap(2, "8:20", None);
+    let snap_repo = snap_repo_with(&[camera_snap(1, "8:10", None), exit.clone()]).await;
+
+    let violations = section_service(Arc::new(MemorySpeedViolationRepo::default()), snap_repo)
+        .check_sections(&exit)
+        .await
+        .unwrap();
+
+    assert!(violations.is_empty());
+}
+
+#[tokio::test]
+async fn test_section_without_entry_not_checked() {
+    let exit = camera_snap(2, "8:14", None);
+    let snap_repo = snap_repo_with(&[camera_snap(3, "8:10", None), exit.clone()]).await;
+
+    let violations = section_service(Arc::new(MemorySpeedViolationRepo::default()), snap_repo)
+        .check_sections(&exit)
+        .await
+        .unwrap();
+
+    assert!(violations.is_empty());
+}
+
+#[tokio::test]
+async fn test_section_entry_paired_with_first_exit_only() {
+    let first_exit = camera_snap(2, "8:14", None);
+    let second_exit = camera_snap(2, "8:16", None);
+    let snap_repo = snap_repo_with(&[
+        camera_snap(1, "8:10", None),
+        first_exit.clone(),
+        second_exit.clone(),
+    ])
+    .await;
+    let service = section_service(Arc::new(MemorySpeedViolationRepo::default()), snap_repo);
+
+    assert_eq!(service.check_sections(&first_exit).await.unwrap().len(), 1);
+    assert!(service
+        .check_sections(&second_exit)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+// Снимок въезда, выгруженный позже снимка выезда, тоже дает нарушение на выезде
+#[tokio::test]
+async fn test_section_checked_by_late_entry() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let entry = camera_snap(1, "8:10", None);
+    let exit = camera_snap(2, "8:14", None);
+    let snap_repo = snap_repo_with(std::slice::from_ref(&exit)).await;
+    let service = section_service(repo.clone(), snap_repo.clone());
+
+    assert!(service.check_sections(&exit).await.unwrap().is_empty());
+    snap_repo.insert_snap(&entry).await.unwrap();
+    let violations = service.check_sections(&entry).await.unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].camera_id, 2);
+    assert_eq!(violations[0].time, "8:14");
+    assert_eq!(violations[0].speed, 120);
+    assert_eq!(repo.violations.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_section_late_entry_paired_with_first_exit_only() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let entry = camera_snap(1, "8:10", None);
+    let snap_repo = snap_repo_with(&[
+        camera_snap(1, "8:12", None),
+        camera_snap(2, "8:14", None),
+        entry.clone(),
+    ])
+    .await;
+
+    let violations = section_service(repo.clone(), snap_repo)
+        .check_sections(&entry)
+        .await
+        .unwrap();
+
+    assert!(violations.is_empty());
+}
+
+#[tokio::test]
+async fn test_section_checked_for_non_radar_camera() {
+    let mut exit = camera_snap(2, "8:14", None);
+    exit.camera.is_radar = false;
+    let mut entry = camera_snap(1, "8:10", None);
+    entry.camera.is_radar = false;
+    let snap_repo = snap_repo_with(&[entry, exit.clone()]).await;
+
+    let violations = section_service(Arc::new(MemorySpeedViolationRepo::default()), snap_repo)
+        .check_sections(&exit)
+        .await
+        .unwrap();
+
+    assert_eq!(violations.len(), 1);
+}
+
+#[tokio::test]
+async fn test_section_violation_recorded_on_snap_insert() {
+    let repo = Arc::new(MemorySpeedViolationRepo::default());
+    let snap_repo = snap_repo_with(&[camera_snap(1, "8:10", None)]).await;
+    let sender = SnapSendService::from(snap_repo.clone(), Arc::new(MockCameraRepo))
+        .with_speed_controller(Arc::new(section_service(repo.clone(), snap_repo)));
+
+    let exit = camera_snap(2, "8:14", None);
+    sender
+        .insert_snap(&IncomingSnap {
+            camera_id: exit.camera.id,
+            reported_camera: None,
+            speed: exit.speed,
+            time: exit.time,
+            date: exit.date,
+            gos_num: exit.gos_num,
+            idempotency_key: None,
+        })
+        .await
+        .unwrap();
+
+    let violations = repo.violations.lock().unwrap();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].section_id, Some(1));
+}
+
+#[tokio::test]
+async fn test_add_section_invalid() {
+    let service = service(Arc::new(MemorySpeedViolationRepo::default()));
+
+    let same_cameras = service.add_section(1, 1, 10000, 60).await;
+    let zero_distance = service.add_section(1, 2, 0, 60).await;
+
+    assert_eq!(
+        same_cameras.err().unwrap().to_string(),
+        "Invalid data: road section"
+    );
+    assert_eq!(
+        zero_distance.err().unwrap().to_string(),
+        "Invalid data: road section"
+    );
+}
+
+#[tokio::test]
+async fn test_add_section() {
+    let section = service(Arc::new(MemorySpeedViolationRepo::default()))
+        .add_section(1, 2, 10000, 60)
+        .await
+        .unwrap();
+
+    assert_eq!(section.id, 1);
+    assert_eq!(section.start_camera_id, 1);
+    assert_eq!(section.end_camera_id, 2);
+}
@@ -0,0 +1,342 @@
+use async_trait::async_trait;
+use business_logic::alert_hub::{AlertEvent, AlertHub};
+use business_logic::services::snap_send_service::SnapSendService;
+use business_logic::services::watchlist_service::WatchlistService;
+use business_logic::services_traits::{SnapSender, WatchlistManager};
+use data_access::error::DataAccessError;
+use data_access::repositories::mocked::{MockCameraRepo, MockSnapRepo, MockWatchlistRepo};
+use data_access::repositories_traits::WatchlistRepository;
+use models::{Camera, IncomingSnap, Location, Snap, WatchlistAlert, WatchlistEntry};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Хранилище списка в памяти для проверки записи фиксаций
+#[derive(Default)]
+struct MemoryWatchlistRepo {
+    entries: Mutex<Vec<WatchlistEntry>>,
+    alerts: Mutex<Vec<WatchlistAlert>>,
+}
+
+#[async_trait]
+impl WatchlistRepository for MemoryWatchlistRepo {
+    async fn insert_entry(&self, entry: &WatchlistEntry) -> Result<usize, DataAccessError> {
+        let mut entries = self.entries.lock().unwrap();
+        let id = entries.len() + 1;
+        entries.push(WatchlistEntry {
+            id,
+            ..entry.clone()
+        });
+        Ok(id)
+    }
+    async fn update_entry(&self, entry: &WatchlistEntry) -> Result<(), DataAccessError> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.iter_mut().find(|e| e.id == entry.id) {
+            Some(stored) => {
+                *stored = entry.clone();
+                Ok(())
+            }
+            None => Err(DataAccessError::NotFoundError(
+                "watchlist entry".to_string(),
+            )),
+        }
+    }
+    async fn delete_entry(&self, id: usize) -> Result<(), DataAccessError> {
+        let mut entries = self.entries.lock().unwrap();
+        let len = entries.len();
+        entries.retain(|e| e.id != id);
+        if entries.len() == len {
+            return Err(DataAccessError::NotFoundError(
+                "watchlist entry".to_string(),
+            ));
+        }
+        Ok(())
+    }
+    async fn get_entry(&self, id: usize) -> Result<Option<WatchlistEntry>, DataAccessError> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|e| e.id == id)
+            .cloned())
+    }
+    async fn get_entries(&self) -> Result<Vec<WatchlistEntry>, DataAccessError> {
+        Ok(self.entries.lock().unwrap().clone())
+    }
+    async fn insert_alert(&self, alert: &WatchlistAlert) -> Result<Option<usize>, DataAccessError> {
+        let mut alerts = self.alerts.lock().unwrap();
+        let is_replay = alerts.iter().any(|a| {
+            a.entry_id == alert.entry_id
+                && a.camera_id == alert.camera_id
+                && a.gos_num == alert.gos_num
+                && a.date == alert.date
+                && a.time == alert.time
+        });
+        if is_replay {
+            return Ok(None);
+        }
+        let id = alerts.len() + 1;
+        alerts.push(WatchlistAlert {
+            id,
+            ..alert.clone()
+        });
+        Ok(Some(id))
+    }
+    async fn get_alerts_by_date(&self, date: &str) -> Result<Vec<WatchlistAlert>, DataAccessError> {
+        Ok(self
+            .alerts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.date == date)
+            .cloned()
+            .collect())
+    }
+}
+
+fn mock_snap(time: &str, gos_num: &str) -> Snap {
+    Snap {
+        camera: Camera {
+            id: 3,
+            is_radar: true,
+            location: Location {
+                longitude: 37.61556,
+                latitude: 55.75222,
+            },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
+        },
+        speed: Some(70),
+        time: time.to_string(),
+        date: "01.01.2025".to_string(),
+        gos_num: gos_num.to_string(),
+        idempotency_key: None,
+    }
+}
+
+fn service() -> WatchlistService {
+    WatchlistService::from(
+        Arc::new(MemoryWatchlistRepo::default()),
+        Arc::new(AlertHub::new()),
+    )
+}
+
+#[tokio::test]
+async fn test_watchlist_add_entry() {
+    let service = service();
+
+    let entry = service
+        .add_entry(
+            "А***МР*",
+            " Угон ",
+            Some("31.12.2025 23:59"),
+            "exist@exist.com",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(entry.id, 1);
+    assert_eq!(entry.reason, "Угон");
+    assert_eq!(entry.created_by, "exist@exist.com");
+    assert_eq!(service.get_entries().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_watchlist_add_entry_invalid_mask() {
+    let res = service()
+        .add_entry("А77МР77", "Угон", None, "exist@exist.com")
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Invalid data: gos number mask"
+    );
+}
+
+#[tokio::test]
+async fn test_watchlist_add_entry_empty_reason() {
+    let res = service()
+        .add_entry("А777МР77", "  ", None, "exist@exist.com")
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: reason");
+}
+
+#[tokio::test]
+async fn test_watchlist_add_entry_invalid_expiration() {
+    let res = service()
+        .add_entry("А777МР77", "Угон", Some("31.12.2025"), "exist@exist.com")
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: datetime");
+}
+
+#[tokio::test]
+async fn test_watchlist_update_and_delete_missing_entry() {
+    let service = service();
+
+    let res = service.update_entry(7, "А777МР77", "Угон", None).await;
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "watchlist entry is not found"
+    );
+
+    let res = service.delete_entry(7).await;
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "watchlist entry is not found"
+    );
+}
+
+#[tokio::test]
+async fn test_watchlist_check_snap_by_mask() {
+    let service = service();
+    service
+        .add_entry("А***МР*", "Угон", None, "exist@exist.com")
+        .await
+        .unwrap();
+
+    let alerts = service
+        .check_snap(&mock_snap("8:10", "А123МР777"))
+        .await
+        .unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].entry_id, 1);
+    assert_eq!(alerts[0].camera_id, 3);
+    assert_eq!(alerts[0].reason, "Угон");
+    assert!((alerts[0].cords.latitude - 55.75222).abs() < 1e-9);
+
+    let alerts = service
+        .check_snap(&mock_snap("8:10", "В123МР77"))
+        .await
+        .unwrap();
+    assert!(alerts.is_empty());
+}
+
+#[tokio::test]
+async fn test_watchlist_check_snap_exact_region() {
+    let service = service();
+    service
+        .add_entry("А777МР77", "Угон", None, "exist@exist.com")
+        .await
+        .unwrap();
+
+    let alerts = service
+        .check_snap(&mock_snap("8:10", "А777МР777"))
+        .await
+        .unwrap();
+    assert!(alerts.is_empty());
+}
+
+#[tokio::test]
+async fn test_watchlist_check_snap_expired_entry() {
+    let service = service();
+    service
+        .add_entry(
+            "А777МР77",
+            "Угон",
+            Some("01.01.2025 9:00"),
+            "exist@exist.com",
+        )
+        .await
+        .unwrap();
+
+    let before = service
+        .check_snap(&mock_snap("8:10", "А777МР77"))
+        .await
+        .unwrap();
+    let after = service
+        .check_snap(&mock_snap("9:15", "А777МР77"))
+        .await
+        .unwrap();
+
+    assert_eq!(before.len(), 1);
+    assert!(after.is_empty());
+}
+
+#[tokio::test]
+async fn test_watchlist_replayed_snap_not_alerted_twice() {
+    let service = service();
+    service
+        .add_entry("А777МР77", "Угон", None, "exist@exist.com")
+        .await
+        .unwrap();
+
+    let snap = mock_snap("8:10", "А777МР77");
+    assert_eq!(service.check_snap(&snap).await.unwrap().len(), 1);
+    assert!(service.check_snap(&snap).await.unwrap().is_empty());
+    assert_eq!(
+        service
+            .get_alerts_by_date("01.01.2025")
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_watchlist_deleted_entry_not_alerted() {
+    let service = service();
+    let entry = service
+        .add_entry("А777МР77", "Угон", None, "exist@exist.com")
+        .await
+        .unwrap();
+    service.delete_entry(entry.id).await.unwrap();
+
+    let alerts = service
+        .check_snap(&mock_snap("8:10", "А777МР77"))
+        .await
+        .unwrap();
+    assert!(alerts.is_empty());
+}
+
+#[tokio::test]
+async fn test_watchlist_alert_pushed_on_snap_insert() {
+    let watchlist = Arc::new(WatchlistService::from(
+        Arc::new(MockWatchlistRepo),
+        Arc::new(AlertHub::new()),
+    ));
+    let sender = SnapSendService::from(Arc::new(MockSnapRepo), Arc::new(MockCameraRepo))
+        .with_watchlist(watchlist.clone());
+    let mut subscription = watchlist.subscribe_alerts();
+
+    let snap = mock_snap("8:10", "А777МР77");
+    sender
+        .insert_snap(&IncomingSnap {
+            camera_id: snap.camera.id,
+            reported_camera: None,
+            speed: snap.speed,
+            time: snap.time,
+            date: snap.date,
+            gos_num: snap.gos_num,
+            idempotency_key: None,
+        })
+        .await
+        .unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(1), subscription.next())
+        .await
+        .unwrap()
+        .unwrap();
+    match event {
+        AlertEvent::Item(alert) => {
+            assert_eq!(alert.gos_num, "А777МР77");
+            assert_eq!(alert.camera_id, 3);
+            assert_eq!(alert.time, "8:10");
+        }
+        AlertEvent::Lagged(_) => panic!("unexpected lagged event"),
+    }
+}
+
+#[tokio::test]
+async fn test_watchlist_get_alerts_invalid_date() {
+    let res = service().get_alerts_by_date("0101.2025").await;
+
+    assert!(res.is_err());
+    assert_eq!(res.err().unwrap().to_string(), "Invalid data: date");
+}
@@ -13,3 +13,15 @@ pub enum DataAccessError {
     #[error(transparent)]
     ClickHouseBaseError(#[from] clickhouse::error::Error),
 }
+
+impl DataAccessError {
+    // Хранилище отвергло сами данные (ограничение, неверный формат), повтор не поможет
+    pub fn is_rejected_data(&self) -> bool {
+        matches!(
+            self,
+            DataAccessError::InvalidInput(_)
+                | DataAccessError::PsqlDataBaseError(sqlx::Error::Database(_))
+                | DataAccessError::ClickHouseBaseError(clickhouse::error::Error::BadResponse(_))
+        )
+    }
+}
@@ -1,9 +1,11 @@
-use super::create_clickhouse_client;
+use super::super::{install_date, radius_bbox};
+use super::{create_clickhouse_client, MaxIDRow};
 use crate::error::DataAccessError;
 use crate::repositories_traits::CameraRepository;
 use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
 use clickhouse::{Client, Row};
-use models::{Camera, Location};
+use models::{BoundingBox, Camera, Location, NearbyCamera};
 use serde::Deserialize;
 
 pub struct ClickHouseCameraRepo {
@@ -19,6 +21,11 @@ impl ClickHouseCameraRepo {
         log::info!("Successfully connected to ClickHouse");
         Ok(Self { client })
     }
+
+    pub fn from_client(client: Client) -> Self {
+        log::info!("Creating ClickHouseCameraRepo on shared ClickHouse client");
+        Self { client }
+    }
 }
 
 #[derive(Debug, Deserialize, Row)]
@@ -32,6 +39,23 @@ struct CameraRow {
     is_radar: bool,
     longitude: f64,
     latitude: f64,
+    speed_limit: Option<u16>,
+    #[serde(with = "clickhouse::serde::chrono::date")]
+    install_date: NaiveDate,
+    is_active: bool,
+}
+
+#[derive(Debug, Deserialize, Row)]
+struct NearbyCameraRow {
+    id: u32,
+    is_radar: bool,
+    longitude: f64,
+    latitude: f64,
+    speed_limit: Option<u16>,
+    #[serde(with = "clickhouse::serde::chrono::date")]
+    install_date: NaiveDate,
+    is_active: bool,
+    distance: f64,
 }
 
 #[derive(Debug, Deserialize, Row)]
@@ -40,6 +64,20 @@ struct AvgSpeedRow {
 }
 
 impl ClickHouseCameraRepo {
+    async fn gen_id(&self) -> Result<u32, DataAccessError> {
+        let max_id_result = self
+            .client
+            .query("SELECT max(id) as max_id FROM Camera")
+            .fetch_one::<MaxIDRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get max id: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        Ok(max_id_result.max_id + 1)
+    }
+
     fn from_row_to_camera(camera: CameraRow) -> Camera {
         Camera {
             id: camera.id as usize,
@@ -48,6 +86,26 @@ impl ClickHouseCameraRepo {
                 longitude: camera.longitude,
                 latitude: camera.latitude,
             },
+            speed_limit: camera.speed_limit,
+            install_date: Some(camera.install_date.format("%d.%m.%Y").to_string()),
+            is_active: camera.is_active,
+        }
+    }
+
+    fn from_row_to_nearby_camera(camera: NearbyCameraRow) -> NearbyCamera {
+        NearbyCamera {
+            camera: Camera {
+                id: camera.id as usize,
+                is_radar: camera.is_radar,
+                location: Location {
+                    longitude: camera.longitude,
+                    latitude: camera.latitude,
+                },
+                speed_limit: camera.speed_limit,
+                install_date: Some(camera.install_date.format("%d.%m.%Y").to_string()),
+                is_active: camera.is_active,
+            },
+            distance: camera.distance,
         }
     }
 }
@@ -76,7 +134,7 @@ impl CameraRepository for ClickHouseCameraRepo {
     async fn get_camera_by_id(&self, id: usize) -> Result<Camera, DataAccessError> {
         log::info!("Getting camera by ID: {}", id);
 
-        let query = "SELECT id, is_radar, longitude, latitude FROM Camera WHERE id = ? LIMIT 1";
+        let query = "SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active FROM Camera WHERE id = ? LIMIT 1";
 
         let camera = self
             .client
@@ -96,7 +154,7 @@ impl CameraRepository for ClickHouseCameraRepo {
         log::info!("Getting camera by location: {:?}", location);
 
         let query = "
-            SELECT id, is_radar, longitude, latitude
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active
             FROM Camera
             WHERE longitude = ? AND latitude = ?
             LIMIT 1
@@ -117,6 +175,117 @@ impl CameraRepository for ClickHouseCameraRepo {
         Ok(Self::from_row_to_camera(camera))
     }
 
+    async fn find_nearest_cameras(
+        &self,
+        location: &Location,
+        k: usize,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError> {
+        log::info!("Getting {} nearest cameras to location: {:?}", k, location);
+
+        let query = "
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active,
+                toFloat64(greatCircleDistance(?, ?, longitude, latitude)) AS distance
+            FROM Camera
+            ORDER BY distance, id
+            LIMIT ?
+        ";
+
+        let cameras = self
+            .client
+            .query(query)
+            .bind(location.longitude)
+            .bind(location.latitude)
+            .bind(k as u64)
+            .fetch_all::<NearbyCameraRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get nearest cameras: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Found {} nearest cameras", cameras.len());
+        Ok(cameras
+            .into_iter()
+            .map(Self::from_row_to_nearby_camera)
+            .collect())
+    }
+
+    async fn find_cameras_within(
+        &self,
+        location: &Location,
+        radius_m: f64,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError> {
+        log::info!(
+            "Getting cameras within {} m of location: {:?}",
+            radius_m,
+            location
+        );
+
+        let bbox = radius_bbox(location, radius_m);
+        let query = "
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active,
+                toFloat64(greatCircleDistance(?, ?, longitude, latitude)) AS distance
+            FROM Camera
+            WHERE latitude BETWEEN ? AND ? AND longitude BETWEEN ? AND ?
+                AND distance <= ?
+            ORDER BY distance, id
+        ";
+
+        let cameras = self
+            .client
+            .query(query)
+            .bind(location.longitude)
+            .bind(location.latitude)
+            .bind(bbox.south_west.latitude)
+            .bind(bbox.north_east.latitude)
+            .bind(bbox.south_west.longitude)
+            .bind(bbox.north_east.longitude)
+            .bind(radius_m)
+            .fetch_all::<NearbyCameraRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get cameras within radius: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Found {} cameras within radius", cameras.len());
+        Ok(cameras
+            .into_iter()
+            .map(Self::from_row_to_nearby_camera)
+            .collect())
+    }
+
+    async fn find_cameras_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<Camera>, DataAccessError> {
+        log::info!("Getting cameras in bounding box: {:?}", bbox);
+
+        let query = "
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active
+            FROM Camera
+            WHERE latitude BETWEEN ? AND ? AND longitude BETWEEN ? AND ?
+            ORDER BY id
+        ";
+
+        let cameras = self
+            .client
+            .query(query)
+            .bind(bbox.south_west.latitude)
+            .bind(bbox.north_east.latitude)
+            .bind(bbox.south_west.longitude)
+            .bind(bbox.north_east.longitude)
+            .fetch_all::<CameraRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get cameras in bounding box: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Found {} cameras in bounding box", cameras.len());
+        Ok(cameras.into_iter().map(Self::from_row_to_camera).collect())
+    }
+
     async fn get_avg_speed_for_car_at_camera(
         &self,
         gos_num: &str,
@@ -130,7 +299,7 @@ impl CameraRepository for ClickHouseCameraRepo {
 
         let query = "
             SELECT avg(speed) AS avg_speed
-            FROM CarSnapshot
+            FROM CarSnapshot FINAL
             WHERE gos_num = ? AND camera_id = ?
         ";
 
@@ -144,4 +313,94 @@ impl CameraRepository for ClickHouseCameraRepo {
 
         Ok(row.avg_speed.unwrap_or(0.))
     }
+
+    async fn insert_camera(&self, camera: &Camera) -> Result<usize, DataAccessError> {
+        log::info!("Inserting camera at location: {:?}", camera.location);
+
+        let id = self.gen_id().await?;
+        let install_date = install_date(camera)?.unwrap_or_else(|| Utc::now().date_naive());
+        let query = "
+            INSERT INTO Camera (id, longitude, latitude, install_date, is_radar, speed_limit, is_active)
+            VALUES (?, ?, ?, toDate(?), ?, ?, ?)
+        ";
+
+        self.client
+            .query(query)
+            .bind(id)
+            .bind(camera.location.longitude)
+            .bind(camera.location.latitude)
+            .bind(install_date.format("%Y-%m-%d").to_string())
+            .bind(camera.is_radar as u8)
+            .bind(camera.speed_limit)
+            .bind(camera.is_active as u8)
+            .execute()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to insert camera: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Successfully inserted camera {}", id);
+        Ok(id as usize)
+    }
+
+    async fn update_camera(&self, camera: &Camera) -> Result<(), DataAccessError> {
+        log::info!("Updating camera {}", camera.id);
+
+        let stored = self.get_camera_by_id(camera.id).await?;
+        let install_date = match install_date(camera)? {
+            Some(date) => date,
+            None => install_date(&stored)?.unwrap_or_else(|| Utc::now().date_naive()),
+        };
+        let query = "
+            ALTER TABLE Camera
+            UPDATE
+              longitude = ?,
+              latitude = ?,
+              install_date = toDate(?),
+              is_radar = ?,
+              speed_limit = ?,
+              is_active = ?
+            WHERE id = ?
+        ";
+
+        self.client
+            .query(query)
+            .bind(camera.location.longitude)
+            .bind(camera.location.latitude)
+            .bind(install_date.format("%Y-%m-%d").to_string())
+            .bind(camera.is_radar as u8)
+            .bind(camera.speed_limit)
+            .bind(camera.is_active as u8)
+            .bind(camera.id as u32)
+            .execute()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to update camera: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Successfully updated camera");
+        Ok(())
+    }
+
+    async fn decommission_camera(&self, id: usize) -> Result<(), DataAccessError> {
+        log::info!("Decommissioning camera {}", id);
+
+        self.get_camera_by_id(id).await?;
+        let query = "ALTER TABLE Camera UPDATE is_active = 0 WHERE id = ?";
+
+        self.client
+            .query(query)
+            .bind(id as u32)
+            .execute()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to decommission camera: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Successfully decommissioned camera");
+        Ok(())
+    }
 }
@@ -1,10 +1,10 @@
-use super::create_clickhouse_client;
+use super::{create_clickhouse_client, BoundQuery, QueryParam, TotalRow};
 use crate::error::DataAccessError;
 use crate::repositories_traits::CarRepository;
 use async_trait::async_trait;
 use chrono::{Datelike, NaiveDate};
 use clickhouse::{Client, Row};
-use models::{Car, Document};
+use models::{Car, CarFilter, DetailedCar, Document, OwnershipRecord, Page, Paged, SortKey};
 use serde::Deserialize;
 
 pub struct ClickHouseCarRepo {
@@ -20,6 +20,11 @@ impl ClickHouseCarRepo {
         log::info!("Successfully connected to ClickHouse");
         Ok(Self { client })
     }
+
+    pub fn from_client(client: Client) -> Self {
+        log::info!("Creating ClickHouseCarRepo on shared ClickHouse client");
+        Self { client }
+    }
 }
 
 impl ClickHouseCarRepo {
@@ -115,17 +120,10 @@ impl ClickHouseCarRepo {
 }
 
 impl ClickHouseCarRepo {
-    fn build_filter_query(
-        firstname: Option<&str>,
-        surname: Option<&str>,
-        lastname: Option<&str>,
-        passport: Option<Document>,
-        gos_num_mask: Option<&str>,
-    ) -> Result<String, DataAccessError> {
-        let mut query_builder = Self::joined_tables_query();
-        query_builder.push_str(" WHERE 1 = 1 ");
-
-        let pserial = match &passport {
+    fn build_filter_query(filter: &CarFilter) -> Result<BoundQuery, DataAccessError> {
+        let mut query = BoundQuery::from(Self::joined_tables_query() + " WHERE 1 = 1 ");
+
+        let pserial = match &filter.passport {
             Some(psprt) => Some(psprt.serial.clone().parse::<i32>().map_err(|_| {
                 log::error!("Invalid passport serial format: {}", &psprt.serial);
                 DataAccessError::InvalidInput("Invalid passport serial format".to_string())
@@ -133,7 +131,7 @@ impl ClickHouseCarRepo {
             None => None,
         };
 
-        let pnumber = match &passport {
+        let pnumber = match &filter.passport {
             Some(psprt) => Some(psprt.number.clone().parse::<i32>().map_err(|_| {
                 log::error!("Invalid passport number format: {}", &psprt.number);
                 DataAccessError::InvalidInput("Invalid passport number format".to_string())
@@ -141,64 +139,290 @@ impl ClickHouseCarRepo {
             None => None,
         };
 
-        if let Some(frstname) = firstname {
-            let val = frstname.replace("'", "");
-            query_builder.push_str(&format!(" AND o.name = '{}' ", val));
+        if let Some(frstname) = &filter.firstname {
+            query.and_name("o.name", frstname);
         }
 
-        if let Some(surname) = surname {
-            let val = surname.replace("'", "");
-            query_builder.push_str(&format!(" AND o.surname = '{}' ", val));
+        if let Some(surname) = &filter.surname {
+            query.and_name("o.surname", surname);
         }
 
-        if let Some(lastname) = lastname {
-            let val = lastname.replace("'", "");
-            query_builder.push_str(&format!(" AND o.lastname = '{}' ", val));
+        if let Some(lastname) = &filter.lastname {
+            query.and_name("o.lastname", lastname);
         }
 
         if let Some(pserial) = pserial {
-            query_builder.push_str(&format!(" AND o.passport_serial = {} ", pserial));
+            query.and("o.passport_serial = ?", QueryParam::Int(pserial));
         }
 
         if let Some(pnum) = pnumber {
-            query_builder.push_str(&format!(" AND o.passport_num = {} ", pnum));
+            query.and("o.passport_num = ?", QueryParam::Int(pnum));
+        }
+
+        if let Some(gos_num) = &filter.gos_num_mask {
+            query.and(
+                "s.gos_num LIKE ?",
+                QueryParam::Str(Self::transform_mask_for_psql_like(gos_num)),
+            );
+        }
+
+        // VIN короче 17 символов ищется по началу
+        match &filter.vin {
+            Some(vin) if vin.len() == 17 => query.and("s.vin = ?", QueryParam::Str(vin.clone())),
+            Some(vin) => query.and("s.vin LIKE ?", QueryParam::Str(format!("{vin}%"))),
+            None => {}
+        }
+
+        if let Some(mark) = &filter.mark {
+            query.and("lower(s.mark) = lower(?)", QueryParam::Str(mark.clone()));
+        }
+
+        if let Some(model) = &filter.model {
+            query.and("lower(s.model) = lower(?)", QueryParam::Str(model.clone()));
+        }
+
+        if let Some(color) = &filter.color {
+            query.and("lower(c.color) = lower(?)", QueryParam::Str(color.clone()));
+        }
+
+        if let Some(year) = filter.year_from {
+            query.and(
+                "s.release_date >= makeDate(?, 1, 1)",
+                QueryParam::Int(year as i32),
+            );
         }
 
-        if let Some(gos_num) = gos_num_mask {
-            let val = gos_num.replace("'", "");
-            query_builder.push_str(&format!(" AND  s.gos_num LIKE '{}' ", val));
+        if let Some(year) = filter.year_to {
+            query.and(
+                "s.release_date < makeDate(?, 1, 1)",
+                QueryParam::Int(year as i32 + 1),
+            );
         }
 
-        Ok(query_builder)
+        if let Some(engine_type) = &filter.engine_type {
+            query.and("s.engine_type = ?", QueryParam::Str(engine_type.clone()));
+        }
+
+        if let Some(car_class) = &filter.car_class {
+            query.and("s.car_class = ?", QueryParam::Str(car_class.clone()));
+        }
+
+        Ok(query)
     }
 }
 
-#[async_trait]
-impl CarRepository for ClickHouseCarRepo {
-    async fn get_cars_by_filters(
+#[derive(Debug, Deserialize, Row)]
+struct DetailedCarRow {
+    name: String,
+    surname: String,
+    lastname: Option<String>,
+    gos_num: String,
+    model: String,
+    mark: String,
+    color: String,
+    mileage: i32,
+    release_date: String,
+    reg_date: String,
+    vin: String,
+    horse_power: i32,
+    car_weight: i32,
+    engine_type: String,
+    car_class: String,
+    sts_serial: i32,
+    sts_num: i32,
+    pts_serial: i32,
+    pts_number: i32,
+    import_country: String,
+}
+
+impl ClickHouseCarRepo {
+    fn detailed_tables_query() -> String {
+        log::debug!("Generating joined tables query for detailed cars");
+        "SELECT 
+            o.name, 
+            o.surname, 
+            o.lastname, 
+            s.gos_num, 
+            s.model, 
+            s.mark, 
+            c.color, 
+            c.mileage, 
+            formatDateTime(s.release_date, '%d.%m.%Y') as release_date, 
+            formatDateTime(s.reg_date, '%d.%m.%Y') as reg_date, 
+            s.vin, 
+            s.horse_power, 
+            s.car_weight, 
+            s.engine_type, 
+            s.car_class, 
+            s.sts_serial, 
+            s.sts_num, 
+            p.pts_serial, 
+            p.pts_number, 
+            p.import_country 
+         FROM CarOwner o
+         JOIN Car c ON o.id = c.owner_id
+         JOIN STS s ON c.id = s.car_id
+         JOIN PTS p ON c.id = p.id"
+            .to_string()
+    }
+
+    fn detailed_car_row_to_detailed_car(row: DetailedCarRow) -> DetailedCar {
+        let release_date = row.release_date.clone();
+        DetailedCar {
+            car: Self::car_row_to_car(CarRow {
+                name: row.name,
+                surname: row.surname,
+                lastname: row.lastname,
+                gos_num: row.gos_num,
+                model: row.model,
+                mark: row.mark,
+                color: row.color,
+                release_date: row.release_date,
+                vin: row.vin,
+                sts_serial: row.sts_serial,
+                sts_num: row.sts_num,
+                pts_serial: row.pts_serial,
+                pts_number: row.pts_number,
+            }),
+            mileage: row.mileage as u32,
+            horse_power: Some(row.horse_power as u32),
+            car_weight: Some(row.car_weight as u32),
+            engine_type: row.engine_type,
+            car_class: row.car_class,
+            release_date,
+            reg_date: row.reg_date,
+            import_country: row.import_country,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Row)]
+struct OwnershipRow {
+    name: String,
+    surname: String,
+    lastname: Option<String>,
+    mileage: i32,
+    #[serde(with = "clickhouse::serde::chrono::date")]
+    reg_date: NaiveDate,
+    #[serde(with = "clickhouse::serde::chrono::date::option")]
+    dereg_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, Row)]
+struct CountRow {
+    cnt: u64,
+}
+
+impl ClickHouseCarRepo {
+    // column — столбец STS, по которому ищется автомобиль
+    async fn get_owner_history(
         &self,
-        firstname: Option<&str>,
-        surname: Option<&str>,
-        lastname: Option<&str>,
-        passport: Option<Document>,
-        gos_num_mask: Option<&str>,
-    ) -> Result<Vec<Car>, DataAccessError> {
-        let transformed_gos_num = gos_num_mask.map(|gsn| Self::transform_mask_for_psql_like(gsn));
-        log::info!(
-            "Searching cars by filters: {:?} {:?} {:?} {:?} {:?}",
-            firstname,
-            surname,
-            lastname,
-            transformed_gos_num,
-            passport,
+        column: &str,
+        value: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        let query = format!(
+            "SELECT 
+                o.name, 
+                o.surname, 
+                o.lastname, 
+                h.mileage, 
+                h.reg_date, 
+                h.dereg_date 
+             FROM STS s
+             JOIN PTS p ON s.id = p.sts_id
+             JOIN OwnerHistory h ON p.id = h.pts_id
+             JOIN OwnerHistoryOwner ho ON h.id = ho.owner_history_id
+             JOIN CarOwner o ON ho.owner_id = o.id
+             WHERE s.{column} = ?
+             ORDER BY h.reg_date, h.id"
         );
-
-        let query = Self::build_filter_query(firstname, surname, lastname, passport, gos_num_mask)?;
-        log::debug!("Executing query:\n{}", query);
+        log::debug!("Executing query: {} with param: {}", query, value);
 
         let rows = self
             .client
             .query(&query)
+            .bind(value)
+            .fetch_all::<OwnershipRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Owner history query failed for {} {}: {}", column, value, e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        if rows.is_empty() {
+            let query = format!("SELECT count() as cnt FROM STS WHERE {column} = ?");
+            let row = self
+                .client
+                .query(&query)
+                .bind(value)
+                .fetch_one::<CountRow>()
+                .await
+                .map_err(|e| {
+                    log::error!("Car lookup failed for {} {}: {}", column, value, e);
+                    DataAccessError::ClickHouseBaseError(e)
+                })?;
+            if row.cnt == 0 {
+                log::warn!("Car with {} {} not found", column, value);
+                return Err(DataAccessError::NotFoundError("car".to_string()));
+            }
+        }
+
+        log::info!(
+            "Found {} owner history records for {} {}",
+            rows.len(),
+            column,
+            value
+        );
+        Ok(rows
+            .into_iter()
+            .map(|row| OwnershipRecord {
+                owner_fio: (row.surname, row.name, row.lastname),
+                reg_date: row.reg_date.format("%d.%m.%Y").to_string(),
+                dereg_date: row
+                    .dereg_date
+                    .map(|date| date.format("%d.%m.%Y").to_string()),
+                mileage: row.mileage as u32,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CarRepository for ClickHouseCarRepo {
+    async fn get_cars_by_filters(
+        &self,
+        filter: &CarFilter,
+        page: &Page,
+    ) -> Result<Paged<Car>, DataAccessError> {
+        log::info!("Searching cars by filters: {:?} ({:?})", filter, page);
+
+        let order_by = match page.sort {
+            SortKey::gos_num => "s.gos_num, s.vin",
+            SortKey::owner_surname => "o.surname, o.name, s.gos_num, s.vin",
+            SortKey::track_time => {
+                log::error!("Cars can't be sorted by track time");
+                return Err(DataAccessError::InvalidInput(
+                    "Invalid sort key for cars".to_string(),
+                ));
+            }
+        };
+
+        let mut query = Self::build_filter_query(filter)?;
+        let count_query = query.count();
+        query.paginate(order_by, page);
+
+        let total = count_query
+            .query(&self.client)
+            .fetch_one::<TotalRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Count query failed: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?
+            .total as usize;
+
+        let rows = query
+            .query(&self.client)
             .fetch_all::<CarRow>()
             .await
             .map_err(|e| {
@@ -206,8 +430,15 @@ impl CarRepository for ClickHouseCarRepo {
                 DataAccessError::ClickHouseBaseError(e)
             })?;
 
-        log::info!("Found {} cars matching filters", rows.len());
-        Ok(Self::car_rows_to_cars(&rows))
+        log::info!(
+            "Found {} cars matching filters, {} on page",
+            total,
+            rows.len()
+        );
+        Ok(Paged {
+            items: Self::car_rows_to_cars(&rows),
+            total,
+        })
     }
 
     async fn get_car_by_gos_number_mask(
@@ -253,11 +484,16 @@ impl CarRepository for ClickHouseCarRepo {
             last_name
         );
 
-        let query = Self::build_filter_query(name, surname, last_name, None, None)?;
+        let mut query = Self::build_filter_query(&CarFilter {
+            firstname: name.map(String::from),
+            surname: surname.map(String::from),
+            lastname: last_name.map(String::from),
+            ..CarFilter::default()
+        })?;
+        query.order_by("s.gos_num, s.vin");
 
-        let rows = self
-            .client
-            .query(&query)
+        let rows = query
+            .query(&self.client)
             .fetch_all::<CarRow>()
             .await
             .map_err(|e| {
@@ -312,4 +548,47 @@ impl CarRepository for ClickHouseCarRepo {
         log::info!("Found {} cars matching passport criteria", rows.len());
         Ok(Self::car_rows_to_cars(&rows))
     }
+
+    async fn get_detailed_cars_by_vins(
+        &self,
+        vins: &[String],
+    ) -> Result<Vec<DetailedCar>, DataAccessError> {
+        log::info!("Getting detailed cars by {} VINs", vins.len());
+
+        let query = format!("{} WHERE has(?, s.vin)", Self::detailed_tables_query());
+        log::debug!("Executing query: {} with params: {:?}", query, vins);
+
+        let rows = self
+            .client
+            .query(&query)
+            .bind(vins)
+            .fetch_all::<DetailedCarRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Query failed for detailed cars: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        log::info!("Found {} detailed cars", rows.len());
+        Ok(rows
+            .into_iter()
+            .map(Self::detailed_car_row_to_detailed_car)
+            .collect())
+    }
+
+    async fn get_owner_history_by_vin(
+        &self,
+        vin: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        log::info!("Getting owner history by VIN: {}", vin);
+        self.get_owner_history("vin", vin).await
+    }
+
+    async fn get_owner_history_by_gos_num(
+        &self,
+        gos_num: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        log::info!("Getting owner history by gos number: {}", gos_num);
+        self.get_owner_history("gos_num", gos_num).await
+    }
 }
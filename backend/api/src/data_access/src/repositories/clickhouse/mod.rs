@@ -19,7 +19,7 @@ lazy_static! {
     pub static ref CLICKHOUSE_URL: String = cfg::var("database.clickhouse_url");
 }
 
-fn create_clickhouse_client(dsn: &str) -> Client {
+pub fn create_clickhouse_client(dsn: &str) -> Client {
     let url = Url::parse(dsn).expect("Invalid ClickHouse URL");
 
     let host = format!(
@@ -40,10 +40,105 @@ fn create_clickhouse_client(dsn: &str) -> Client {
         .with_database(database)
 }
 
-use clickhouse::Row;
-use serde::Deserialize;
+use clickhouse::{query::Query, Row};
+use models::Page;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Row)]
 struct MaxIDRow {
     max_id: u32,
 }
+
+#[derive(Debug, Deserialize, Row)]
+struct TotalRow {
+    total: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+enum QueryParam {
+    Str(String),
+    Int(i32),
+    UInt(u64),
+}
+
+// Запрос с условиями, значения которых передаются только через bind
+#[derive(Debug)]
+struct BoundQuery {
+    sql: String,
+    params: Vec<QueryParam>,
+    // Колонки и образцы имен, по оценке совпадения с которыми ранжируется выборка
+    name_scores: Vec<(String, QueryParam)>,
+}
+
+impl BoundQuery {
+    fn from(sql: String) -> Self {
+        BoundQuery {
+            sql,
+            params: Vec::new(),
+            name_scores: Vec::new(),
+        }
+    }
+
+    fn and(&mut self, condition: &str, param: QueryParam) {
+        self.sql.push_str(" AND ");
+        self.sql.push_str(condition);
+        self.sql.push(' ');
+        self.params.push(param);
+    }
+
+    // Имя сравнивается без учета регистра и различия ё/е, по началу или нечетко
+    fn and_name(&mut self, column: &str, name: &str) {
+        let param = QueryParam::Str(name.to_string());
+        self.and(
+            &format!("name_match_score({column}, ?) IS NOT NULL"),
+            param.clone(),
+        );
+        self.name_scores.push((column.to_string(), param));
+    }
+
+    // Число строк запроса с теми же условиями, без учета страницы
+    fn count(&self) -> BoundQuery {
+        BoundQuery {
+            sql: format!("SELECT count() AS total FROM ({})", self.sql),
+            params: self.params.clone(),
+            name_scores: Vec::new(),
+        }
+    }
+
+    // Найденные по имени строки идут первыми в порядке близости к образцу
+    fn order_by(&mut self, order_by: &str) {
+        self.sql.push_str(" ORDER BY ");
+        if !self.name_scores.is_empty() {
+            let score = self
+                .name_scores
+                .iter()
+                .map(|(column, _)| format!("name_match_score({column}, ?)"))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            self.sql.push_str(&score);
+            self.sql.push_str(", ");
+            self.params
+                .extend(self.name_scores.iter().map(|(_, param)| param.clone()));
+        }
+        self.sql.push_str(order_by);
+    }
+
+    fn paginate(&mut self, order_by: &str, page: &Page) {
+        self.order_by(order_by);
+        self.sql.push_str(" LIMIT ? OFFSET ? ");
+        self.params.push(QueryParam::UInt(page.limit as u64));
+        self.params.push(QueryParam::UInt(page.offset as u64));
+    }
+
+    fn query(&self, client: &Client) -> Query {
+        log::debug!(
+            "Executing query:\n{}\nwith params: {:?}",
+            self.sql,
+            self.params
+        );
+        self.params
+            .iter()
+            .fold(client.query(&self.sql), |query, param| query.bind(param))
+    }
+}
@@ -1,11 +1,13 @@
 use super::MaxIDRow;
 use crate::error::DataAccessError;
+use crate::repositories::day_period;
 use crate::repositories_traits::SnapRepository;
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime};
 use clickhouse::{Client, Row};
 use models::{Camera, Location, Snap};
 use serde::Deserialize;
+use std::collections::HashSet;
 
 use super::create_clickhouse_client;
 
@@ -22,6 +24,11 @@ impl ClickHouseSnapRepo {
         log::info!("Successfully connected to ClickHouse");
         Ok(Self { client })
     }
+
+    pub fn from_client(client: Client) -> Self {
+        log::info!("Creating ClickHouseSnapRepo on shared ClickHouse client");
+        Self { client }
+    }
 }
 
 #[allow(dead_code)]
@@ -31,10 +38,22 @@ struct CarSnapshotRow {
     speed: Option<u16>,
     gos_num: String,
     snap_datetime: NaiveDateTime,
+    idempotency_key: Option<String>,
     camera_id: u32,
     is_radar: bool,
     longitude: f64,
     latitude: f64,
+    speed_limit: Option<u16>,
+    #[serde(with = "clickhouse::serde::chrono::date")]
+    install_date: NaiveDate,
+    is_active: bool,
+}
+
+#[derive(Debug, Deserialize, Row)]
+struct StoredSnapKeyRow {
+    camera_id: u32,
+    gos_num: String,
+    snap_datetime: String,
 }
 
 impl ClickHouseSnapRepo {
@@ -45,11 +64,15 @@ impl ClickHouseSnapRepo {
             s.speed,
             s.gos_num,
             s.snap_datetime,
+            s.idempotency_key,
             c.id AS camera_id,
             c.is_radar,
             c.longitude,
-            c.latitude
-        FROM CarSnapshot s
+            c.latitude,
+            c.speed_limit,
+            c.install_date,
+            c.is_active
+        FROM CarSnapshot s FINAL
         INNER JOIN Camera c ON s.camera_id = c.id"
             .to_string()
     }
@@ -66,11 +89,15 @@ impl ClickHouseSnapRepo {
                         longitude: r.longitude,
                         latitude: r.latitude,
                     },
+                    speed_limit: r.speed_limit,
+                    install_date: Some(r.install_date.format("%d.%m.%Y").to_string()),
+                    is_active: r.is_active,
                 },
                 speed: r.speed,
                 gos_num: r.gos_num.clone(),
                 date: r.snap_datetime.date().format("%d.%m.%Y").to_string(),
                 time: r.snap_datetime.time().format("%H:%M").to_string(),
+                idempotency_key: r.idempotency_key.clone(),
             })
             .collect();
 
@@ -90,11 +117,87 @@ impl ClickHouseSnapRepo {
 
         Ok(max_id_result.max_id + 1)
     }
+
+    fn to_db_datetime(snap: &Snap) -> Result<String, DataAccessError> {
+        NaiveDateTime::parse_from_str(&format!("{} {}", snap.date, snap.time), "%d.%m.%Y %H:%M")
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .map_err(|e| {
+                log::error!("Failed to parse datetime: {}", e);
+                DataAccessError::InvalidInput(e.to_string())
+            })
+    }
+
+    // Повторы по ключу (камера, номер, время) схлопывает ReplacingMergeTree,
+    // а до слияния частей их скрывает FINAL при чтении. Здесь отсекаются
+    // повторы внутри одной вставки, в том числе по ключу идемпотентности
+    fn unique_snaps(snaps: &[Snap], datetimes: Vec<String>) -> Vec<(&Snap, String)> {
+        let mut natural_keys = HashSet::new();
+        let mut idempotency_keys = HashSet::new();
+
+        snaps
+            .iter()
+            .zip(datetimes)
+            .filter(|(snap, datetime)| {
+                let camera_id = snap.camera.id;
+                let is_replay =
+                    !natural_keys.insert((camera_id, snap.gos_num.clone(), datetime.clone()))
+                        || snap
+                            .idempotency_key
+                            .as_ref()
+                            .is_some_and(|key| !idempotency_keys.insert((camera_id, key.clone())));
+
+                if is_replay {
+                    log::info!(
+                        "Snap for vehicle {} at {} from camera {} repeated in batch, replay acknowledged",
+                        snap.gos_num,
+                        datetime,
+                        camera_id
+                    );
+                }
+                !is_replay
+            })
+            .collect()
+    }
+
+    // Поиск идет по ключу сортировки таблицы и читает только нужные гранулы.
+    // Результат определяет, какие снимки считать новыми; одновременные повторы,
+    // прошедшие проверку, все равно схлопнет ReplacingMergeTree
+    async fn stored_keys(
+        &self,
+        snaps: &[(&Snap, String)],
+    ) -> Result<HashSet<(u32, String, String)>, DataAccessError> {
+        let keys = vec!["(?, ?, toDateTime(?))"; snaps.len()].join(", ");
+        let query = format!(
+            "SELECT camera_id, gos_num, toString(snap_datetime) AS snap_datetime
+            FROM CarSnapshot
+            WHERE (camera_id, gos_num, snap_datetime) IN ({})",
+            keys
+        );
+
+        let query = snaps
+            .iter()
+            .fold(self.client.query(&query), |query, (snap, datetime)| {
+                query
+                    .bind(snap.camera.id as u32)
+                    .bind(&snap.gos_num)
+                    .bind(datetime)
+            });
+
+        let rows = query.fetch_all::<StoredSnapKeyRow>().await.map_err(|e| {
+            log::error!("Failed to check stored snaps: {}", e);
+            DataAccessError::ClickHouseBaseError(e)
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.camera_id, row.gos_num, row.snap_datetime))
+            .collect())
+    }
 }
 
 #[async_trait]
 impl SnapRepository for ClickHouseSnapRepo {
-    async fn insert_snap(&self, snap: &Snap) -> Result<(), DataAccessError> {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
         log::info!(
             "Inserting snap for vehicle {} at {} {}",
             snap.gos_num,
@@ -102,45 +205,74 @@ impl SnapRepository for ClickHouseSnapRepo {
             snap.time
         );
 
-        let naive_dt = NaiveDateTime::parse_from_str(
-            &format!("{} {}", snap.date, snap.time),
-            "%d.%m.%Y %H:%M",
-        )
-        .map_err(|e| {
-            log::error!("Failed to parse datetime: {}", e);
-            DataAccessError::InvalidInput(e.to_string())
-        })?;
+        let inserted = self.insert_snap_batch(std::slice::from_ref(snap)).await?;
+        Ok(!inserted.is_empty())
+    }
 
-        let datetime_str = naive_dt.format("%Y-%m-%d %H:%M:%S").to_string();
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!("Inserting batch of {} snaps", snaps.len());
+        if snaps.is_empty() {
+            return Ok(vec![]);
+        }
 
-        let id = self.gen_id().await?;
-        let query = &format!(
-            "
-                INSERT INTO CarSnapshot (id, camera_id, snap_datetime, speed, gos_num, road_line)
-                VALUES (?, ?, toDateTime('{}'), {}, ?, 0)
-            ",
-            datetime_str,
-            if snap.speed.is_some() {
-                snap.speed.unwrap().to_string()
-            } else {
-                "NULL".to_string()
+        let datetimes = snaps
+            .iter()
+            .map(Self::to_db_datetime)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut new_snaps = Self::unique_snaps(snaps, datetimes);
+        let stored = self.stored_keys(&new_snaps).await?;
+        new_snaps.retain(|(snap, datetime)| {
+            let is_stored = stored.contains(&(
+                snap.camera.id as u32,
+                snap.gos_num.clone(),
+                datetime.clone(),
+            ));
+            if is_stored {
+                log::info!(
+                    "Snap for vehicle {} at {} from camera {} already stored, replay acknowledged",
+                    snap.gos_num,
+                    datetime,
+                    snap.camera.id
+                );
             }
+            !is_stored
+        });
+        if new_snaps.is_empty() {
+            log::info!("All {} snaps already stored", snaps.len());
+            return Ok(vec![]);
+        }
+
+        let first_id = self.gen_id().await?;
+        let values = vec!["(?, ?, toDateTime(?), ?, ?, 0, ?)"; new_snaps.len()].join(", ");
+        let query = format!(
+            "INSERT INTO CarSnapshot (id, camera_id, snap_datetime, speed, gos_num, road_line, idempotency_key) VALUES {}",
+            values
         );
 
-        self.client
-            .query(query)
-            .bind(id)
-            .bind(snap.camera.id as u32)
-            .bind(&snap.gos_num)
-            .execute()
-            .await
-            .map_err(|e| {
-                log::error!("Insert failed: {}", e);
-                DataAccessError::ClickHouseBaseError(e)
-            })?;
+        let query = new_snaps.iter().enumerate().fold(
+            self.client.query(&query),
+            |query, (i, (snap, datetime))| {
+                query
+                    .bind(first_id + i as u32)
+                    .bind(snap.camera.id as u32)
+                    .bind(datetime)
+                    .bind(snap.speed)
+                    .bind(&snap.gos_num)
+                    .bind(&snap.idempotency_key)
+            },
+        );
 
-        log::info!("Successfully inserted snap with id {}", id);
-        Ok(())
+        query.execute().await.map_err(|e| {
+            log::error!("Batch insert failed: {}", e);
+            DataAccessError::ClickHouseBaseError(e)
+        })?;
+
+        log::info!("Successfully inserted batch of {} snaps", new_snaps.len());
+        Ok(new_snaps
+            .into_iter()
+            .map(|(snap, _)| snap.clone())
+            .collect())
     }
 
     async fn get_car_snaps_by_date(
@@ -150,33 +282,42 @@ impl SnapRepository for ClickHouseSnapRepo {
     ) -> Result<Vec<Snap>, DataAccessError> {
         log::info!("Getting snaps for vehicle {} on date {}", gos_number, date);
 
-        let date = NaiveDate::parse_from_str(date, "%d.%m.%Y").map_err(|e| {
-            log::error!("Invalid date format: {}", e);
-            DataAccessError::InvalidInput(e.to_string())
-        })?;
+        let (from, to) = day_period(date)?;
+        self.get_car_snaps_by_period(gos_number, &from, &to).await
+    }
 
-        let start_datetime = date.and_hms_opt(0, 0, 0).unwrap();
-        let end_datetime = date.and_hms_opt(23, 59, 59).unwrap();
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!(
+            "Getting snaps for vehicle {} from {} to {}",
+            gos_number,
+            from,
+            to
+        );
 
-        let start = start_datetime.format("%Y-%m-%d %H:%M:%S").to_string();
-        let end = end_datetime.format("%Y-%m-%d %H:%M:%S").to_string();
+        let start = from.format("%Y-%m-%d %H:%M:%S").to_string();
+        let end = to.format("%Y-%m-%d %H:%M:%S").to_string();
 
         let query = format!(
             "
             {}
-            WHERE s.gos_num = ? 
-              AND s.snap_datetime BETWEEN toDateTime('{}') AND toDateTime('{}')
+            WHERE s.gos_num = ?
+              AND s.snap_datetime BETWEEN toDateTime(?) AND toDateTime(?)
             ORDER BY s.snap_datetime
             ",
             Self::joined_tables_query(),
-            start,
-            end,
         );
 
         let rows = self
             .client
             .query(&query)
             .bind(gos_number)
+            .bind(start)
+            .bind(end)
             .fetch_all::<CarSnapshotRow>()
             .await
             .map_err(|e| {
@@ -208,14 +349,12 @@ impl ClickHouseSnapRepo {
             DataAccessError::InvalidInput(e.to_string())
         })?;
 
-        let query = format!(
-            "ALTER TABLE CarSnapshot DELETE WHERE camera_id = ? AND snap_datetime = toDateTime('{}') AND gos_num = ?",
-            datetime.format("%Y-%m-%d %H:%M:%S"),
-        );
+        let query = "ALTER TABLE CarSnapshot DELETE WHERE camera_id = ? AND snap_datetime = toDateTime(?) AND gos_num = ?";
 
         self.client
-            .query(&query)
+            .query(query)
             .bind(snap.camera.id)
+            .bind(datetime.format("%Y-%m-%d %H:%M:%S").to_string())
             .bind(&snap.gos_num)
             .execute()
             .await
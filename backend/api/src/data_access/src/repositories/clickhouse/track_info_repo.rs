@@ -1,10 +1,10 @@
-use super::{create_clickhouse_client, MaxIDRow};
+use super::{create_clickhouse_client, BoundQuery, MaxIDRow, QueryParam, TotalRow};
 use crate::error::DataAccessError;
 use crate::repositories_traits::TrackInfoRepository;
 use async_trait::async_trait;
 use chrono::{Datelike, NaiveDate, Utc};
 use clickhouse::{Client, Row};
-use models::{Car, Document, Role, TrackInfo, User};
+use models::{Car, Document, Page, Paged, Role, SortKey, TrackInfo, User};
 use serde::Deserialize;
 
 pub struct ClickHouseTrackInfoRepo {
@@ -20,6 +20,11 @@ impl ClickHouseTrackInfoRepo {
         log::info!("Successfully connected to ClickHouse");
         Ok(Self { client })
     }
+
+    pub fn from_client(client: Client) -> Self {
+        log::info!("Creating ClickHouseTrackInfoRepo on shared ClickHouse client");
+        Self { client }
+    }
 }
 
 impl ClickHouseTrackInfoRepo {
@@ -166,6 +171,7 @@ impl ClickHouseTrackInfoRepo {
                     "user" => Role::user,
                     "operator" => Role::operator,
                     "audit" => Role::audit,
+                    "camera" => Role::camera,
                     _ => Role::user,
                 },
                 is_verified: row.is_verified != 0,
@@ -200,9 +206,8 @@ impl ClickHouseTrackInfoRepo {
         passport: Option<Document>,
         gos_num_mask: Option<&str>,
         date: Option<&str>,
-    ) -> Result<String, DataAccessError> {
-        let mut query_builder = Self::joined_tables_query();
-        query_builder.push_str(" WHERE 1 = 1 ");
+    ) -> Result<BoundQuery, DataAccessError> {
+        let mut query = BoundQuery::from(Self::joined_tables_query() + " WHERE 1 = 1 ");
 
         let pserial = match &passport {
             Some(psprt) => Some(psprt.serial.clone().parse::<i32>().map_err(|_| {
@@ -221,26 +226,23 @@ impl ClickHouseTrackInfoRepo {
         };
 
         if let Some(frstname) = firstname {
-            let val = frstname.replace("'", "");
-            query_builder.push_str(&format!(" AND a.name = '{}' ", val));
+            query.and_name("a.name", frstname);
         }
 
         if let Some(surname) = surname {
-            let val = surname.replace("'", "");
-            query_builder.push_str(&format!(" AND a.surname = '{}' ", val));
+            query.and_name("a.surname", surname);
         }
 
         if let Some(lastname) = lastname {
-            let val = lastname.replace("'", "");
-            query_builder.push_str(&format!(" AND a.lastname = '{}' ", val));
+            query.and_name("a.lastname", lastname);
         }
 
         if let Some(pserial) = pserial {
-            query_builder.push_str(&format!(" AND a.passport_serial = {} ", pserial));
+            query.and("a.passport_serial = ?", QueryParam::Int(pserial));
         }
 
         if let Some(pnum) = pnumber {
-            query_builder.push_str(&format!(" AND a.passport_num = {} ", pnum));
+            query.and("a.passport_num = ?", QueryParam::Int(pnum));
         }
 
         if let Some(dt) = date {
@@ -252,16 +254,14 @@ impl ClickHouseTrackInfoRepo {
                 .format("%Y-%m-%d")
                 .to_string();
 
-            let val = dt.replace("'", "");
-            query_builder.push_str(&format!(" AND t.track_time::date = '{}' ", val));
+            query.and("toDate(t.track_time) = toDate(?)", QueryParam::Str(dt));
         }
 
         if let Some(gos_num) = gos_num_mask {
-            let val = gos_num.replace("'", "");
-            query_builder.push_str(&format!(" AND  s.gos_num LIKE '{}' ", val));
+            query.and("s.gos_num LIKE ?", QueryParam::Str(gos_num.to_string()));
         }
 
-        Ok(query_builder)
+        Ok(query)
     }
 }
 
@@ -342,24 +342,48 @@ impl TrackInfoRepository for ClickHouseTrackInfoRepo {
         passport: Option<Document>,
         gos_num_mask: Option<&str>,
         date: Option<&str>,
-    ) -> Result<Vec<TrackInfo>, DataAccessError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, DataAccessError> {
         let transformed_gos_num = gos_num_mask.map(|gsn| Self::transform_mask_for_psql_like(gsn));
         log::info!(
-            "Searching cars by filters: {:?} {:?} {:?} {:?} {:?}",
+            "Searching cars by filters: {:?} {:?} {:?} {:?} {:?} ({:?})",
             firstname,
             surname,
             lastname,
             transformed_gos_num,
             passport,
+            page,
         );
 
-        let query =
-            Self::build_filter_query(firstname, surname, lastname, passport, gos_num_mask, date)?;
-        log::debug!("Executing query:\n{}", query);
+        let order_by = match page.sort {
+            SortKey::gos_num => "s.gos_num, t.id",
+            SortKey::owner_surname => "o.surname, o.name, t.id",
+            SortKey::track_time => "t.track_time DESC, t.id",
+        };
 
-        let rows = self
-            .client
-            .query(&query)
+        let mut query = Self::build_filter_query(
+            firstname,
+            surname,
+            lastname,
+            passport,
+            transformed_gos_num.as_deref(),
+            date,
+        )?;
+        let count_query = query.count();
+        query.paginate(order_by, page);
+
+        let total = count_query
+            .query(&self.client)
+            .fetch_one::<TotalRow>()
+            .await
+            .map_err(|e| {
+                log::error!("Count query failed: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?
+            .total as usize;
+
+        let rows = query
+            .query(&self.client)
             .fetch_all::<TrackInfoRow>()
             .await
             .map_err(|e| {
@@ -367,19 +391,24 @@ impl TrackInfoRepository for ClickHouseTrackInfoRepo {
                 DataAccessError::ClickHouseBaseError(e)
             })?;
 
-        log::info!("Found {} cars matching filters", rows.len());
-        Ok(Self::track_info_rows_to_tracks_info(&rows))
+        log::info!(
+            "Found {} track info records by filters, {} on page",
+            total,
+            rows.len()
+        );
+        Ok(Paged {
+            items: Self::track_info_rows_to_tracks_info(&rows),
+            total,
+        })
     }
 
     async fn get_track_info_by_date(&self, date: &str) -> Result<Vec<TrackInfo>, DataAccessError> {
         log::info!("Getting track info by date: {}", date);
 
         let query = Self::build_filter_query(None, None, None, None, None, Some(date))?;
-        log::debug!("Executing query:\n{}", query);
 
-        let rows = self
-            .client
-            .query(&query)
+        let rows = query
+            .query(&self.client)
             .fetch_all::<TrackInfoRow>()
             .await
             .map_err(|e| {
@@ -434,11 +463,11 @@ impl TrackInfoRepository for ClickHouseTrackInfoRepo {
             last_name
         );
 
-        let query = Self::build_filter_query(name, surname, last_name, None, None, None)?;
+        let mut query = Self::build_filter_query(name, surname, last_name, None, None, None)?;
+        query.order_by("s.gos_num, t.id");
 
-        let rows = self
-            .client
-            .query(&query)
+        let rows = query
+            .query(&self.client)
             .fetch_all::<TrackInfoRow>()
             .await
             .map_err(|e| {
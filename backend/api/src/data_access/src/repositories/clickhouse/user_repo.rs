@@ -20,6 +20,11 @@ impl ClickHouseUserRepo {
         log::info!("Successfully connected to ClickHouse");
         Ok(Self { client })
     }
+
+    pub fn from_client(client: Client) -> Self {
+        log::info!("Creating ClickHouseUserRepo on shared ClickHouse client");
+        Self { client }
+    }
 }
 
 #[allow(dead_code)]
@@ -52,6 +57,7 @@ impl ClickHouseUserRepo {
                 "user" => Role::user,
                 "operator" => Role::operator,
                 "audit" => Role::audit,
+                "camera" => Role::camera,
                 _ => Role::user,
             },
             is_verified: row.is_verified != 0,
@@ -114,7 +120,7 @@ impl ClickHouseUserRepo {
         &self,
         id: u32,
         user: &User,
-        password: &str,
+        pswd_hash: &str,
     ) -> Result<(), DataAccessError> {
         log::info!("ClickHouse: Inserting user: {}", user.email);
 
@@ -134,25 +140,24 @@ impl ClickHouseUserRepo {
             Role::user => "user",
             Role::operator => "operator",
             Role::audit => "audit",
+            Role::camera => "camera",
         };
 
-        let query = format!(
-            "INSERT INTO AppUser (id, login, password, role, name, surname, lastname, is_verified, passport_serial, passport_num)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, {}, {})",
-            passport_serial.map_or("NULL".into(), |v| v.to_string()),
-            passport_num.map_or("NULL".into(), |v| v.to_string())
-        );
+        let query = "INSERT INTO AppUser (id, login, password, role, name, surname, lastname, is_verified, passport_serial, passport_num)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
 
         self.client
-            .query(&query)
+            .query(query)
             .bind(id)
             .bind(&user.email)
-            .bind(password)
+            .bind(pswd_hash)
             .bind(role_str)
             .bind(&user.name)
             .bind(&user.surname)
             .bind(user.lastname.as_ref().unwrap_or(&"NULL".to_string()))
             .bind(if user.is_verified { 1 } else { 0 })
+            .bind(passport_serial)
+            .bind(passport_num)
             .execute()
             .await
             .map_err(|e| {
@@ -166,34 +171,15 @@ impl ClickHouseUserRepo {
 
 #[async_trait]
 impl UserRepository for ClickHouseUserRepo {
-    async fn get_user_by_auth_info(
+    async fn get_user_with_pswd_hash(
         &self,
         email: &str,
-        password: &str,
-    ) -> Result<Option<User>, DataAccessError> {
-        log::info!("ClickHouse: Getting user by auth info: {}", email);
+    ) -> Result<Option<(User, String)>, DataAccessError> {
+        log::info!("ClickHouse: Getting user with password hash: {}", email);
 
-        let query = "SELECT * FROM AppUser WHERE login = ? AND password = ? LIMIT 1";
-
-        let row = self
-            .client
-            .query(&query)
-            .bind(email)
-            .bind(password)
-            .fetch_all::<ClickHouseUserRow>()
-            .await
-            .map_err(|e| {
-                log::error!("ClickHouse query failed: {}", e);
-                DataAccessError::ClickHouseBaseError(e)
-            })?;
+        let row = self.get_row_by_email(email).await?;
 
-        if row.len() == 1 {
-            log::info!("User authenticated successfully: {}", email);
-            Ok(Some(Self::form_row_to_user(&row[0])))
-        } else {
-            log::warn!("Authentication failed for user: {}", email);
-            Ok(None)
-        }
+        Ok(row.map(|row| (Self::form_row_to_user(&row), row.password)))
     }
 
     async fn get_user_by_passport(
@@ -209,8 +195,7 @@ impl UserRepository for ClickHouseUserRepo {
             log::error!("Invalid passport number format: {}", passport.number);
             DataAccessError::InvalidInput("Invalid passport number format".to_string())
         })?;
-        let query =
-            "SELECT * FROM AppUser WHERE passport_serial = $1 AND passport_num = $2 LIMIT 1";
+        let query = "SELECT * FROM AppUser WHERE passport_serial = ? AND passport_num = ? LIMIT 1";
         log::debug!("Executing query: {}", query);
 
         let row = self
@@ -251,8 +236,8 @@ impl UserRepository for ClickHouseUserRepo {
         }
     }
 
-    async fn insert_user(&self, user: &User, password: &str) -> Result<(), DataAccessError> {
-        self.insert_user_with_id(self.gen_id().await?, user, password)
+    async fn insert_user(&self, user: &User, pswd_hash: &str) -> Result<(), DataAccessError> {
+        self.insert_user_with_id(self.gen_id().await?, user, pswd_hash)
             .await
     }
 
@@ -301,6 +286,29 @@ impl UserRepository for ClickHouseUserRepo {
 
         Ok(())
     }
+
+    async fn update_user_pswd_hash(
+        &self,
+        email: &str,
+        pswd_hash: &str,
+    ) -> Result<(), DataAccessError> {
+        log::info!("ClickHouse: Updating password hash for user: {}", email);
+
+        let query = "ALTER TABLE AppUser UPDATE password = ? WHERE login = ?";
+
+        self.client
+            .query(query)
+            .bind(pswd_hash)
+            .bind(email)
+            .execute()
+            .await
+            .map_err(|e| {
+                log::error!("ClickHouse update failed: {}", e);
+                DataAccessError::ClickHouseBaseError(e)
+            })?;
+
+        Ok(())
+    }
 }
 
 impl ClickHouseUserRepo {
@@ -1,26 +1,44 @@
 use super::error::DataAccessError;
 use super::repositories_traits;
-use models::{Camera, Car, Document, Location, Role, Snap, TrackInfo, User};
+use super::snap_datetime;
+use chrono::NaiveDateTime;
+use models::{
+    BoundingBox, Camera, Car, CarFilter, DetailedCar, Document, Location, NearbyCamera,
+    OwnershipRecord, Page, Paged, RoadSection, Role, Snap, SpeedViolation, TrackInfo, User,
+    ViolationSeverity, WatchlistAlert, WatchlistEntry,
+};
 
 pub struct MockUserRepo;
 
+// Argon2id-хэш строки "password"
+pub const MOCK_PSWD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$w8vCiFuWzzDhQv/75LWsxQ$czHIaO9lexgpFWL58fUHlosyvS6gK6Fp2J02hHaHWkw";
+pub const MOCK_LEGACY_EMAIL: &str = "legacy@legacy.com";
+
 #[allow(unused_variables)]
 #[async_trait::async_trait]
 impl repositories_traits::UserRepository for MockUserRepo {
-    async fn get_user_by_auth_info(
+    async fn get_user_with_pswd_hash(
         &self,
         email: &str,
-        pswd: &str,
-    ) -> Result<Option<User>, DataAccessError> {
-        Ok(Some(User {
-            name: "mock_name".to_string(),
-            surname: "mock_surname".to_string(),
-            lastname: None,
-            email: email.to_string(),
-            passport: None,
-            role: Role::user,
-            is_verified: false,
-        }))
+    ) -> Result<Option<(User, String)>, DataAccessError> {
+        let stored_pswd = if email == MOCK_LEGACY_EMAIL {
+            "password"
+        } else {
+            MOCK_PSWD_HASH
+        };
+
+        Ok(Some((
+            User {
+                name: "mock_name".to_string(),
+                surname: "mock_surname".to_string(),
+                lastname: None,
+                email: email.to_string(),
+                passport: None,
+                role: Role::user,
+                is_verified: false,
+            },
+            stored_pswd.to_string(),
+        )))
     }
     async fn get_user_by_passport(
         &self,
@@ -43,7 +61,7 @@ impl repositories_traits::UserRepository for MockUserRepo {
             Ok(None)
         }
     }
-    async fn insert_user(&self, user: &User, pswd: &str) -> Result<(), DataAccessError> {
+    async fn insert_user(&self, user: &User, pswd_hash: &str) -> Result<(), DataAccessError> {
         Ok(())
     }
     async fn update_user_passport(
@@ -53,22 +71,111 @@ impl repositories_traits::UserRepository for MockUserRepo {
     ) -> Result<(), DataAccessError> {
         Ok(())
     }
+    async fn update_user_pswd_hash(
+        &self,
+        email: &str,
+        pswd_hash: &str,
+    ) -> Result<(), DataAccessError> {
+        Ok(())
+    }
+}
+
+pub struct MockSessionRepo;
+
+pub const MOCK_REFRESH_TOKEN: &str = "mock_refresh_token";
+
+#[allow(unused_variables)]
+#[async_trait::async_trait]
+impl repositories_traits::SessionRepository for MockSessionRepo {
+    async fn insert_session(
+        &self,
+        refresh_token: &str,
+        email: &str,
+        ttl_secs: u64,
+    ) -> Result<(), DataAccessError> {
+        Ok(())
+    }
+    async fn take_session(&self, refresh_token: &str) -> Result<Option<String>, DataAccessError> {
+        if refresh_token == MOCK_REFRESH_TOKEN {
+            Ok(Some("exist@exist.com".to_string()))
+        } else {
+            Ok(None)
+        }
+    }
+    async fn delete_session(&self, refresh_token: &str) -> Result<(), DataAccessError> {
+        Ok(())
+    }
 }
 
 pub struct MockCarRepo;
 
+// История владения есть только у этого автомобиля
+pub const MOCK_CAR_VIN: &str = "XTA210990Y2765432";
+pub const MOCK_CAR_GOS_NUM: &str = "А777МР77";
+
+fn mock_detailed_car() -> DetailedCar {
+    DetailedCar {
+        car: Car {
+            owner_fio: ("Петров".to_string(), "Петр".to_string(), None),
+            gos_num: MOCK_CAR_GOS_NUM.to_string(),
+            model: "2109".to_string(),
+            mark: "LADA".to_string(),
+            color: "white".to_string(),
+            year: 2015,
+            vin: MOCK_CAR_VIN.to_string(),
+            sts: Document {
+                serial: "7701".to_string(),
+                number: "123456".to_string(),
+            },
+            pts: Document {
+                serial: "6301".to_string(),
+                number: "654321".to_string(),
+            },
+        },
+        mileage: 120000,
+        horse_power: Some(70),
+        car_weight: Some(945),
+        engine_type: "petrol".to_string(),
+        car_class: "B".to_string(),
+        release_date: "15.03.2015".to_string(),
+        reg_date: "21.06.2019".to_string(),
+        import_country: "Германия".to_string(),
+    }
+}
+
+fn mock_owner_history() -> Vec<OwnershipRecord> {
+    vec![
+        OwnershipRecord {
+            owner_fio: (
+                "Иванов".to_string(),
+                "Иван".to_string(),
+                Some("Иванович".to_string()),
+            ),
+            reg_date: "15.03.2015".to_string(),
+            dereg_date: Some("20.06.2019".to_string()),
+            mileage: 0,
+        },
+        OwnershipRecord {
+            owner_fio: ("Петров".to_string(), "Петр".to_string(), None),
+            reg_date: "21.06.2019".to_string(),
+            dereg_date: None,
+            mileage: 84000,
+        },
+    ]
+}
+
 #[async_trait::async_trait]
 #[allow(unused_variables)]
 impl repositories_traits::CarRepository for MockCarRepo {
     async fn get_cars_by_filters(
         &self,
-        firstname: Option<&str>,
-        surname: Option<&str>,
-        lastname: Option<&str>,
-        passport: Option<Document>,
-        gos_num_mask: Option<&str>,
-    ) -> Result<Vec<Car>, DataAccessError> {
-        Ok(vec![])
+        filter: &CarFilter,
+        page: &Page,
+    ) -> Result<Paged<Car>, DataAccessError> {
+        Ok(Paged {
+            items: vec![],
+            total: 0,
+        })
     }
     async fn get_car_by_gos_number_mask(
         &self,
@@ -90,6 +197,34 @@ impl repositories_traits::CarRepository for MockCarRepo {
     ) -> Result<Vec<Car>, DataAccessError> {
         Ok(vec![])
     }
+    async fn get_detailed_cars_by_vins(
+        &self,
+        vins: &[String],
+    ) -> Result<Vec<DetailedCar>, DataAccessError> {
+        Ok(vins
+            .iter()
+            .filter(|vin| vin.as_str() == MOCK_CAR_VIN)
+            .map(|_| mock_detailed_car())
+            .collect())
+    }
+    async fn get_owner_history_by_vin(
+        &self,
+        vin: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        if vin != MOCK_CAR_VIN {
+            return Err(DataAccessError::NotFoundError("car".to_string()));
+        }
+        Ok(mock_owner_history())
+    }
+    async fn get_owner_history_by_gos_num(
+        &self,
+        gos_num: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        if gos_num != MOCK_CAR_GOS_NUM {
+            return Err(DataAccessError::NotFoundError("car".to_string()));
+        }
+        Ok(mock_owner_history())
+    }
 }
 
 pub struct MockTrackInfoRepo;
@@ -113,8 +248,12 @@ impl repositories_traits::TrackInfoRepository for MockTrackInfoRepo {
         passport: Option<Document>,
         gos_num_mask: Option<&str>,
         date: Option<&str>,
-    ) -> Result<Vec<TrackInfo>, DataAccessError> {
-        Ok(vec![])
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, DataAccessError> {
+        Ok(Paged {
+            items: vec![],
+            total: 0,
+        })
     }
     async fn get_track_info_by_date(&self, date: &str) -> Result<Vec<TrackInfo>, DataAccessError> {
         Ok(vec![])
@@ -146,8 +285,11 @@ pub struct MockSnapRepo;
 #[async_trait::async_trait]
 #[allow(unused_variables)]
 impl repositories_traits::SnapRepository for MockSnapRepo {
-    async fn insert_snap(&self, snap: &Snap) -> Result<(), DataAccessError> {
-        Ok(())
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
+        Ok(true)
+    }
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(snaps.to_vec())
     }
     async fn get_car_snaps_by_date(
         &self,
@@ -165,9 +307,13 @@ impl repositories_traits::SnapRepository for MockSnapRepo {
                         latitude: 55.75222,
                         longitude: 37.61556,
                     },
+                    speed_limit: Some(60),
+                    install_date: Some("04.07.2021".to_string()),
+                    is_active: true,
                 },
                 date: date.to_string(),
                 time: "8:10".to_string(),
+                idempotency_key: None,
             },
             Snap {
                 speed: Some(70),
@@ -179,9 +325,13 @@ impl repositories_traits::SnapRepository for MockSnapRepo {
                         latitude: 77.75222,
                         longitude: 47.61556,
                     },
+                    speed_limit: Some(60),
+                    install_date: Some("04.07.2021".to_string()),
+                    is_active: true,
                 },
                 date: date.to_string(),
                 time: "9:15".to_string(),
+                idempotency_key: None,
             },
             Snap {
                 speed: Some(70),
@@ -193,39 +343,114 @@ impl repositories_traits::SnapRepository for MockSnapRepo {
                         latitude: 55.75222,
                         longitude: 37.61556,
                     },
+                    speed_limit: Some(60),
+                    install_date: Some("04.07.2021".to_string()),
+                    is_active: true,
                 },
                 date: date.to_string(),
                 time: "10:30".to_string(),
+                idempotency_key: None,
             },
         ])
     }
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        let mut snaps = Vec::new();
+        for day in from.date().iter_days().take_while(|day| *day <= to.date()) {
+            let date = day.format("%d.%m.%Y").to_string();
+            snaps.extend(self.get_car_snaps_by_date(gos_number, &date).await?);
+        }
+        snaps.retain(|snap| {
+            snap_datetime(snap).is_some_and(|datetime| *from <= datetime && datetime <= *to)
+        });
+        Ok(snaps)
+    }
 }
 
 pub struct MockCameraRepo;
 
+// Камеры 1..=10, девятая без радара, последняя выведена из эксплуатации
+pub const MOCK_CAMERAS_CNT: usize = 10;
+pub const MOCK_NO_RADAR_CAMERA_ID: usize = 9;
+pub const MOCK_DECOMMISSIONED_CAMERA_ID: usize = 10;
+// Чтение этой камеры завершается ошибкой хранилища
+pub const MOCK_UNAVAILABLE_CAMERA_ID: usize = 500;
+
 #[allow(unused_variables)]
 #[async_trait::async_trait]
 impl repositories_traits::CameraRepository for MockCameraRepo {
     async fn get_camera_count(&self) -> Result<usize, DataAccessError> {
-        Ok(10)
+        Ok(MOCK_CAMERAS_CNT)
     }
     async fn get_camera_by_id(&self, id: usize) -> Result<Camera, DataAccessError> {
+        if id == MOCK_UNAVAILABLE_CAMERA_ID {
+            return Err(DataAccessError::ReconnectionError);
+        }
+        if !(1..=MOCK_CAMERAS_CNT).contains(&id) {
+            return Err(DataAccessError::NotFoundError("camera".to_string()));
+        }
         Ok(Camera {
             id,
-            is_radar: true,
+            is_radar: id != MOCK_NO_RADAR_CAMERA_ID,
             location: Location {
                 latitude: 55.75222,
                 longitude: 37.61556,
             },
+            speed_limit: Some(60),
+            install_date: Some("04.07.2021".to_string()),
+            is_active: id != MOCK_DECOMMISSIONED_CAMERA_ID,
         })
     }
     async fn get_camera_by_location(&self, location: &Location) -> Result<Camera, DataAccessError> {
         Ok(Camera {
-            id: 1,
-            is_radar: true,
-            location: location.clone(),
+            location: *location,
+            ..self.get_camera_by_id(1).await?
         })
     }
+    async fn find_nearest_cameras(
+        &self,
+        location: &Location,
+        k: usize,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError> {
+        let mut cameras = self.find_cameras_within(location, 1000.).await?;
+        cameras.truncate(k);
+        Ok(cameras)
+    }
+    async fn find_cameras_within(
+        &self,
+        location: &Location,
+        radius_m: f64,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError> {
+        Ok(vec![
+            NearbyCamera {
+                camera: self.get_camera_by_id(1).await?,
+                distance: 120.,
+            },
+            NearbyCamera {
+                camera: self.get_camera_by_id(2).await?,
+                distance: 480.,
+            },
+        ])
+    }
+    async fn find_cameras_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<Camera>, DataAccessError> {
+        Ok(vec![
+            Camera {
+                location: bbox.south_west,
+                ..self.get_camera_by_id(1).await?
+            },
+            Camera {
+                location: bbox.north_east,
+                ..self.get_camera_by_id(2).await?
+            },
+        ])
+    }
     async fn get_avg_speed_for_car_at_camera(
         &self,
         gos_num: &str,
@@ -233,4 +458,132 @@ impl repositories_traits::CameraRepository for MockCameraRepo {
     ) -> Result<f64, DataAccessError> {
         Ok(70.)
     }
+    async fn insert_camera(&self, camera: &Camera) -> Result<usize, DataAccessError> {
+        Ok(MOCK_CAMERAS_CNT + 1)
+    }
+    async fn update_camera(&self, camera: &Camera) -> Result<(), DataAccessError> {
+        self.get_camera_by_id(camera.id).await.map(|_| ())
+    }
+    async fn decommission_camera(&self, id: usize) -> Result<(), DataAccessError> {
+        self.get_camera_by_id(id).await.map(|_| ())
+    }
+}
+
+pub struct MockWatchlistRepo;
+
+#[allow(unused_variables)]
+#[async_trait::async_trait]
+impl repositories_traits::WatchlistRepository for MockWatchlistRepo {
+    async fn insert_entry(&self, entry: &WatchlistEntry) -> Result<usize, DataAccessError> {
+        Ok(2)
+    }
+    async fn update_entry(&self, entry: &WatchlistEntry) -> Result<(), DataAccessError> {
+        if entry.id == 1 {
+            Ok(())
+        } else {
+            Err(DataAccessError::NotFoundError(
+                "watchlist entry".to_string(),
+            ))
+        }
+    }
+    async fn delete_entry(&self, id: usize) -> Result<(), DataAccessError> {
+        if id == 1 {
+            Ok(())
+        } else {
+            Err(DataAccessError::NotFoundError(
+                "watchlist entry".to_string(),
+            ))
+        }
+    }
+    async fn get_entry(&self, id: usize) -> Result<Option<WatchlistEntry>, DataAccessError> {
+        Ok(self.get_entries().await?.into_iter().find(|e| e.id == id))
+    }
+    async fn get_entries(&self) -> Result<Vec<WatchlistEntry>, DataAccessError> {
+        Ok(vec![WatchlistEntry {
+            id: 1,
+            gos_num_mask: "А777МР77".to_string(),
+            reason: "Угон".to_string(),
+            expires_at: None,
+            created_by: "exist@exist.com".to_string(),
+        }])
+    }
+    async fn insert_alert(&self, alert: &WatchlistAlert) -> Result<Option<usize>, DataAccessError> {
+        Ok(Some(1))
+    }
+    async fn get_alerts_by_date(&self, date: &str) -> Result<Vec<WatchlistAlert>, DataAccessError> {
+        Ok(vec![WatchlistAlert {
+            id: 1,
+            entry_id: 1,
+            reason: "Угон".to_string(),
+            gos_num: "А777МР77".to_string(),
+            camera_id: 1,
+            cords: Location {
+                latitude: 55.75222,
+                longitude: 37.61556,
+            },
+            date: date.to_string(),
+            time: "8:10".to_string(),
+        }])
+    }
+}
+
+pub struct MockSpeedViolationRepo;
+
+#[allow(unused_variables)]
+#[async_trait::async_trait]
+impl repositories_traits::SpeedViolationRepository for MockSpeedViolationRepo {
+    async fn insert_violation(
+        &self,
+        violation: &SpeedViolation,
+    ) -> Result<Option<usize>, DataAccessError> {
+        Ok(Some(1))
+    }
+    async fn get_violations_by_filters(
+        &self,
+        gos_num: Option<&str>,
+        camera_id: Option<usize>,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<SpeedViolation>, DataAccessError> {
+        Ok(vec![SpeedViolation {
+            id: 1,
+            gos_num: gos_num.unwrap_or("А777МР77").to_string(),
+            camera_id: camera_id.unwrap_or(1),
+            cords: Location {
+                latitude: 55.75222,
+                longitude: 37.61556,
+            },
+            date: from.format("%d.%m.%Y").to_string(),
+            time: "8:10".to_string(),
+            speed: 95,
+            speed_limit: 60,
+            severity: ViolationSeverity::low,
+            section_id: None,
+        }])
+    }
+}
+
+pub struct MockRoadSectionRepo;
+
+#[allow(unused_variables)]
+#[async_trait::async_trait]
+impl repositories_traits::RoadSectionRepository for MockRoadSectionRepo {
+    async fn insert_section(
+        &self,
+        section: &RoadSection,
+    ) -> Result<Option<usize>, DataAccessError> {
+        Ok(Some(1))
+    }
+    async fn delete_section(&self, id: usize) -> Result<(), DataAccessError> {
+        Ok(())
+    }
+    async fn get_sections(&self) -> Result<Vec<RoadSection>, DataAccessError> {
+        Ok(vec![RoadSection {
+            id: 1,
+            start_camera_id: 1,
+            end_camera_id: 2,
+            distance: 10000,
+            speed_limit: 60,
+        }])
+    }
 }
@@ -7,3 +7,57 @@ pub mod tandem;
 
 use super::error;
 use super::repositories_traits;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use error::DataAccessError;
+use models::{BoundingBox, Camera, Location, Snap};
+
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+// Границы суток для выборки снимков за одну дату
+pub(crate) fn day_period(date: &str) -> Result<(NaiveDateTime, NaiveDateTime), DataAccessError> {
+    let date = NaiveDate::parse_from_str(date, "%d.%m.%Y").map_err(|e| {
+        log::error!("Invalid date format: {}", e);
+        DataAccessError::InvalidInput(e.to_string())
+    })?;
+
+    Ok((
+        date.and_hms_opt(0, 0, 0).unwrap(),
+        date.and_hms_opt(23, 59, 59).unwrap(),
+    ))
+}
+
+// Дата установки камеры: без нее при добавлении берется текущая дата
+pub(crate) fn install_date(camera: &Camera) -> Result<Option<NaiveDate>, DataAccessError> {
+    camera
+        .install_date
+        .as_deref()
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%d.%m.%Y").map_err(|e| {
+                log::error!("Invalid install date format: {}", e);
+                DataAccessError::InvalidInput(e.to_string())
+            })
+        })
+        .transpose()
+}
+
+pub(crate) fn snap_datetime(snap: &Snap) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(&format!("{} {}", snap.date, snap.time), "%d.%m.%Y %H:%M").ok()
+}
+
+// Область, описанная вокруг круга, для предварительного отбора камер по координатам
+pub(crate) fn radius_bbox(location: &Location, radius_m: f64) -> BoundingBox {
+    let d_lat = radius_m / METERS_PER_DEGREE;
+    let d_lon = radius_m / (METERS_PER_DEGREE * location.latitude.to_radians().cos().max(0.01));
+
+    BoundingBox {
+        south_west: Location {
+            longitude: location.longitude - d_lon,
+            latitude: location.latitude - d_lat,
+        },
+        north_east: Location {
+            longitude: location.longitude + d_lon,
+            latitude: location.latitude + d_lat,
+        },
+    }
+}
@@ -1,12 +1,21 @@
+use super::super::{install_date, radius_bbox};
 use crate::error::DataAccessError;
 use crate::repositories_traits::CameraRepository;
 use async_trait::async_trait;
-use models::{Camera, Location};
+use chrono::NaiveDate;
+use models::{BoundingBox, Camera, Location, NearbyCamera};
 use sqlx::{
     postgres::{PgPoolOptions, PgRow},
     PgPool, Row,
 };
 
+// Расстояние (м) от точки ($1 - долгота, $2 - широта) до камеры по формуле гаверсинусов
+const DISTANCE_SQL: &str = "
+    6371000 * 2 * ASIN(SQRT(
+        POWER(SIN(RADIANS(latitude - $2) / 2), 2)
+        + COS(RADIANS($2)) * COS(RADIANS(latitude)) * POWER(SIN(RADIANS(longitude - $1) / 2), 2)
+    ))";
+
 pub struct PgCameraRepo {
     pool: PgPool,
 }
@@ -27,9 +36,21 @@ impl PgCameraRepo {
         log::info!("Successfully connected to PostgreSQL");
         Ok(PgCameraRepo { pool })
     }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgCameraRepo on shared PostgreSQL pool");
+        PgCameraRepo { pool }
+    }
 }
 
 impl PgCameraRepo {
+    fn from_row_to_nearby_camera(row: PgRow) -> NearbyCamera {
+        NearbyCamera {
+            distance: row.get("distance"),
+            camera: Self::from_row_to_camera(row),
+        }
+    }
+
     fn from_row_to_camera(row: PgRow) -> Camera {
         Camera {
             id: row.get::<i32, _>("id") as usize,
@@ -38,6 +59,15 @@ impl PgCameraRepo {
                 longitude: row.get("longitude"),
                 latitude: row.get("latitude"),
             },
+            speed_limit: row
+                .get::<Option<i32>, _>("speed_limit")
+                .and_then(|v| u16::try_from(v).ok()),
+            install_date: Some(
+                row.get::<NaiveDate, _>("install_date")
+                    .format("%d.%m.%Y")
+                    .to_string(),
+            ),
+            is_active: row.get("is_active"),
         }
     }
 }
@@ -64,7 +94,8 @@ impl CameraRepository for PgCameraRepo {
 
     async fn get_camera_by_id(&self, id: usize) -> Result<Camera, DataAccessError> {
         log::info!("Getting camera by ID: {}", id);
-        let query = "SELECT id, is_radar, longitude, latitude FROM Camera WHERE id = $1";
+        let query =
+            "SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active FROM Camera WHERE id = $1";
         log::debug!("Executing query: {} with ID: {}", query, id);
 
         let row = sqlx::query(query)
@@ -92,7 +123,7 @@ impl CameraRepository for PgCameraRepo {
     async fn get_camera_by_location(&self, location: &Location) -> Result<Camera, DataAccessError> {
         log::info!("Getting camera with location: {:#?}", location);
         let query = "
-            SELECT id, is_radar, longitude, latitude 
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active
             FROM Camera 
             WHERE longitude = $1 AND latitude = $2";
         log::debug!("Executing query: {} with location: {:?}", query, location);
@@ -120,6 +151,113 @@ impl CameraRepository for PgCameraRepo {
         }
     }
 
+    async fn find_nearest_cameras(
+        &self,
+        location: &Location,
+        k: usize,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError> {
+        log::info!("Getting {} nearest cameras to location: {:?}", k, location);
+        let query = format!(
+            "
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active, {DISTANCE_SQL} AS distance
+            FROM Camera
+            ORDER BY distance, id
+            LIMIT $3"
+        );
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(&query)
+            .bind(location.longitude)
+            .bind(location.latitude)
+            .bind(k as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get nearest cameras: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} nearest cameras", rows.len());
+        Ok(rows
+            .into_iter()
+            .map(Self::from_row_to_nearby_camera)
+            .collect())
+    }
+
+    async fn find_cameras_within(
+        &self,
+        location: &Location,
+        radius_m: f64,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError> {
+        log::info!(
+            "Getting cameras within {} m of location: {:?}",
+            radius_m,
+            location
+        );
+        // Диапазоны координат отсекают камеры по индексу до расчета расстояния
+        let bbox = radius_bbox(location, radius_m);
+        let query = format!(
+            "
+            SELECT * FROM (
+                SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active, {DISTANCE_SQL} AS distance
+                FROM Camera
+                WHERE latitude BETWEEN $3 AND $4 AND longitude BETWEEN $5 AND $6
+            ) c
+            WHERE distance <= $7
+            ORDER BY distance, id"
+        );
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(&query)
+            .bind(location.longitude)
+            .bind(location.latitude)
+            .bind(bbox.south_west.latitude)
+            .bind(bbox.north_east.latitude)
+            .bind(bbox.south_west.longitude)
+            .bind(bbox.north_east.longitude)
+            .bind(radius_m)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get cameras within radius: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} cameras within radius", rows.len());
+        Ok(rows
+            .into_iter()
+            .map(Self::from_row_to_nearby_camera)
+            .collect())
+    }
+
+    async fn find_cameras_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<Camera>, DataAccessError> {
+        log::info!("Getting cameras in bounding box: {:?}", bbox);
+        let query = "
+            SELECT id, is_radar, longitude, latitude, speed_limit, install_date, is_active
+            FROM Camera
+            WHERE latitude BETWEEN $1 AND $2 AND longitude BETWEEN $3 AND $4
+            ORDER BY id";
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(query)
+            .bind(bbox.south_west.latitude)
+            .bind(bbox.north_east.latitude)
+            .bind(bbox.south_west.longitude)
+            .bind(bbox.north_east.longitude)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get cameras in bounding box: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} cameras in bounding box", rows.len());
+        Ok(rows.into_iter().map(Self::from_row_to_camera).collect())
+    }
+
     async fn get_avg_speed_for_car_at_camera(
         &self,
         gos_num: &str,
@@ -151,4 +289,93 @@ impl CameraRepository for PgCameraRepo {
         log::info!("Getted avg speed {:?}", avg_speed);
         Ok(avg_speed.unwrap_or(0.))
     }
+
+    async fn insert_camera(&self, camera: &Camera) -> Result<usize, DataAccessError> {
+        log::info!("Inserting camera at location: {:?}", camera.location);
+        let query = "
+            INSERT INTO Camera (longitude, latitude, install_date, is_radar, speed_limit, is_active)
+            VALUES ($1, $2, COALESCE($3, CURRENT_DATE), $4, $5, $6)
+            RETURNING id
+        ";
+        log::debug!("Executing insert query: {}", query);
+
+        let row = sqlx::query(query)
+            .bind(camera.location.longitude)
+            .bind(camera.location.latitude)
+            .bind(install_date(camera)?)
+            .bind(camera.is_radar)
+            .bind(camera.speed_limit.map(|v| v as i32))
+            .bind(camera.is_active)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to insert camera: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        let id = row.get::<i32, _>("id") as usize;
+        log::info!("Successfully inserted camera {}", id);
+        Ok(id)
+    }
+
+    async fn update_camera(&self, camera: &Camera) -> Result<(), DataAccessError> {
+        log::info!("Updating camera {}", camera.id);
+        let query = "
+            UPDATE Camera
+            SET longitude = $2,
+                latitude = $3,
+                install_date = COALESCE($4, install_date),
+                is_radar = $5,
+                speed_limit = $6,
+                is_active = $7
+            WHERE id = $1
+        ";
+        log::debug!("Executing update query: {}", query);
+
+        let updated = sqlx::query(query)
+            .bind(camera.id as i32)
+            .bind(camera.location.longitude)
+            .bind(camera.location.latitude)
+            .bind(install_date(camera)?)
+            .bind(camera.is_radar)
+            .bind(camera.speed_limit.map(|v| v as i32))
+            .bind(camera.is_active)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to update camera: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?
+            .rows_affected();
+
+        if updated == 0 {
+            log::warn!("Camera {} not found", camera.id);
+            return Err(DataAccessError::NotFoundError("camera".to_string()));
+        }
+        log::info!("Successfully updated camera");
+        Ok(())
+    }
+
+    async fn decommission_camera(&self, id: usize) -> Result<(), DataAccessError> {
+        log::info!("Decommissioning camera {}", id);
+        let query = "UPDATE Camera SET is_active = FALSE WHERE id = $1";
+        log::debug!("Executing query: {} with ID: {}", query, id);
+
+        let updated = sqlx::query(query)
+            .bind(id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to decommission camera: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?
+            .rows_affected();
+
+        if updated == 0 {
+            log::warn!("Camera {} not found", id);
+            return Err(DataAccessError::NotFoundError("camera".to_string()));
+        }
+        log::info!("Successfully decommissioned camera");
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@ use crate::error::DataAccessError;
 use crate::repositories_traits::CarRepository;
 use async_trait::async_trait;
 use chrono::{Datelike, NaiveDate};
-use models::{Car, Document};
+use models::{Car, CarFilter, DetailedCar, Document, OwnershipRecord, Page, Paged, SortKey};
 use sqlx::{postgres::PgPoolOptions, PgPool, QueryBuilder, Row};
 
 pub struct PgCarRepo {
@@ -25,6 +25,11 @@ impl PgCarRepo {
         log::info!("Successfully connected to PostgreSQL");
         Ok(PgCarRepo { pool })
     }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgCarRepo on shared PostgreSQL pool");
+        PgCarRepo { pool }
+    }
 }
 
 impl PgCarRepo {
@@ -64,58 +69,196 @@ impl PgCarRepo {
             .to_string()
     }
 
+    fn row_to_car(row: &sqlx::postgres::PgRow) -> Car {
+        Car {
+            gos_num: row.get("gos_num"),
+            model: row.get("model"),
+            owner_fio: (row.get("surname"), row.get("name"), row.get("lastname")),
+            mark: row.get("mark"),
+            color: row.get("color"),
+            year: row.get::<NaiveDate, _>("release_date").year() as u16,
+            vin: row.get("vin"),
+            sts: Document {
+                serial: format!("{:0>4}", row.get::<i32, _>("sts_serial").to_string()),
+                number: format!("{:0>6}", row.get::<i32, _>("sts_num").to_string()),
+            },
+            pts: Document {
+                serial: format!("{:0>4}", row.get::<i32, _>("pts_serial").to_string()),
+                number: format!("{:0>6}", row.get::<i32, _>("pts_number").to_string()),
+            },
+        }
+    }
+
     fn form_rows_to_cars(rows: &[sqlx::postgres::PgRow]) -> Vec<Car> {
         log::debug!("Converting {} database rows to Car objects", rows.len());
         rows.into_iter()
             .map(|row| {
-                let car = Car {
-                    gos_num: row.get("gos_num"),
-                    model: row.get("model"),
-                    owner_fio: (row.get("surname"), row.get("name"), row.get("lastname")),
-                    mark: row.get("mark"),
-                    color: row.get("color"),
-                    year: row.get::<NaiveDate, _>("release_date").year() as u16,
-                    vin: row.get("vin"),
-                    sts: Document {
-                        serial: format!("{:0>4}", row.get::<i32, _>("sts_serial").to_string()),
-                        number: format!("{:0>6}", row.get::<i32, _>("sts_num").to_string()),
-                    },
-                    pts: Document {
-                        serial: format!("{:0>4}", row.get::<i32, _>("pts_serial").to_string()),
-                        number: format!("{:0>6}", row.get::<i32, _>("pts_number").to_string()),
-                    },
-                };
+                let car = Self::row_to_car(row);
                 log::debug!("Converted car: {:?}", car);
                 car
             })
             .collect()
     }
+
+    fn detailed_tables_query() -> String {
+        log::debug!("Generating joined tables query for detailed cars");
+        "SELECT 
+            o.name, 
+            o.surname, 
+            o.lastname, 
+            s.gos_num, 
+            s.model, 
+            s.mark, 
+            c.color, 
+            c.mileage, 
+            s.release_date, 
+            s.reg_date, 
+            s.vin, 
+            s.horse_power, 
+            s.car_weight, 
+            s.engine_type, 
+            s.car_class, 
+            s.sts_serial, 
+            s.sts_num, 
+            p.pts_serial, 
+            p.pts_number, 
+            p.import_country 
+         FROM CarOwner o
+         JOIN Car c ON o.id = c.owner_id
+         JOIN STS s ON c.id = s.car_id
+         JOIN PTS p ON c.id = p.id"
+            .to_string()
+    }
+
+    fn row_to_detailed_car(row: &sqlx::postgres::PgRow) -> DetailedCar {
+        DetailedCar {
+            car: Self::row_to_car(row),
+            mileage: row.get::<i32, _>("mileage") as u32,
+            horse_power: row.get::<Option<i32>, _>("horse_power").map(|hp| hp as u32),
+            car_weight: row.get::<Option<i32>, _>("car_weight").map(|w| w as u32),
+            engine_type: row.get("engine_type"),
+            car_class: row.get("car_class"),
+            release_date: row
+                .get::<NaiveDate, _>("release_date")
+                .format("%d.%m.%Y")
+                .to_string(),
+            reg_date: row
+                .get::<NaiveDate, _>("reg_date")
+                .format("%d.%m.%Y")
+                .to_string(),
+            import_country: row.get("import_country"),
+        }
+    }
+}
+
+impl PgCarRepo {
+    // column — столбец STS, по которому ищется автомобиль
+    async fn get_owner_history(
+        &self,
+        column: &str,
+        value: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        let query = format!(
+            "SELECT 
+                o.name, 
+                o.surname, 
+                o.lastname, 
+                h.mileage, 
+                h.reg_date, 
+                h.dereg_date 
+             FROM STS s
+             JOIN PTS p ON s.id = p.sts_id
+             JOIN OwnerHistory h ON p.id = h.pts_id
+             JOIN OwnerHistoryOwner ho ON h.id = ho.owner_history_id
+             JOIN CarOwner o ON ho.owner_id = o.id
+             WHERE s.{column} = $1
+             ORDER BY h.reg_date, h.id"
+        );
+        log::debug!("Executing query: {} with param: {}", query, value);
+
+        let rows = sqlx::query(&query)
+            .bind(value)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Owner history query failed for {} {}: {}", column, value, e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        if rows.is_empty() {
+            let query = format!("SELECT EXISTS(SELECT 1 FROM STS WHERE {column} = $1)");
+            let exists: bool = sqlx::query_scalar(&query)
+                .bind(value)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| {
+                    log::error!("Car lookup failed for {} {}: {}", column, value, e);
+                    DataAccessError::PsqlDataBaseError(e)
+                })?;
+            if !exists {
+                log::warn!("Car with {} {} not found", column, value);
+                return Err(DataAccessError::NotFoundError("car".to_string()));
+            }
+        }
+
+        log::info!(
+            "Found {} owner history records for {} {}",
+            rows.len(),
+            column,
+            value
+        );
+        Ok(rows
+            .iter()
+            .map(|row| OwnershipRecord {
+                owner_fio: (row.get("surname"), row.get("name"), row.get("lastname")),
+                reg_date: row
+                    .get::<NaiveDate, _>("reg_date")
+                    .format("%d.%m.%Y")
+                    .to_string(),
+                dereg_date: row
+                    .get::<Option<NaiveDate>, _>("dereg_date")
+                    .map(|date| date.format("%d.%m.%Y").to_string()),
+                mileage: row.get::<i32, _>("mileage") as u32,
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
 impl CarRepository for PgCarRepo {
     async fn get_cars_by_filters(
         &self,
-        firstname: Option<&str>,
-        surname: Option<&str>,
-        lastname: Option<&str>,
-        passport: Option<Document>,
-        gos_num_mask: Option<&str>,
-    ) -> Result<Vec<Car>, DataAccessError> {
-        let transformed_gos_num = gos_num_mask.map(|gsn| Self::transform_mask_for_psql_like(gsn));
+        filter: &CarFilter,
+        page: &Page,
+    ) -> Result<Paged<Car>, DataAccessError> {
+        let transformed_gos_num = filter
+            .gos_num_mask
+            .as_deref()
+            .map(Self::transform_mask_for_psql_like);
         log::info!(
-            "Searching cars by filters: {:?} {:?} {:?} {:?} {:?}",
-            firstname,
-            surname,
-            lastname,
+            "Searching cars by filters: {:?} ({:?}) ({:?})",
+            filter,
             transformed_gos_num,
-            passport,
+            page,
         );
 
-        let query = "SELECT * FROM get_cars($1, $2, $3, $4, $5, $6)";
+        let sort = match page.sort {
+            SortKey::gos_num => "gos_num",
+            SortKey::owner_surname => "owner_surname",
+            SortKey::track_time => {
+                log::error!("Cars can't be sorted by track time");
+                return Err(DataAccessError::InvalidInput(
+                    "Invalid sort key for cars".to_string(),
+                ));
+            }
+        };
+
+        let query = "SELECT * FROM get_cars(
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17
+        )";
         log::debug!("Executing query: {}", query);
 
-        let pserial = match &passport {
+        let pserial = match &filter.passport {
             Some(psprt) => Some(psprt.serial.clone().parse::<i32>().map_err(|_| {
                 log::error!("Invalid passport serial format: {}", &psprt.serial);
                 DataAccessError::InvalidInput("Invalid passport serial format".to_string())
@@ -123,7 +266,7 @@ impl CarRepository for PgCarRepo {
             None => None,
         };
 
-        let pnumber = match &passport {
+        let pnumber = match &filter.passport {
             Some(psprt) => Some(psprt.number.clone().parse::<i32>().map_err(|_| {
                 log::error!("Invalid passport number format: {}", &psprt.number);
                 DataAccessError::InvalidInput("Invalid passport number format".to_string())
@@ -132,12 +275,23 @@ impl CarRepository for PgCarRepo {
         };
 
         let rows = sqlx::query(query)
-            .bind(firstname)
-            .bind(surname)
-            .bind(lastname)
-            .bind(transformed_gos_num)
+            .bind(&filter.firstname)
+            .bind(&filter.surname)
+            .bind(&filter.lastname)
+            .bind(&transformed_gos_num)
             .bind(pserial)
             .bind(pnumber)
+            .bind(&filter.vin)
+            .bind(&filter.mark)
+            .bind(&filter.model)
+            .bind(&filter.color)
+            .bind(filter.year_from.map(i32::from))
+            .bind(filter.year_to.map(i32::from))
+            .bind(&filter.engine_type)
+            .bind(&filter.car_class)
+            .bind(sort)
+            .bind(page.limit as i32)
+            .bind(page.offset as i32)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| {
@@ -145,8 +299,47 @@ impl CarRepository for PgCarRepo {
                 DataAccessError::PsqlDataBaseError(e)
             })?;
 
-        log::info!("Found {} cars matching filters", rows.len());
-        Ok(Self::form_rows_to_cars(&rows))
+        let total = match rows.first() {
+            Some(row) => row.get::<i64, _>("total_count") as usize,
+            None if page.offset == 0 => 0,
+            // Страница за пределами выборки: общее число считается отдельно
+            None => {
+                let query = "SELECT COUNT(*) FROM get_cars(
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14
+                )";
+                sqlx::query_scalar::<_, i64>(query)
+                    .bind(&filter.firstname)
+                    .bind(&filter.surname)
+                    .bind(&filter.lastname)
+                    .bind(&transformed_gos_num)
+                    .bind(pserial)
+                    .bind(pnumber)
+                    .bind(&filter.vin)
+                    .bind(&filter.mark)
+                    .bind(&filter.model)
+                    .bind(&filter.color)
+                    .bind(filter.year_from.map(i32::from))
+                    .bind(filter.year_to.map(i32::from))
+                    .bind(&filter.engine_type)
+                    .bind(&filter.car_class)
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(|e| {
+                        log::error!("Count query failed: {}", e);
+                        DataAccessError::PsqlDataBaseError(e)
+                    })? as usize
+            }
+        };
+
+        log::info!(
+            "Found {} cars matching filters, {} on page",
+            total,
+            rows.len()
+        );
+        Ok(Paged {
+            items: Self::form_rows_to_cars(&rows),
+            total,
+        })
     }
 
     async fn get_car_by_gos_number_mask(
@@ -192,36 +385,37 @@ impl CarRepository for PgCarRepo {
 
         let mut query_builder = QueryBuilder::new(Self::joined_tables_query());
 
-        if name.is_some() || surname.is_some() || last_name.is_some() {
-            query_builder.push(" WHERE ");
+        let fio: Vec<(&str, &str)> = [
+            ("o.name", name),
+            ("o.surname", surname),
+            ("o.lastname", last_name),
+        ]
+        .into_iter()
+        .filter_map(|(column, value)| value.map(|value| (column, value)))
+        .collect();
 
-            let mut needs_and = false;
-
-            if let Some(name) = name {
-                if needs_and {
-                    query_builder.push(" AND ");
-                }
-                query_builder.push("o.name = ");
-                query_builder.push_bind(name);
-                needs_and = true;
+        // Оценка совпадения считается один раз для кандидатов, отобранных по индексу
+        if !fio.is_empty() {
+            query_builder.push(" CROSS JOIN LATERAL (SELECT 0");
+            for (column, value) in &fio {
+                query_builder.push(format!(" + name_match_score({column}, "));
+                query_builder.push_bind(*value);
+                query_builder.push(")");
             }
+            query_builder.push(" OFFSET 0) AS ns(name_score)");
+        }
 
-            if let Some(surname) = surname {
-                if needs_and {
-                    query_builder.push(" AND ");
-                }
-                query_builder.push("o.surname = ");
-                query_builder.push_bind(surname);
-                needs_and = true;
-            }
+        // Имена сравниваются без учета регистра и различия ё/е, по началу или нечетко
+        query_builder.push(" WHERE 1 = 1");
+        for (column, value) in &fio {
+            query_builder.push(format!(" AND name_candidate({column}, "));
+            query_builder.push_bind(*value);
+            query_builder.push(")");
+        }
 
-            if let Some(last_name) = last_name {
-                if needs_and {
-                    query_builder.push(" AND ");
-                }
-                query_builder.push("o.lastname = ");
-                query_builder.push_bind(last_name);
-            }
+        // Сначала наиболее близкие по ФИО
+        if !fio.is_empty() {
+            query_builder.push(" AND ns.name_score IS NOT NULL ORDER BY ns.name_score");
         }
 
         let query = query_builder.build();
@@ -276,4 +470,42 @@ impl CarRepository for PgCarRepo {
         log::info!("Found {} cars matching passport criteria", rows.len());
         Ok(Self::form_rows_to_cars(&rows))
     }
+
+    async fn get_detailed_cars_by_vins(
+        &self,
+        vins: &[String],
+    ) -> Result<Vec<DetailedCar>, DataAccessError> {
+        log::info!("Getting detailed cars by {} VINs", vins.len());
+
+        let query = format!("{} WHERE s.vin = ANY($1)", Self::detailed_tables_query());
+        log::debug!("Executing query: {} with params: {:?}", query, vins);
+
+        let rows = sqlx::query(&query)
+            .bind(vins)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Query failed for detailed cars: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} detailed cars", rows.len());
+        Ok(rows.iter().map(Self::row_to_detailed_car).collect())
+    }
+
+    async fn get_owner_history_by_vin(
+        &self,
+        vin: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        log::info!("Getting owner history by VIN: {}", vin);
+        self.get_owner_history("vin", vin).await
+    }
+
+    async fn get_owner_history_by_gos_num(
+        &self,
+        gos_num: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError> {
+        log::info!("Getting owner history by gos number: {}", gos_num);
+        self.get_owner_history("gos_num", gos_num).await
+    }
 }
@@ -5,15 +5,21 @@ use tokio::sync::Mutex;
 
 mod camera_repo;
 mod car_repo;
+mod road_section_repo;
 mod snap_repo;
+mod speed_violation_repo;
 mod track_info_repo;
 mod user_repo;
+mod watchlist_repo;
 
 pub use camera_repo::PgCameraRepo;
 pub use car_repo::PgCarRepo;
+pub use road_section_repo::PgRoadSectionRepo;
 pub use snap_repo::PgSnapRepo;
+pub use speed_violation_repo::PgSpeedViolationRepo;
 pub use track_info_repo::PgTrackInfoRepo;
 pub use user_repo::PgUserRepo;
+pub use watchlist_repo::PgWatchlistRepo;
 
 use crate::error::DataAccessError;
 
@@ -21,9 +27,45 @@ lazy_static! {
     pub static ref PG_URL: String = cfg::var("database.postgres_url");
 }
 
-const MAX_CONNECTIONS: u32 = 10000;
+const MAX_CONNECTIONS: u32 = 32;
 const CONNECTION_WATING_TIME: Duration = Duration::from_secs(1);
 
+pub async fn create_pg_pool(pg_url: &str, max_connections: u32) -> Result<PgPool, DataAccessError> {
+    log::info!(
+        "Creating shared PostgreSQL pool (max connections: {})",
+        max_connections
+    );
+    let pool = PgPoolOptions::new()
+        .max_connections(max_connections)
+        .acquire_timeout(CONNECTION_WATING_TIME)
+        .connect(pg_url)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to connect to PostgreSQL: {}", e);
+            DataAccessError::PsqlDataBaseError(e)
+        })?;
+
+    log::info!("Successfully created PostgreSQL pool");
+    Ok(pool)
+}
+
+// Пул без установки соединения при создании: подключение выполняется
+// при первом запросе. Нужен хранилищам, не основным для текущего режима
+pub fn create_lazy_pg_pool(pg_url: &str, max_connections: u32) -> Result<PgPool, DataAccessError> {
+    log::info!(
+        "Creating lazy PostgreSQL pool (max connections: {})",
+        max_connections
+    );
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .acquire_timeout(CONNECTION_WATING_TIME)
+        .connect_lazy(pg_url)
+        .map_err(|e| {
+            log::error!("Invalid PostgreSQL url: {}", e);
+            DataAccessError::PsqlDataBaseError(e)
+        })
+}
+
 #[derive(Clone)]
 pub struct PgConnectionManager {
     pg_url: String,
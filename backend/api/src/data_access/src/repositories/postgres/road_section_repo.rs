@@ -0,0 +1,137 @@
+use crate::error::DataAccessError;
+use crate::repositories_traits::RoadSectionRepository;
+use async_trait::async_trait;
+use models::RoadSection;
+use sqlx::{
+    postgres::{PgPoolOptions, PgRow},
+    PgPool, Row,
+};
+
+pub struct PgRoadSectionRepo {
+    pool: PgPool,
+}
+
+impl PgRoadSectionRepo {
+    pub async fn from(pg_url: &str) -> Result<Self, DataAccessError> {
+        log::info!("Connecting to PostgreSQL database for RoadSection repository");
+        let pool = PgPoolOptions::new()
+            .max_connections(super::MAX_CONNECTIONS)
+            .acquire_timeout(super::CONNECTION_WATING_TIME)
+            .connect(pg_url)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to connect to PostgreSQL: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Successfully connected to PostgreSQL");
+        Ok(PgRoadSectionRepo { pool })
+    }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgRoadSectionRepo on shared PostgreSQL pool");
+        PgRoadSectionRepo { pool }
+    }
+}
+
+impl PgRoadSectionRepo {
+    fn from_row_to_section(row: &PgRow) -> RoadSection {
+        RoadSection {
+            id: row.get::<i32, _>("id") as usize,
+            start_camera_id: row.get::<i32, _>("start_camera_id") as usize,
+            end_camera_id: row.get::<i32, _>("end_camera_id") as usize,
+            distance: row.get::<i32, _>("distance") as u32,
+            speed_limit: row.get::<i32, _>("speed_limit") as u16,
+        }
+    }
+}
+
+#[async_trait]
+impl RoadSectionRepository for PgRoadSectionRepo {
+    async fn insert_section(
+        &self,
+        section: &RoadSection,
+    ) -> Result<Option<usize>, DataAccessError> {
+        log::info!(
+            "Inserting road section from camera {} to camera {}",
+            section.start_camera_id,
+            section.end_camera_id
+        );
+
+        let query = "
+            INSERT INTO RoadSection (start_camera_id, end_camera_id, distance, speed_limit)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (start_camera_id, end_camera_id) DO NOTHING
+            RETURNING id
+        ";
+        log::debug!("Executing insert query: {}", query);
+
+        let row = sqlx::query(query)
+            .bind(section.start_camera_id as i32)
+            .bind(section.end_camera_id as i32)
+            .bind(section.distance as i32)
+            .bind(section.speed_limit as i32)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to insert road section: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        match row {
+            Some(row) => {
+                let id = row.get::<i32, _>("id") as usize;
+                log::info!("Successfully inserted road section {}", id);
+                Ok(Some(id))
+            }
+            None => {
+                log::warn!("Road section already exists");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn delete_section(&self, id: usize) -> Result<(), DataAccessError> {
+        log::info!("Deleting road section {}", id);
+        let query = "DELETE FROM RoadSection WHERE id = $1";
+        log::debug!("Executing query: {} with ID: {}", query, id);
+
+        let deleted = sqlx::query(query)
+            .bind(id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to delete road section: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?
+            .rows_affected();
+
+        if deleted == 0 {
+            log::warn!("Road section {} not found", id);
+            return Err(DataAccessError::NotFoundError("road section".to_string()));
+        }
+        log::info!("Successfully deleted road section");
+        Ok(())
+    }
+
+    async fn get_sections(&self) -> Result<Vec<RoadSection>, DataAccessError> {
+        log::info!("Getting road sections");
+        let query = "
+            SELECT id, start_camera_id, end_camera_id, distance, speed_limit
+            FROM RoadSection
+            ORDER BY id
+        ";
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(query)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get road sections: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} road sections", rows.len());
+        Ok(rows.iter().map(Self::from_row_to_section).collect())
+    }
+}
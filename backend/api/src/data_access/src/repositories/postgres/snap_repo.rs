@@ -1,12 +1,14 @@
 use crate::error::DataAccessError;
+use crate::repositories::day_period;
 use crate::repositories_traits::{SnapRepoTransfer, SnapRepository, VolatileSnapRepo};
 use async_trait::async_trait;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime};
 use models::{Camera, Location, Snap};
 use sqlx::{
     postgres::{PgPoolOptions, PgRow},
     PgPool, QueryBuilder, Row,
 };
+use std::collections::HashSet;
 use std::time::{Duration, Instant};
 
 pub struct PgSnapRepo {
@@ -28,6 +30,11 @@ impl PgSnapRepo {
         log::info!("Successfully connected to PostgreSQL");
         Ok(PgSnapRepo { pool })
     }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgSnapRepo on shared PostgreSQL pool");
+        PgSnapRepo { pool }
+    }
 }
 
 impl PgSnapRepo {
@@ -38,10 +45,14 @@ impl PgSnapRepo {
             s.speed,
             s.gos_num,
             s.snap_datetime,
+            s.idempotency_key,
             c.id as camera_id,
             c.is_radar,
             c.longitude,
-            c.latitude
+            c.latitude,
+            c.speed_limit,
+            c.install_date,
+            c.is_active
         FROM CarSnapshot s
         JOIN Camera c ON s.camera_id = c.id"
             .to_string()
@@ -61,6 +72,15 @@ impl PgSnapRepo {
                         longitude: row.get("longitude"),
                         latitude: row.get("latitude"),
                     },
+                    speed_limit: row
+                        .get::<Option<i32>, _>("speed_limit")
+                        .and_then(|v| u16::try_from(v).ok()),
+                    install_date: Some(
+                        row.get::<NaiveDate, _>("install_date")
+                            .format("%d.%m.%Y")
+                            .to_string(),
+                    ),
+                    is_active: row.get("is_active"),
                 },
                 speed: row
                     .get::<Option<i32>, _>("speed")
@@ -68,6 +88,7 @@ impl PgSnapRepo {
                 gos_num: row.get("gos_num"),
                 date: datetime.date().format("%d.%m.%Y").to_string(),
                 time: datetime.time().format("%H:%M").to_string(),
+                idempotency_key: row.get("idempotency_key"),
             });
         }
         Ok(snaps)
@@ -76,7 +97,7 @@ impl PgSnapRepo {
 
 #[async_trait]
 impl SnapRepository for PgSnapRepo {
-    async fn insert_snap(&self, snap: &Snap) -> Result<(), DataAccessError> {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
         log::info!(
             "Inserting snap for vehicle {} at {} {}",
             snap.gos_num,
@@ -93,24 +114,42 @@ impl SnapRepository for PgSnapRepo {
             DataAccessError::InvalidInput(e.to_string())
         })?;
 
+        // Повторная отправка снимка (тот же ключ идемпотентности или
+        // та же камера, номер и время) не создает новую строку
         let query = "INSERT INTO CarSnapshot 
-                    (camera_id, speed, snap_datetime, gos_num) 
-                    VALUES ($1, $2, $3, $4)";
+                    (camera_id, speed, snap_datetime, gos_num, idempotency_key) 
+                    VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT DO NOTHING";
 
-        sqlx::query(query)
+        let inserted = sqlx::query(query)
             .bind(snap.camera.id as i32)
             .bind(snap.speed.map(|s| s as i32))
             .bind(datetime)
             .bind(&snap.gos_num)
+            .bind(&snap.idempotency_key)
             .execute(&self.pool)
             .await
             .map_err(|e| {
                 log::error!("Failed to insert snap: {}", e);
                 DataAccessError::PsqlDataBaseError(e)
-            })?;
+            })?
+            .rows_affected();
 
-        log::info!("Successfully inserted snap");
-        Ok(())
+        if inserted == 0 {
+            log::info!("Snap already stored, replay acknowledged");
+        } else {
+            log::info!("Successfully inserted snap");
+        }
+        Ok(inserted > 0)
+    }
+
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        if snaps.is_empty() {
+            log::debug!("Empty snap batch, nothing to insert");
+            return Ok(vec![]);
+        }
+
+        self.insert_new_snaps(snaps).await
     }
 
     async fn get_car_snaps_by_date(
@@ -120,13 +159,22 @@ impl SnapRepository for PgSnapRepo {
     ) -> Result<Vec<Snap>, DataAccessError> {
         log::info!("Getting snaps for vehicle {} on date {}", gos_number, date);
 
-        let date = NaiveDate::parse_from_str(date, "%d.%m.%Y").map_err(|e| {
-            log::error!("Invalid date format: {}", e);
-            DataAccessError::InvalidInput(e.to_string())
-        })?;
+        let (from, to) = day_period(date)?;
+        self.get_car_snaps_by_period(gos_number, &from, &to).await
+    }
 
-        let start_datetime = date.and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
-        let end_datetime = date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap());
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!(
+            "Getting snaps for vehicle {} from {} to {}",
+            gos_number,
+            from,
+            to
+        );
 
         let where_query = "
             WHERE s.gos_num = $1 
@@ -138,8 +186,8 @@ impl SnapRepository for PgSnapRepo {
 
         let rows = sqlx::query(query)
             .bind(gos_number)
-            .bind(start_datetime)
-            .bind(end_datetime)
+            .bind(from)
+            .bind(to)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| {
@@ -195,6 +243,85 @@ impl PgSnapRepo {
         Ok(())
     }
 
+    // Повторы отбрасываются по ON CONFLICT, а RETURNING возвращает ключи
+    // только действительно записанных строк
+    async fn insert_new_snaps(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        let datetimes = snaps
+            .iter()
+            .map(|snap| {
+                NaiveDateTime::parse_from_str(
+                    &format!("{} {}", snap.date, snap.time),
+                    "%d.%m.%Y %H:%M",
+                )
+                .map_err(|e| {
+                    log::error!("Failed to parse datetime: {}", e);
+                    DataAccessError::InvalidInput(e.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            log::error!("Failed to begin transaction: {}", e);
+            DataAccessError::PsqlDataBaseError(e)
+        })?;
+
+        let mut inserted_keys = HashSet::new();
+        for chunk in snaps.iter().zip(&datetimes).collect::<Vec<_>>().chunks(100) {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO CarSnapshot (camera_id, speed, snap_datetime, gos_num, idempotency_key) ",
+            );
+
+            query_builder.push_values(chunk, |mut b, (snap, datetime)| {
+                b.push_bind(snap.camera.id as i32)
+                    .push_bind(snap.speed.map(|s| s as i32))
+                    .push_bind(**datetime)
+                    .push_bind(&snap.gos_num)
+                    .push_bind(&snap.idempotency_key);
+            });
+            query_builder
+                .push(" ON CONFLICT DO NOTHING RETURNING camera_id, gos_num, snap_datetime");
+
+            let rows = query_builder
+                .build()
+                .fetch_all(&mut tx)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to execute bulk insert: {}", e);
+                    DataAccessError::PsqlDataBaseError(e)
+                })?;
+
+            for row in rows {
+                inserted_keys.insert((
+                    row.get::<i32, _>("camera_id"),
+                    row.get::<String, _>("gos_num"),
+                    row.get::<NaiveDateTime, _>("snap_datetime"),
+                ));
+            }
+        }
+
+        tx.commit().await.map_err(|e| {
+            log::error!("Failed to commit bulk insert: {}", e);
+            DataAccessError::PsqlDataBaseError(e)
+        })?;
+
+        // Ключ удаляется при первом совпадении, чтобы повтор внутри пакета не попал в ответ
+        let inserted: Vec<Snap> = snaps
+            .iter()
+            .zip(datetimes)
+            .filter(|(snap, datetime)| {
+                inserted_keys.remove(&(snap.camera.id as i32, snap.gos_num.clone(), *datetime))
+            })
+            .map(|(snap, _)| snap.clone())
+            .collect();
+
+        log::info!(
+            "Inserted {} of {} snaps, others already stored",
+            inserted.len(),
+            snaps.len()
+        );
+        Ok(inserted)
+    }
+
     pub async fn delete_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
         for snap in snaps {
             self.delete_snap(&snap).await?
@@ -237,31 +364,7 @@ impl PgSnapRepo {
         log::info!("Inserting batch of {} snaps using by VALUES", snaps.len());
         let start_time = std::time::Instant::now();
 
-        for chunk in snaps.chunks(100) {
-            let mut query_builder = QueryBuilder::new(
-                "INSERT INTO CarSnapshot (camera_id, speed, snap_datetime, gos_num) ",
-            );
-
-            query_builder.push_values(chunk, |mut b, snap| {
-                let datetime = NaiveDateTime::parse_from_str(
-                    &format!("{} {}", snap.date, snap.time),
-                    "%d.%m.%Y %H:%M",
-                )
-                .expect("Failed to parse datetime");
-
-                b.push_bind(snap.camera.id as i32)
-                    .push_bind(snap.speed.map(|s| s as i32))
-                    .push_bind(datetime)
-                    .push_bind(&snap.gos_num);
-            });
-
-            let query = query_builder.build();
-
-            query.execute(&self.pool).await.map_err(|e| {
-                log::error!("Failed to execute bulk insert: {}", e);
-                DataAccessError::PsqlDataBaseError(e)
-            })?;
-        }
+        self.insert_new_snaps(snaps).await?;
 
         let total_time = start_time.elapsed();
         log::info!(
@@ -381,6 +484,48 @@ impl SnapRepoTransfer for PgSnapRepo {
         log::info!("Deleted {} snaps", affected);
         Ok(())
     }
+
+    async fn buffered_snaps_count(&self) -> Result<usize, DataAccessError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM CarSnapshot")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Count failed: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::debug!("Snap count: {}", count);
+        Ok(count as usize)
+    }
+
+    async fn peek_buffered_snaps(&self, limit: usize) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!("Getting up to {} oldest snaps", limit);
+        let query = &format!("{} ORDER BY s.id LIMIT $1", Self::joined_tables_query());
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(query)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Query failed: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        Self::from_rows_to_snaps(&rows)
+    }
+
+    async fn remove_buffered_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        self.delete_snaps(snaps).await
+    }
+
+    // Отдельного хранилища отвергнутых снимков нет, они остаются только в логе
+    async fn dead_letter_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        for snap in snaps {
+            log::error!("Dropping rejected snap: {:?}", snap);
+        }
+        self.delete_snaps(snaps).await
+    }
 }
 
 impl VolatileSnapRepo for PgSnapRepo {}
@@ -0,0 +1,179 @@
+use crate::error::DataAccessError;
+use crate::repositories_traits::SpeedViolationRepository;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use models::{Location, SpeedViolation, ViolationSeverity};
+use sqlx::{
+    postgres::{PgPoolOptions, PgRow},
+    PgPool, Row,
+};
+
+pub struct PgSpeedViolationRepo {
+    pool: PgPool,
+}
+
+impl PgSpeedViolationRepo {
+    pub async fn from(pg_url: &str) -> Result<Self, DataAccessError> {
+        log::info!("Connecting to PostgreSQL database for SpeedViolation repository");
+        let pool = PgPoolOptions::new()
+            .max_connections(super::MAX_CONNECTIONS)
+            .acquire_timeout(super::CONNECTION_WATING_TIME)
+            .connect(pg_url)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to connect to PostgreSQL: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Successfully connected to PostgreSQL");
+        Ok(PgSpeedViolationRepo { pool })
+    }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgSpeedViolationRepo on shared PostgreSQL pool");
+        PgSpeedViolationRepo { pool }
+    }
+}
+
+impl PgSpeedViolationRepo {
+    fn severity_to_str(severity: ViolationSeverity) -> &'static str {
+        match severity {
+            ViolationSeverity::low => "low",
+            ViolationSeverity::medium => "medium",
+            ViolationSeverity::high => "high",
+            ViolationSeverity::critical => "critical",
+        }
+    }
+
+    fn severity_from_str(severity: &str) -> ViolationSeverity {
+        match severity {
+            "medium" => ViolationSeverity::medium,
+            "high" => ViolationSeverity::high,
+            "critical" => ViolationSeverity::critical,
+            _ => ViolationSeverity::low,
+        }
+    }
+
+    fn from_row_to_violation(row: &PgRow) -> SpeedViolation {
+        let datetime: NaiveDateTime = row.get("snap_datetime");
+        SpeedViolation {
+            id: row.get::<i32, _>("id") as usize,
+            gos_num: row.get("gos_num"),
+            camera_id: row.get::<i32, _>("camera_id") as usize,
+            cords: Location {
+                longitude: row.get("longitude"),
+                latitude: row.get("latitude"),
+            },
+            date: datetime.date().format("%d.%m.%Y").to_string(),
+            time: datetime.time().format("%H:%M").to_string(),
+            speed: row.get::<i32, _>("speed") as u16,
+            speed_limit: row.get::<i32, _>("speed_limit") as u16,
+            severity: Self::severity_from_str(row.get("severity")),
+            section_id: row
+                .get::<Option<i32>, _>("section_id")
+                .map(|id| id as usize),
+        }
+    }
+}
+
+#[async_trait]
+impl SpeedViolationRepository for PgSpeedViolationRepo {
+    async fn insert_violation(
+        &self,
+        violation: &SpeedViolation,
+    ) -> Result<Option<usize>, DataAccessError> {
+        log::info!(
+            "Inserting speed violation for vehicle {} at camera {}",
+            violation.gos_num,
+            violation.camera_id
+        );
+
+        let datetime = NaiveDateTime::parse_from_str(
+            &format!("{} {}", violation.date, violation.time),
+            "%d.%m.%Y %H:%M",
+        )
+        .map_err(|e| {
+            log::error!("Failed to parse datetime: {}", e);
+            DataAccessError::InvalidInput(e.to_string())
+        })?;
+
+        let query = "
+            INSERT INTO SpeedViolation
+                (camera_id, gos_num, snap_datetime, speed, speed_limit, severity, section_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING
+            RETURNING id
+        ";
+        log::debug!("Executing insert query: {}", query);
+
+        let row = sqlx::query(query)
+            .bind(violation.camera_id as i32)
+            .bind(&violation.gos_num)
+            .bind(datetime)
+            .bind(violation.speed as i32)
+            .bind(violation.speed_limit as i32)
+            .bind(Self::severity_to_str(violation.severity))
+            .bind(violation.section_id.map(|id| id as i32))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to insert speed violation: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        match row {
+            Some(row) => {
+                log::info!("Successfully inserted speed violation");
+                Ok(Some(row.get::<i32, _>("id") as usize))
+            }
+            None => {
+                log::info!("Speed violation already stored");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn get_violations_by_filters(
+        &self,
+        gos_num: Option<&str>,
+        camera_id: Option<usize>,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<SpeedViolation>, DataAccessError> {
+        log::info!(
+            "Searching speed violations by filters: {:?} {:?} {} - {}",
+            gos_num,
+            camera_id,
+            from,
+            to
+        );
+
+        let query = "
+            SELECT
+                v.id, v.gos_num, v.camera_id, v.snap_datetime, v.speed, v.speed_limit,
+                v.severity, v.section_id, c.longitude, c.latitude
+            FROM SpeedViolation v
+            JOIN Camera c ON c.id = v.camera_id
+            WHERE v.snap_datetime BETWEEN $1 AND $2
+                AND ($3::TEXT IS NULL OR v.gos_num = $3)
+                AND ($4::INTEGER IS NULL OR v.camera_id = $4)
+            ORDER BY v.snap_datetime, v.id
+        ";
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(query)
+            .bind(from)
+            .bind(to)
+            .bind(gos_num)
+            .bind(camera_id.map(|id| id as i32))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get speed violations: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} speed violations", rows.len());
+        Ok(rows.iter().map(Self::from_row_to_violation).collect())
+    }
+}
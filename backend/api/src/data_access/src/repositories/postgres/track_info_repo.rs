@@ -2,7 +2,7 @@ use crate::error::DataAccessError;
 use crate::repositories_traits::TrackInfoRepository;
 use async_trait::async_trait;
 use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc};
-use models::{Car, Document, Role, TrackInfo, User};
+use models::{Car, Document, Page, Paged, Role, SortKey, TrackInfo, User};
 use sqlx::{postgres::PgPoolOptions, PgPool, QueryBuilder, Row};
 
 pub struct PgTrackInfoRepo {
@@ -25,6 +25,11 @@ impl PgTrackInfoRepo {
         log::info!("Successfully connected to PostgreSQL");
         Ok(PgTrackInfoRepo { pool })
     }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgTrackInfoRepo on shared PostgreSQL pool");
+        PgTrackInfoRepo { pool }
+    }
 }
 
 impl PgTrackInfoRepo {
@@ -121,6 +126,7 @@ impl PgTrackInfoRepo {
                             "user" => Role::user,
                             "operator" => Role::operator,
                             "audit" => Role::audit,
+                            "camera" => Role::camera,
                             _ => Role::user,
                         },
                         is_verified: row.get("is_verified"),
@@ -207,21 +213,29 @@ impl TrackInfoRepository for PgTrackInfoRepo {
         passport: Option<Document>,
         gos_num_mask: Option<&str>,
         date: Option<&str>,
-    ) -> Result<Vec<TrackInfo>, DataAccessError> {
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, DataAccessError> {
         let transformed_gos_num = gos_num_mask.map(|gsn| Self::transform_mask_for_psql_like(gsn));
         log::info!(
-            "Searching track info records by filters: {:?} {:?} {:?} {:?} {:?} {:?}",
+            "Searching track info records by filters: {:?} {:?} {:?} {:?} {:?} {:?} ({:?})",
             firstname,
             surname,
             lastname,
             passport,
             transformed_gos_num,
             date,
+            page,
         );
 
         gos_num_mask.map(|gsn| Self::transform_mask_for_psql_like(gsn));
 
-        let query = "SELECT * FROM get_tracks_info($1, $2, $3, $4, $5, $6, $7)";
+        let sort = match page.sort {
+            SortKey::gos_num => "gos_num",
+            SortKey::owner_surname => "owner_surname",
+            SortKey::track_time => "track_time",
+        };
+
+        let query = "SELECT * FROM get_tracks_info($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)";
         log::debug!("Executing query: {}", query);
 
         let sdate = match &date {
@@ -253,9 +267,12 @@ impl TrackInfoRepository for PgTrackInfoRepo {
             .bind(surname)
             .bind(lastname)
             .bind(sdate)
-            .bind(transformed_gos_num)
+            .bind(&transformed_gos_num)
             .bind(pserial)
             .bind(pnumber)
+            .bind(sort)
+            .bind(page.limit as i32)
+            .bind(page.offset as i32)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| {
@@ -263,8 +280,38 @@ impl TrackInfoRepository for PgTrackInfoRepo {
                 DataAccessError::PsqlDataBaseError(e)
             })?;
 
-        log::info!("Found {} track info records by filters", rows.len());
-        Ok(Self::form_rows_to_track_infos(&rows))
+        let total = match rows.first() {
+            Some(row) => row.get::<i64, _>("total_count") as usize,
+            None if page.offset == 0 => 0,
+            // Страница за пределами выборки: общее число считается отдельно
+            None => {
+                let query = "SELECT COUNT(*) FROM get_tracks_info($1, $2, $3, $4, $5, $6, $7)";
+                sqlx::query_scalar::<_, i64>(query)
+                    .bind(firstname)
+                    .bind(surname)
+                    .bind(lastname)
+                    .bind(sdate)
+                    .bind(&transformed_gos_num)
+                    .bind(pserial)
+                    .bind(pnumber)
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(|e| {
+                        log::error!("Count query failed: {}", e);
+                        DataAccessError::PsqlDataBaseError(e)
+                    })? as usize
+            }
+        };
+
+        log::info!(
+            "Found {} track info records by filters, {} on page",
+            total,
+            rows.len()
+        );
+        Ok(Paged {
+            items: Self::form_rows_to_track_infos(&rows),
+            total,
+        })
     }
 
     async fn get_track_info_by_date(&self, date: &str) -> Result<Vec<TrackInfo>, DataAccessError> {
@@ -339,36 +386,37 @@ impl TrackInfoRepository for PgTrackInfoRepo {
 
         let mut query_builder = QueryBuilder::new(Self::joined_tables_query());
 
-        if name.is_some() || surname.is_some() || last_name.is_some() {
-            query_builder.push(" WHERE ");
-
-            let mut needs_and = false;
-
-            if let Some(name) = name {
-                if needs_and {
-                    query_builder.push(" AND ");
-                }
-                query_builder.push("a.name = ");
-                query_builder.push_bind(name);
-                needs_and = true;
+        let fio: Vec<(&str, &str)> = [
+            ("a.name", name),
+            ("a.surname", surname),
+            ("a.lastname", last_name),
+        ]
+        .into_iter()
+        .filter_map(|(column, value)| value.map(|value| (column, value)))
+        .collect();
+
+        // Оценка совпадения считается один раз для кандидатов, отобранных по индексу
+        if !fio.is_empty() {
+            query_builder.push(" CROSS JOIN LATERAL (SELECT 0");
+            for (column, value) in &fio {
+                query_builder.push(format!(" + name_match_score({column}, "));
+                query_builder.push_bind(*value);
+                query_builder.push(")");
             }
+            query_builder.push(" OFFSET 0) AS ns(name_score)");
+        }
 
-            if let Some(surname) = surname {
-                if needs_and {
-                    query_builder.push(" AND ");
-                }
-                query_builder.push("a.surname = ");
-                query_builder.push_bind(surname);
-                needs_and = true;
-            }
+        // Имена сравниваются без учета регистра и различия ё/е, по началу или нечетко
+        query_builder.push(" WHERE 1 = 1");
+        for (column, value) in &fio {
+            query_builder.push(format!(" AND name_candidate({column}, "));
+            query_builder.push_bind(*value);
+            query_builder.push(")");
+        }
 
-            if let Some(last_name) = last_name {
-                if needs_and {
-                    query_builder.push(" AND ");
-                }
-                query_builder.push("a.lastname = ");
-                query_builder.push_bind(last_name);
-            }
+        // Сначала наиболее близкие по ФИО
+        if !fio.is_empty() {
+            query_builder.push(" AND ns.name_score IS NOT NULL ORDER BY ns.name_score");
         }
 
         let query = query_builder.build();
@@ -27,6 +27,11 @@ impl PgUserRepo {
         log::info!("Successfully connected to PostgreSQL");
         Ok(PgUserRepo { pool })
     }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgUserRepo on shared PostgreSQL pool");
+        PgUserRepo { pool }
+    }
 }
 
 impl PgUserRepo {
@@ -44,6 +49,7 @@ impl PgUserRepo {
                 "user" => Role::user,
                 "operator" => Role::operator,
                 "audit" => Role::audit,
+                "camera" => Role::camera,
                 _ => Role::user,
             },
             is_verified: row.get("is_verified"),
@@ -62,18 +68,16 @@ impl PgUserRepo {
 
 #[async_trait]
 impl UserRepository for PgUserRepo {
-    async fn get_user_by_auth_info(
+    async fn get_user_with_pswd_hash(
         &self,
         email: &str,
-        password: &str,
-    ) -> Result<Option<User>, DataAccessError> {
-        log::info!("Authenticating user with email: {}", email);
-        let query = "SELECT * FROM AppUser WHERE login = $1 AND password = $2 LIMIT 1";
+    ) -> Result<Option<(User, String)>, DataAccessError> {
+        log::info!("Getting user with password hash by email: {}", email);
+        let query = "SELECT * FROM AppUser WHERE login = $1 LIMIT 1";
         log::debug!("Executing auth query: {}", query);
 
         let row = sqlx::query(query)
             .bind(email)
-            .bind(password)
             .fetch_optional(&self.pool)
             .await
             .map_err(|e| {
@@ -83,11 +87,11 @@ impl UserRepository for PgUserRepo {
 
         match row {
             Some(row) => {
-                log::info!("User authenticated successfully: {}", email);
-                Ok(Some(Self::form_row_to_user(&row)))
+                log::info!("User found: {}", email);
+                Ok(Some((Self::form_row_to_user(&row), row.get("password"))))
             }
             None => {
-                log::warn!("Authentication failed for user: {}", email);
+                log::warn!("User not found: {}", email);
                 Ok(None)
             }
         }
@@ -158,7 +162,7 @@ impl UserRepository for PgUserRepo {
         }
     }
 
-    async fn insert_user(&self, user: &User, password: &str) -> Result<(), DataAccessError> {
+    async fn insert_user(&self, user: &User, pswd_hash: &str) -> Result<(), DataAccessError> {
         log::info!("Inserting new user: {}", user.email);
         let (passport_serial, passport_num) = match &user.passport {
             Some(passport) => {
@@ -185,11 +189,12 @@ impl UserRepository for PgUserRepo {
 
         sqlx::query(query)
             .bind(&user.email)
-            .bind(password)
+            .bind(pswd_hash)
             .bind(match user.role {
                 Role::user => "user",
                 Role::operator => "operator",
                 Role::audit => "audit",
+                Role::camera => "camera",
             })
             .bind(&user.name)
             .bind(&user.surname)
@@ -248,6 +253,29 @@ impl UserRepository for PgUserRepo {
         log::info!("Passport updated successfully for user: {}", email);
         Ok(())
     }
+
+    async fn update_user_pswd_hash(
+        &self,
+        email: &str,
+        pswd_hash: &str,
+    ) -> Result<(), DataAccessError> {
+        log::info!("Updating password hash for user: {}", email);
+        let query = "UPDATE AppUser SET password = $1 WHERE login = $2";
+        log::debug!("Executing update query: {}", query);
+
+        sqlx::query(query)
+            .bind(pswd_hash)
+            .bind(email)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to update password hash for user {}: {}", email, e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Password hash updated successfully for user: {}", email);
+        Ok(())
+    }
 }
 
 impl PgUserRepo {
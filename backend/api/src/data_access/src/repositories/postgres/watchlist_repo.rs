@@ -0,0 +1,312 @@
+use crate::error::DataAccessError;
+use crate::repositories::day_period;
+use crate::repositories_traits::WatchlistRepository;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use models::{Location, WatchlistAlert, WatchlistEntry};
+use sqlx::{
+    postgres::{PgPoolOptions, PgRow},
+    PgPool, Row,
+};
+
+const DATETIME_FORMAT: &str = "%d.%m.%Y %H:%M";
+
+pub struct PgWatchlistRepo {
+    pool: PgPool,
+}
+
+impl PgWatchlistRepo {
+    pub async fn from(pg_url: &str) -> Result<Self, DataAccessError> {
+        log::info!("Connecting to PostgreSQL database for Watchlist repository");
+        let pool = PgPoolOptions::new()
+            .max_connections(super::MAX_CONNECTIONS)
+            .acquire_timeout(super::CONNECTION_WATING_TIME)
+            .connect(pg_url)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to connect to PostgreSQL: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Successfully connected to PostgreSQL");
+        Ok(PgWatchlistRepo { pool })
+    }
+
+    pub fn from_pool(pool: PgPool) -> Self {
+        log::info!("Creating PgWatchlistRepo on shared PostgreSQL pool");
+        PgWatchlistRepo { pool }
+    }
+}
+
+impl PgWatchlistRepo {
+    fn parse_expires_at(
+        expires_at: &Option<String>,
+    ) -> Result<Option<NaiveDateTime>, DataAccessError> {
+        expires_at
+            .as_deref()
+            .map(|dt| {
+                NaiveDateTime::parse_from_str(dt, DATETIME_FORMAT).map_err(|e| {
+                    log::error!("Invalid expiration datetime format: {}", e);
+                    DataAccessError::InvalidInput(e.to_string())
+                })
+            })
+            .transpose()
+    }
+
+    fn from_row_to_entry(row: &PgRow) -> WatchlistEntry {
+        WatchlistEntry {
+            id: row.get::<i32, _>("id") as usize,
+            gos_num_mask: row.get("gos_num_mask"),
+            reason: row.get("reason"),
+            expires_at: row
+                .get::<Option<NaiveDateTime>, _>("expires_at")
+                .map(|dt| dt.format(DATETIME_FORMAT).to_string()),
+            created_by: row.get("login"),
+        }
+    }
+
+    fn from_row_to_alert(row: &PgRow) -> WatchlistAlert {
+        let datetime: NaiveDateTime = row.get("snap_datetime");
+        WatchlistAlert {
+            id: row.get::<i32, _>("id") as usize,
+            entry_id: row.get::<i32, _>("watchlist_id") as usize,
+            reason: row.get("reason"),
+            gos_num: row.get("gos_num"),
+            camera_id: row.get::<i32, _>("camera_id") as usize,
+            cords: Location {
+                longitude: row.get("longitude"),
+                latitude: row.get("latitude"),
+            },
+            date: datetime.date().format("%d.%m.%Y").to_string(),
+            time: datetime.time().format("%H:%M").to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl WatchlistRepository for PgWatchlistRepo {
+    async fn insert_entry(&self, entry: &WatchlistEntry) -> Result<usize, DataAccessError> {
+        log::info!(
+            "Inserting watchlist entry {} by user {}",
+            entry.gos_num_mask,
+            entry.created_by
+        );
+
+        let expires_at = Self::parse_expires_at(&entry.expires_at)?;
+        let query = "
+            INSERT INTO Watchlist (gos_num_mask, reason, expires_at, created_by)
+            SELECT $1, $2, $3, a.id
+            FROM AppUser a
+            WHERE a.login = $4
+            RETURNING id
+        ";
+        log::debug!("Executing insert query: {}", query);
+
+        let row = sqlx::query(query)
+            .bind(&entry.gos_num_mask)
+            .bind(&entry.reason)
+            .bind(expires_at)
+            .bind(&entry.created_by)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to insert watchlist entry: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        match row {
+            Some(row) => {
+                let id = row.get::<i32, _>("id") as usize;
+                log::info!("Successfully inserted watchlist entry {}", id);
+                Ok(id)
+            }
+            None => {
+                log::warn!("User {} not found", entry.created_by);
+                Err(DataAccessError::NotFoundError("email".to_string()))
+            }
+        }
+    }
+
+    async fn update_entry(&self, entry: &WatchlistEntry) -> Result<(), DataAccessError> {
+        log::info!("Updating watchlist entry {}", entry.id);
+
+        let expires_at = Self::parse_expires_at(&entry.expires_at)?;
+        let query = "
+            UPDATE Watchlist
+            SET gos_num_mask = $1, reason = $2, expires_at = $3
+            WHERE id = $4
+        ";
+        log::debug!("Executing update query: {}", query);
+
+        let updated = sqlx::query(query)
+            .bind(&entry.gos_num_mask)
+            .bind(&entry.reason)
+            .bind(expires_at)
+            .bind(entry.id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to update watchlist entry: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?
+            .rows_affected();
+
+        if updated == 0 {
+            log::warn!("Watchlist entry {} not found", entry.id);
+            return Err(DataAccessError::NotFoundError(
+                "watchlist entry".to_string(),
+            ));
+        }
+        log::info!("Successfully updated watchlist entry");
+        Ok(())
+    }
+
+    async fn delete_entry(&self, id: usize) -> Result<(), DataAccessError> {
+        log::info!("Deleting watchlist entry {}", id);
+        let query = "DELETE FROM Watchlist WHERE id = $1";
+        log::debug!("Executing query: {} with ID: {}", query, id);
+
+        let deleted = sqlx::query(query)
+            .bind(id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to delete watchlist entry: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?
+            .rows_affected();
+
+        if deleted == 0 {
+            log::warn!("Watchlist entry {} not found", id);
+            return Err(DataAccessError::NotFoundError(
+                "watchlist entry".to_string(),
+            ));
+        }
+        log::info!("Successfully deleted watchlist entry");
+        Ok(())
+    }
+
+    async fn get_entry(&self, id: usize) -> Result<Option<WatchlistEntry>, DataAccessError> {
+        log::info!("Getting watchlist entry by ID: {}", id);
+        let query = "
+            SELECT w.id, w.gos_num_mask, w.reason, w.expires_at, a.login
+            FROM Watchlist w
+            JOIN AppUser a ON a.id = w.created_by
+            WHERE w.id = $1
+        ";
+        log::debug!("Executing query: {} with ID: {}", query, id);
+
+        let row = sqlx::query(query)
+            .bind(id as i32)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get watchlist entry: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        Ok(row.as_ref().map(Self::from_row_to_entry))
+    }
+
+    async fn get_entries(&self) -> Result<Vec<WatchlistEntry>, DataAccessError> {
+        log::info!("Getting all watchlist entries");
+        let query = "
+            SELECT w.id, w.gos_num_mask, w.reason, w.expires_at, a.login
+            FROM Watchlist w
+            JOIN AppUser a ON a.id = w.created_by
+            ORDER BY w.id
+        ";
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(query)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get watchlist entries: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} watchlist entries", rows.len());
+        Ok(rows.iter().map(Self::from_row_to_entry).collect())
+    }
+
+    async fn insert_alert(&self, alert: &WatchlistAlert) -> Result<Option<usize>, DataAccessError> {
+        log::info!(
+            "Inserting watchlist alert for vehicle {} at camera {}",
+            alert.gos_num,
+            alert.camera_id
+        );
+
+        let datetime = NaiveDateTime::parse_from_str(
+            &format!("{} {}", alert.date, alert.time),
+            DATETIME_FORMAT,
+        )
+        .map_err(|e| {
+            log::error!("Failed to parse datetime: {}", e);
+            DataAccessError::InvalidInput(e.to_string())
+        })?;
+
+        let query = "
+            INSERT INTO WatchlistAlert
+                (watchlist_id, camera_id, gos_num, snap_datetime, longitude, latitude)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT DO NOTHING
+            RETURNING id
+        ";
+        log::debug!("Executing insert query: {}", query);
+
+        let row = sqlx::query(query)
+            .bind(alert.entry_id as i32)
+            .bind(alert.camera_id as i32)
+            .bind(&alert.gos_num)
+            .bind(datetime)
+            .bind(alert.cords.longitude)
+            .bind(alert.cords.latitude)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to insert watchlist alert: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        match row {
+            Some(row) => {
+                log::info!("Successfully inserted watchlist alert");
+                Ok(Some(row.get::<i32, _>("id") as usize))
+            }
+            None => {
+                log::info!("Watchlist alert already stored");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn get_alerts_by_date(&self, date: &str) -> Result<Vec<WatchlistAlert>, DataAccessError> {
+        log::info!("Getting watchlist alerts for date {}", date);
+
+        let (from, to) = day_period(date)?;
+        let query = "
+            SELECT
+                al.id, al.watchlist_id, w.reason, al.gos_num, al.camera_id,
+                al.longitude, al.latitude, al.snap_datetime
+            FROM WatchlistAlert al
+            JOIN Watchlist w ON w.id = al.watchlist_id
+            WHERE al.snap_datetime BETWEEN $1 AND $2
+            ORDER BY al.snap_datetime, al.id
+        ";
+        log::debug!("Executing query: {}", query);
+
+        let rows = sqlx::query(query)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get watchlist alerts: {}", e);
+                DataAccessError::PsqlDataBaseError(e)
+            })?;
+
+        log::info!("Found {} watchlist alerts", rows.len());
+        Ok(rows.iter().map(Self::from_row_to_alert).collect())
+    }
+}
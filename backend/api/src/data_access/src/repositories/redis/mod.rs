@@ -1,6 +1,8 @@
 use lazy_static::lazy_static;
 
+mod session_repo;
 mod snap_repo;
+pub use session_repo::RedisSessionRepo;
 pub use snap_repo::RedisSnapRepo;
 
 lazy_static! {
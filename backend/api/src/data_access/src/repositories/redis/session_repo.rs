@@ -0,0 +1,85 @@
+use crate::error::DataAccessError;
+use crate::repositories_traits::SessionRepository;
+
+use async_trait::async_trait;
+use redis::{AsyncCommands, Client, RedisResult};
+
+pub struct RedisSessionRepo {
+    client: Client,
+}
+
+impl RedisSessionRepo {
+    pub fn from(redis_url: &str) -> Result<Self, DataAccessError> {
+        log::info!("Creating RedisSessionRepo with URL: {}", redis_url);
+        let client = Client::open(redis_url)?;
+        Ok(RedisSessionRepo { client })
+    }
+
+    async fn get_connection(&self) -> RedisResult<redis::aio::MultiplexedConnection> {
+        log::debug!("Acquiring Redis connection");
+        self.client.get_multiplexed_async_connection().await
+    }
+
+    fn session_key(refresh_token: &str) -> String {
+        format!("session:{}", refresh_token)
+    }
+}
+
+#[async_trait]
+impl SessionRepository for RedisSessionRepo {
+    async fn insert_session(
+        &self,
+        refresh_token: &str,
+        email: &str,
+        ttl_secs: u64,
+    ) -> Result<(), DataAccessError> {
+        log::info!("Inserting session for user {}", email);
+        let mut conn = self.get_connection().await?;
+
+        let _: () = conn
+            .set_ex(Self::session_key(refresh_token), email, ttl_secs)
+            .await
+            .map_err(|e| {
+                log::error!("Redis set operation failed: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
+
+        log::info!("Successfully inserted session for user {}", email);
+        Ok(())
+    }
+
+    async fn take_session(&self, refresh_token: &str) -> Result<Option<String>, DataAccessError> {
+        log::info!("Taking session by refresh token");
+        let mut conn = self.get_connection().await?;
+
+        let email: Option<String> = conn
+            .get_del(Self::session_key(refresh_token))
+            .await
+            .map_err(|e| {
+                log::error!("Redis getdel operation failed: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
+
+        match &email {
+            Some(email) => log::info!("Session taken for user {}", email),
+            None => log::warn!("Session not found"),
+        }
+        Ok(email)
+    }
+
+    async fn delete_session(&self, refresh_token: &str) -> Result<(), DataAccessError> {
+        log::info!("Deleting session by refresh token");
+        let mut conn = self.get_connection().await?;
+
+        let _: usize = conn
+            .del(Self::session_key(refresh_token))
+            .await
+            .map_err(|e| {
+                log::error!("Redis delete operation failed: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
+
+        log::info!("Successfully deleted session");
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 use crate::error::DataAccessError;
+use crate::repositories::{day_period, snap_datetime};
 use crate::repositories_traits::{SnapRepoTransfer, SnapRepository, VolatileSnapRepo};
 
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
 use models::{Camera, Location, Snap};
-use redis::{AsyncCommands, Client, RedisResult};
+use redis::{AsyncCommands, Client, RedisResult, Script};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
@@ -16,8 +17,61 @@ struct RedisSnap {
     latitude: f64,
     gos_num: String,
     datetime: String,
+    #[serde(default)]
+    idempotency_key: Option<String>,
+    #[serde(default)]
+    speed_limit: Option<u16>,
 }
 
+// Отметки ключей идемпотентности переживают перенос снимков в основное хранилище
+const IDEMPOTENCY_KEY_TTL_SECS: u64 = 24 * 60 * 60;
+
+const SNAP_DATETIME_FORMAT: &str = "%d.%m.%Y %H:%M";
+
+// Очередь ключей снимков в порядке поступления, ее длина - размер буфера
+const SNAP_BUFFER_KEY: &str = "snap_buffer";
+
+// Снимки, отвергнутые основным хранилищем при переносе
+const SNAP_DEAD_LETTER_KEY: &str = "snap_dead_letter";
+
+// Отметка принятого снимка по номеру, камере и времени: повтор снимка без ключа
+// идемпотентности, пришедший вскоре после переноса буфера, не считается новым
+const SEEN_SNAP_TTL_SECS: u64 = 60 * 60;
+
+// Снимок попадает в очередь только если он не принимался ранее ни по ключу
+// идемпотентности (KEYS[4], если передан), ни по естественному ключу. Отметки
+// ставятся в том же скрипте, что и запись, поэтому сбой не оставит отметку без снимка
+const BUFFERED_SET_SCRIPT: &str = r#"
+if #KEYS == 4 and redis.call('EXISTS', KEYS[4]) == 1 then
+    return 0
+end
+if redis.call('EXISTS', KEYS[3]) == 1 then
+    return 0
+end
+if not redis.call('SET', KEYS[1], ARGV[1], 'NX') then
+    return 0
+end
+redis.call('RPUSH', KEYS[2], KEYS[1])
+redis.call('SET', KEYS[3], 1, 'EX', ARGV[2])
+if #KEYS == 4 then
+    redis.call('SET', KEYS[4], KEYS[1], 'EX', ARGV[3])
+end
+return 1
+"#;
+
+// Снимок переносится из буфера в очередь отвергнутых вместе с данными
+const DEAD_LETTER_SCRIPT: &str = r#"
+for _, key in ipairs(KEYS) do
+    local value = redis.call('GET', key)
+    redis.call('LREM', ARGV[1], 1, key)
+    if value then
+        redis.call('RPUSH', ARGV[2], value)
+        redis.call('DEL', key)
+    end
+end
+return 0
+"#;
+
 pub struct RedisSnapRepo {
     client: Client,
 }
@@ -34,6 +88,58 @@ impl RedisSnapRepo {
         self.client.get_multiplexed_async_connection().await
     }
 
+    fn to_redis_entry(snap: &Snap) -> Result<(String, String), DataAccessError> {
+        let redis_snap = RedisSnap {
+            speed: snap.speed,
+            camera_id: snap.camera.id,
+            longitude: snap.camera.location.longitude,
+            latitude: snap.camera.location.latitude,
+            gos_num: snap.gos_num.clone(),
+            datetime: format!("{} {}", snap.date, snap.time),
+            idempotency_key: snap.idempotency_key.clone(),
+            speed_limit: snap.camera.speed_limit,
+        };
+
+        let serialized = serde_json::to_string(&redis_snap).map_err(|e| {
+            log::error!("Failed to serialize snap: {}", e);
+            DataAccessError::InvalidInput(e.to_string())
+        })?;
+
+        Ok((Self::snap_key(snap), serialized))
+    }
+
+    // Камера входит в ключ, чтобы снимки одного номера с разных камер
+    // в одну минуту не перезаписывали друг друга. Дата и время приводятся к
+    // виду, в котором снимок читается из Redis ("8:10" -> "08:10"), иначе
+    // прочитанный из буфера снимок не найдет свой ключ при удалении
+    fn snap_key(snap: &Snap) -> String {
+        let datetime = snap_datetime(snap)
+            .map(|datetime| datetime.format(SNAP_DATETIME_FORMAT).to_string())
+            .unwrap_or_else(|| format!("{} {}", snap.date, snap.time));
+        format!("snap:{}:{}:{}", snap.gos_num, datetime, snap.camera.id)
+    }
+
+    fn idempotency_marker_key(snap: &Snap) -> Option<String> {
+        snap.idempotency_key
+            .as_ref()
+            .map(|key| format!("snap_idempotency:{}:{}", snap.camera.id, key))
+    }
+
+    fn seen_marker_key(key: &str) -> String {
+        format!("snap_seen:{}", key.trim_start_matches("snap:"))
+    }
+
+    // Ключи BUFFERED_SET_SCRIPT: запись, очередь, отметка снимка и отметка ключа идемпотентности
+    fn buffered_set_keys(snap: &Snap, key: &str) -> Vec<String> {
+        let mut keys = vec![
+            key.to_string(),
+            SNAP_BUFFER_KEY.to_string(),
+            Self::seen_marker_key(key),
+        ];
+        keys.extend(Self::idempotency_marker_key(snap));
+        keys
+    }
+
     async fn get_keys_by_pattern(&self, pattern: &str) -> Result<Vec<String>, DataAccessError> {
         log::info!("Getting keys by pattern: {}", pattern);
         let mut conn = self.get_connection().await?;
@@ -54,7 +160,7 @@ impl RedisSnapRepo {
             "Converting RedisSnap to Snap for vehicle: {}",
             redis_snap.gos_num
         );
-        let datetime = NaiveDateTime::parse_from_str(&redis_snap.datetime, "%d.%m.%Y %H:%M")
+        let datetime = NaiveDateTime::parse_from_str(&redis_snap.datetime, SNAP_DATETIME_FORMAT)
             .map_err(|e| {
                 log::error!("Failed to parse datetime: {}", e);
                 DataAccessError::InvalidInput(e.to_string())
@@ -72,18 +178,22 @@ impl RedisSnapRepo {
                     longitude: redis_snap.longitude,
                     latitude: redis_snap.latitude,
                 },
+                speed_limit: redis_snap.speed_limit,
+                install_date: None,
+                is_active: true,
             },
             speed: redis_snap.speed,
             gos_num: redis_snap.gos_num.clone(),
             date: datetime.date().format("%d.%m.%Y").to_string(),
             time: datetime.time().format("%H:%M").to_string(),
+            idempotency_key: redis_snap.idempotency_key.clone(),
         })
     }
 }
 
 #[async_trait]
 impl SnapRepository for RedisSnapRepo {
-    async fn insert_snap(&self, snap: &Snap) -> Result<(), DataAccessError> {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
         log::info!(
             "Inserting snap for vehicle {} at {} {}",
             snap.gos_num,
@@ -92,30 +202,70 @@ impl SnapRepository for RedisSnapRepo {
         );
         let mut conn = self.get_connection().await?;
 
-        let redis_snap = RedisSnap {
-            speed: snap.speed,
-            camera_id: snap.camera.id,
-            longitude: snap.camera.location.longitude,
-            latitude: snap.camera.location.latitude,
-            gos_num: snap.gos_num.clone(),
-            datetime: format!("{} {}", snap.date, snap.time),
-        };
+        let (key, serialized) = Self::to_redis_entry(snap)?;
 
-        let serialized = serde_json::to_string(&redis_snap).map_err(|e| {
-            log::error!("Failed to serialize snap: {}", e);
-            DataAccessError::InvalidInput(e.to_string())
-        })?;
-
-        let key = format!("snap:{}:{}", snap.gos_num, redis_snap.datetime);
         log::debug!("Storing snap with key: {}", key);
+        let stored: i32 = Script::new(BUFFERED_SET_SCRIPT)
+            .key(Self::buffered_set_keys(snap, &key))
+            .arg(serialized)
+            .arg(SEEN_SNAP_TTL_SECS)
+            .arg(IDEMPOTENCY_KEY_TTL_SECS)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                log::error!("Redis set operation failed: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
 
-        let _: () = conn.set(key, serialized).await.map_err(|e| {
-            log::error!("Redis set operation failed: {}", e);
+        if stored == 1 {
+            log::info!("Successfully inserted snap");
+        } else {
+            log::info!("Snap {} already stored, replay acknowledged", key);
+        }
+        Ok(stored == 1)
+    }
+
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!("Inserting batch of {} snaps", snaps.len());
+        if snaps.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let entries = snaps
+            .iter()
+            .map(Self::to_redis_entry)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut conn = self.get_connection().await?;
+
+        let mut pipe = redis::pipe();
+        for (snap, (key, serialized)) in snaps.iter().zip(entries) {
+            let keys = Self::buffered_set_keys(snap, &key);
+            pipe.cmd("EVAL")
+                .arg(BUFFERED_SET_SCRIPT)
+                .arg(keys.len())
+                .arg(keys)
+                .arg(serialized)
+                .arg(SEEN_SNAP_TTL_SECS)
+                .arg(IDEMPOTENCY_KEY_TTL_SECS);
+        }
+        let stored: Vec<i32> = pipe.query_async(&mut conn).await.map_err(|e| {
+            log::error!("Redis pipeline failed: {}", e);
             DataAccessError::RedisDataBaseError(e)
         })?;
 
-        log::info!("Successfully inserted snap");
-        Ok(())
+        let inserted: Vec<Snap> = snaps
+            .iter()
+            .zip(stored)
+            .filter(|(_, stored)| *stored == 1)
+            .map(|(snap, _)| snap.clone())
+            .collect();
+
+        log::info!(
+            "Successfully inserted {} of {} snaps",
+            inserted.len(),
+            snaps.len()
+        );
+        Ok(inserted)
     }
 
     async fn get_car_snaps_by_date(
@@ -124,33 +274,62 @@ impl SnapRepository for RedisSnapRepo {
         date: &str,
     ) -> Result<Vec<Snap>, DataAccessError> {
         log::info!("Getting snaps for vehicle {} on date {}", gos_number, date);
-        let mut conn = self.get_connection().await?;
 
-        let pattern = format!("snap:{}:{}*", gos_number, date);
-        log::debug!("Using key pattern: {}", pattern);
+        let (from, to) = day_period(date)?;
+        self.get_car_snaps_by_period(gos_number, &from, &to).await
+    }
 
-        let keys: Vec<String> = conn.keys(&pattern).await.map_err(|e| {
-            log::error!("Redis keys operation failed: {}", e);
-            DataAccessError::RedisDataBaseError(e)
-        })?;
+    // Дата входит в ключ снимка, поэтому ключи выбираются по каждому дню периода,
+    // а границы периода внутри дня проверяются по времени снимка
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!(
+            "Getting snaps for vehicle {} from {} to {}",
+            gos_number,
+            from,
+            to
+        );
+        let mut conn = self.get_connection().await?;
 
-        log::debug!("Found {} matching keys", keys.len());
         let mut snaps = Vec::new();
+        for day in from.date().iter_days().take_while(|day| *day <= to.date()) {
+            let pattern = format!("snap:{}:{} *", gos_number, day.format("%d.%m.%Y"));
+            log::debug!("Using key pattern: {}", pattern);
 
-        for key in keys {
-            let serialized: String = conn.get(&key).await.map_err(|e| {
-                log::error!("Redis get operation failed for key {}: {}", key, e);
+            let keys: Vec<String> = conn.keys(&pattern).await.map_err(|e| {
+                log::error!("Redis keys operation failed: {}", e);
                 DataAccessError::RedisDataBaseError(e)
             })?;
-
-            let redis_snap: RedisSnap = serde_json::from_str(&serialized).map_err(|e| {
-                log::error!("Failed to deserialize snap from key {}: {}", key, e);
-                DataAccessError::InvalidInput(e.to_string())
-            })?;
-
-            snaps.push(Self::form_snap_from_redis_snap(&redis_snap)?);
+            log::debug!("Found {} matching keys", keys.len());
+
+            for key in keys {
+                let serialized: Option<String> = conn.get(&key).await.map_err(|e| {
+                    log::error!("Redis get operation failed for key {}: {}", key, e);
+                    DataAccessError::RedisDataBaseError(e)
+                })?;
+                // Ключ мог быть удален переносом в основное хранилище
+                let Some(serialized) = serialized else {
+                    continue;
+                };
+
+                let redis_snap: RedisSnap = serde_json::from_str(&serialized).map_err(|e| {
+                    log::error!("Failed to deserialize snap from key {}: {}", key, e);
+                    DataAccessError::InvalidInput(e.to_string())
+                })?;
+
+                snaps.push(Self::form_snap_from_redis_snap(&redis_snap)?);
+            }
         }
 
+        snaps.retain(|snap| {
+            snap_datetime(snap).is_some_and(|datetime| *from <= datetime && datetime <= *to)
+        });
+        snaps.sort_by_key(snap_datetime);
+
         log::info!("Retrieved {} snaps", snaps.len());
         Ok(snaps)
     }
@@ -166,14 +345,27 @@ impl RedisSnapRepo {
         );
         let mut conn = self.get_connection().await?;
 
-        let datetime = format!("{} {}", snap.date, snap.time);
-        let key = format!("snap:{}:{}", snap.gos_num, datetime);
+        let key = Self::snap_key(snap);
         log::debug!("Deleting key: {}", key);
 
-        let _: usize = conn.del(&key).await.map_err(|e| {
-            log::error!("Redis delete operation failed: {}", e);
-            DataAccessError::RedisDataBaseError(e)
-        })?;
+        let mut pipe = redis::pipe();
+        if let Some(marker) = Self::idempotency_marker_key(snap) {
+            pipe.del(marker).ignore();
+        }
+        let _: () = pipe
+            .atomic()
+            .lrem(SNAP_BUFFER_KEY, 1, &key)
+            .ignore()
+            .del(&key)
+            .ignore()
+            .del(Self::seen_marker_key(&key))
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                log::error!("Redis delete operation failed: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
 
         log::info!("Successfully deleted snap");
         Ok(())
@@ -228,7 +420,8 @@ impl SnapRepoTransfer for RedisSnapRepo {
 
     async fn clear_snaps(&self) -> Result<(), DataAccessError> {
         log::info!("Clearing all snaps from Redis");
-        let keys = self.get_keys_by_pattern("snap:*").await?;
+        let mut keys = self.get_keys_by_pattern("snap:*").await?;
+        keys.extend(self.get_keys_by_pattern("snap_seen:*").await?);
 
         if keys.is_empty() {
             log::debug!("No snaps found to clear");
@@ -238,6 +431,11 @@ impl SnapRepoTransfer for RedisSnapRepo {
         log::debug!("Deleting {} snap keys", keys.len());
         let mut conn = self.get_connection().await?;
 
+        let _: usize = conn.del(SNAP_BUFFER_KEY).await.map_err(|e| {
+            log::error!("Failed to delete snap buffer: {}", e);
+            DataAccessError::RedisDataBaseError(e)
+        })?;
+
         let _: usize = conn.del(keys).await.map_err(|e| {
             log::error!("Failed to delete snaps: {}", e);
             DataAccessError::RedisDataBaseError(e)
@@ -246,6 +444,116 @@ impl SnapRepoTransfer for RedisSnapRepo {
         log::info!("Successfully cleared all snaps");
         Ok(())
     }
+
+    async fn buffered_snaps_count(&self) -> Result<usize, DataAccessError> {
+        let mut conn = self.get_connection().await?;
+
+        let count: usize = conn.llen(SNAP_BUFFER_KEY).await.map_err(|e| {
+            log::error!("Failed to get snap buffer length: {}", e);
+            DataAccessError::RedisDataBaseError(e)
+        })?;
+
+        log::debug!("Snap buffer size: {}", count);
+        Ok(count)
+    }
+
+    async fn peek_buffered_snaps(&self, limit: usize) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!("Reading up to {} buffered snaps", limit);
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+        let mut conn = self.get_connection().await?;
+
+        let keys: Vec<String> = conn
+            .lrange(SNAP_BUFFER_KEY, 0, limit as isize - 1)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to read snap buffer: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(&keys)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get buffered snaps: {}", e);
+                DataAccessError::RedisDataBaseError(e)
+            })?;
+
+        let mut snaps = Vec::with_capacity(keys.len());
+        for (key, value) in keys.iter().zip(values) {
+            match value {
+                Some(serialized) => {
+                    let redis_snap: RedisSnap = serde_json::from_str(&serialized).map_err(|e| {
+                        log::error!("Failed to deserialize snap with key {}: {}", key, e);
+                        DataAccessError::InvalidInput(e.to_string())
+                    })?;
+                    snaps.push(Self::form_snap_from_redis_snap(&redis_snap)?);
+                }
+                // Снимок удален мимо буфера, ключ в очереди больше не нужен
+                None => {
+                    log::warn!("Buffered snap {} not found, dropping it from buffer", key);
+                    let _: usize = conn.lrem(SNAP_BUFFER_KEY, 1, key).await.map_err(|e| {
+                        log::error!("Failed to drop key from snap buffer: {}", e);
+                        DataAccessError::RedisDataBaseError(e)
+                    })?;
+                }
+            }
+        }
+
+        log::info!("Read {} buffered snaps", snaps.len());
+        Ok(snaps)
+    }
+
+    async fn remove_buffered_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        log::info!("Removing {} snaps from buffer", snaps.len());
+        if snaps.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for snap in snaps {
+            let key = Self::snap_key(snap);
+            pipe.lrem(SNAP_BUFFER_KEY, 1, &key).ignore();
+            pipe.del(&key).ignore();
+        }
+
+        let mut conn = self.get_connection().await?;
+        let _: () = pipe.query_async(&mut conn).await.map_err(|e| {
+            log::error!("Failed to remove buffered snaps: {}", e);
+            DataAccessError::RedisDataBaseError(e)
+        })?;
+
+        log::info!("Successfully removed {} snaps from buffer", snaps.len());
+        Ok(())
+    }
+    async fn dead_letter_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        log::warn!("Moving {} rejected snaps to dead letter queue", snaps.len());
+        if snaps.is_empty() {
+            return Ok(());
+        }
+
+        let script = Script::new(DEAD_LETTER_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        for snap in snaps {
+            invocation.key(Self::snap_key(snap));
+        }
+        invocation.arg(SNAP_BUFFER_KEY).arg(SNAP_DEAD_LETTER_KEY);
+
+        let mut conn = self.get_connection().await?;
+        let _: i32 = invocation.invoke_async(&mut conn).await.map_err(|e| {
+            log::error!("Failed to move snaps to dead letter queue: {}", e);
+            DataAccessError::RedisDataBaseError(e)
+        })?;
+
+        log::info!("Moved {} snaps to dead letter queue", snaps.len());
+        Ok(())
+    }
 }
 
 impl VolatileSnapRepo for RedisSnapRepo {}
@@ -1,12 +1,48 @@
 use crate::error::DataAccessError;
+use crate::repositories::{day_period, snap_datetime};
 use crate::repositories_traits::{SnapRepository, TandemRepoForTransfer, VolatileSnapRepo};
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use models::Snap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+const TRANSFER_BATCH_SIZE: usize = 500;
 
 pub struct TandemSnapRepo {
     threshold: usize,
+    flush_interval: Duration,
     main_storage: Box<dyn VolatileSnapRepo>,
     extra_storage: Box<dyn VolatileSnapRepo>,
+    transfer_lock: Mutex<()>,
+    flush_requested: Notify,
+}
+
+// Фоновая задача переноса снимков из буфера в основное хранилище
+pub struct TandemFlushWorker {
+    shutdown: Arc<Notify>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl TandemFlushWorker {
+    // Останавливает задачу после последнего переноса оставшихся в буфере снимков
+    pub async fn shutdown(&self) {
+        let Some(handle) = self.handle.lock().await.take() else {
+            log::debug!("Tandem flush worker already stopped");
+            return;
+        };
+
+        log::info!("Stopping tandem flush worker");
+        self.shutdown.notify_one();
+        if let Err(e) = handle.await {
+            log::error!("Tandem flush worker failed: {}", e);
+        }
+        log::info!("Tandem flush worker stopped");
+    }
 }
 
 impl TandemSnapRepo {
@@ -14,11 +50,14 @@ impl TandemSnapRepo {
         main_storage: Box<dyn VolatileSnapRepo + Send + Sync>,
         extra_storage: Box<dyn VolatileSnapRepo + Send + Sync>,
     ) -> Self {
-        log::info!("Creating TandemSnapRepo with default threshold (5)");
+        log::info!("Creating TandemSnapRepo with default threshold (5) and flush interval (30s)");
         TandemSnapRepo {
             main_storage,
             extra_storage,
             threshold: 5,
+            flush_interval: Duration::from_secs(30),
+            transfer_lock: Mutex::new(()),
+            flush_requested: Notify::new(),
         }
     }
 
@@ -28,49 +67,156 @@ impl TandemSnapRepo {
         self
     }
 
-    async fn check_and_transfer(&self) -> Result<(), DataAccessError> {
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        log::info!("Setting new flush interval: {:?}", flush_interval);
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    // Перенос выполняется только в фоновой задаче: по таймеру, чтобы редко
+    // пополняемый буфер не хранил снимки бесконечно, и по заполнению буфера
+    pub fn spawn_flush_worker(repo: Arc<Self>) -> TandemFlushWorker {
+        log::info!(
+            "Starting tandem flush worker (interval: {:?}, threshold: {})",
+            repo.flush_interval,
+            repo.threshold
+        );
+        let shutdown = Arc::new(Notify::new());
+        let stop = shutdown.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(repo.flush_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            ticker.tick().await;
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => log::debug!("Flush timer fired"),
+                    _ = repo.flush_requested.notified() => log::debug!("Flush requested by threshold"),
+                    _ = stop.notified() => {
+                        log::info!("Draining snap buffer before shutdown");
+                        if let Err(e) = repo.transfer().await {
+                            log::error!("Final transfer failed: {}", e);
+                        }
+                        break;
+                    }
+                }
+
+                match repo.extra_storage.buffered_snaps_count().await {
+                    Ok(0) => log::debug!("Snap buffer is empty, nothing to flush"),
+                    Ok(count) => {
+                        log::info!("Flushing {} buffered snaps", count);
+                        if let Err(e) = repo.transfer().await {
+                            log::error!("Background transfer failed: {}", e);
+                        }
+                    }
+                    Err(e) => log::error!("Failed to get snap buffer size: {}", e),
+                }
+            }
+        });
+
+        TandemFlushWorker {
+            shutdown,
+            handle: Mutex::new(Some(handle)),
+        }
+    }
+
+    // Отвергнутый снимок не должен блокировать перенос всего пакета: он уходит
+    // в очередь отвергнутых, остальные переносятся. При недоступности хранилища
+    // из буфера удаляются только уже перенесенные снимки
+    async fn transfer_by_one(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        let mut transferred = Vec::with_capacity(snaps.len());
+        let mut rejected = Vec::new();
+
+        for snap in snaps {
+            match self.main_storage.insert_snap(snap).await {
+                Ok(_) => transferred.push(snap.clone()),
+                Err(e) if e.is_rejected_data() => {
+                    log::error!("Snap rejected by main_storage ({}): {:?}", e, snap);
+                    rejected.push(snap.clone());
+                }
+                Err(e) => {
+                    self.extra_storage
+                        .remove_buffered_snaps(&transferred)
+                        .await?;
+                    self.extra_storage.dead_letter_snaps(&rejected).await?;
+                    return Err(e);
+                }
+            }
+        }
+
+        self.extra_storage
+            .remove_buffered_snaps(&transferred)
+            .await?;
+        self.extra_storage.dead_letter_snaps(&rejected).await
+    }
+
+    async fn request_flush_if_full(&self) {
         log::debug!(
             "Checking if transfer is needed (threshold: {})",
             self.threshold
         );
-        let count = self.extra_storage.get_all_snaps().await?.len();
-        log::debug!("Current extra_storage size: {}", count);
-
-        if count >= self.threshold {
-            log::info!(
-                "Threshold reached ({} >= {}), initiating transfer",
-                count,
-                self.threshold
-            );
-            self.transfer().await?;
+        match self.extra_storage.buffered_snaps_count().await {
+            Ok(count) if count >= self.threshold => {
+                log::info!(
+                    "Threshold reached ({} >= {}), requesting transfer",
+                    count,
+                    self.threshold
+                );
+                self.flush_requested.notify_one();
+            }
+            Ok(count) => log::debug!("Current extra_storage size: {}", count),
+            Err(e) => log::error!("Failed to get snap buffer size: {}", e),
         }
-        Ok(())
     }
 }
 
 #[async_trait]
 impl TandemRepoForTransfer for TandemSnapRepo {
+    // Из буфера удаляются только перенесенные снимки, поступившие во время
+    // переноса остаются до следующего. Повторный перенос после сбоя безопасен,
+    // так как основное хранилище отбрасывает уже сохраненные снимки
     async fn transfer(&self) -> Result<(), DataAccessError> {
+        let Ok(_guard) = self.transfer_lock.try_lock() else {
+            log::debug!("Transfer already in progress, skipping");
+            return Ok(());
+        };
         log::info!("Starting transfer from extra_storage to main_storage");
 
-        log::debug!("Getting all snaps from extra_storage");
-        let snaps = self.extra_storage.get_all_snaps().await?;
-        log::debug!("Found {} snaps to transfer", snaps.len());
+        let mut transferred = 0;
+        loop {
+            let snaps = self
+                .extra_storage
+                .peek_buffered_snaps(TRANSFER_BATCH_SIZE)
+                .await?;
+            if snaps.is_empty() {
+                break;
+            }
+            log::debug!("Transferring {} snaps", snaps.len());
 
-        log::info!("Inserting {} snaps into main_storage", snaps.len());
-        let _ = self.main_storage.insert_snaps(&snaps).await?;
+            match self.main_storage.insert_snap_batch(&snaps).await {
+                Ok(_) => self.extra_storage.remove_buffered_snaps(&snaps).await?,
+                Err(e) if e.is_rejected_data() => {
+                    log::warn!("Batch rejected ({}), transferring snaps one by one", e);
+                    self.transfer_by_one(&snaps).await?;
+                }
+                Err(e) => return Err(e),
+            }
+            transferred += snaps.len();
 
-        log::info!("Clearing extra_storage");
-        self.extra_storage.clear_snaps().await?;
+            if snaps.len() < TRANSFER_BATCH_SIZE {
+                break;
+            }
+        }
 
-        log::info!("Transfer completed successfully");
+        log::info!("Transfer completed successfully ({} snaps)", transferred);
         Ok(())
     }
 }
 
 #[async_trait]
 impl SnapRepository for TandemSnapRepo {
-    async fn insert_snap(&self, snap: &Snap) -> Result<(), DataAccessError> {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
         log::info!(
             "Inserting snap into extra_storage (vehicle: {}, date: {}, time: {})",
             snap.gos_num,
@@ -78,15 +224,20 @@ impl SnapRepository for TandemSnapRepo {
             snap.time
         );
 
-        let result = self.extra_storage.insert_snap(snap).await;
+        let inserted = self.extra_storage.insert_snap(snap).await?;
+        self.request_flush_if_full().await;
+        Ok(inserted)
+    }
 
-        log::debug!("Checking transfer conditions after insert");
-        if let Err(e) = self.check_and_transfer().await {
-            log::error!("Failed to check/transfer after insert: {}", e);
-            return Err(e);
-        }
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!(
+            "Inserting batch of {} snaps into extra_storage",
+            snaps.len()
+        );
 
-        result
+        let inserted = self.extra_storage.insert_snap_batch(snaps).await?;
+        self.request_flush_if_full().await;
+        Ok(inserted)
     }
 
     async fn get_car_snaps_by_date(
@@ -96,21 +247,50 @@ impl SnapRepository for TandemSnapRepo {
     ) -> Result<Vec<Snap>, DataAccessError> {
         log::info!("Getting snaps for vehicle {} on date {}", gos_number, date);
 
+        let (from, to) = day_period(date)?;
+        self.get_car_snaps_by_period(gos_number, &from, &to).await
+    }
+
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        log::info!(
+            "Getting snaps for vehicle {} from {} to {}",
+            gos_number,
+            from,
+            to
+        );
+
         log::debug!("Querying main_storage");
         let mut snaps = self
             .main_storage
-            .get_car_snaps_by_date(gos_number, date)
+            .get_car_snaps_by_period(gos_number, from, to)
             .await?;
         log::debug!("Found {} snaps in main_storage", snaps.len());
 
         log::debug!("Querying extra_storage");
         let mut extra_snaps = self
             .extra_storage
-            .get_car_snaps_by_date(gos_number, date)
+            .get_car_snaps_by_period(gos_number, from, to)
             .await?;
         log::debug!("Found {} snaps in extra_storage", extra_snaps.len());
 
         snaps.append(&mut extra_snaps);
+
+        // Повтор уже перенесенного снимка может оказаться в обоих хранилищах
+        let mut seen = HashSet::new();
+        snaps.retain(|snap| {
+            seen.insert((
+                snap.camera.id,
+                snap.gos_num.clone(),
+                snap.date.clone(),
+                snap.time.clone(),
+            ))
+        });
+        snaps.sort_by_key(snap_datetime);
         log::info!("Returning combined result of {} snaps", snaps.len());
         Ok(snaps)
     }
@@ -1,37 +1,56 @@
 use super::error::DataAccessError;
 use async_trait::async_trait;
-use models::{Camera, Car, Document, Location, Snap, TrackInfo, User};
+use chrono::NaiveDateTime;
+use models::{
+    BoundingBox, Camera, Car, CarFilter, DetailedCar, Document, Location, NearbyCamera,
+    OwnershipRecord, Page, Paged, RoadSection, Snap, SpeedViolation, TrackInfo, User,
+    WatchlistAlert, WatchlistEntry,
+};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
-    async fn get_user_by_auth_info(
+    async fn get_user_with_pswd_hash(
         &self,
         email: &str,
-        pswd: &str,
-    ) -> Result<Option<User>, DataAccessError>;
+    ) -> Result<Option<(User, String)>, DataAccessError>;
     async fn get_user_by_passport(
         &self,
         passport: &Document,
     ) -> Result<Option<User>, DataAccessError>;
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, DataAccessError>;
-    async fn insert_user(&self, user: &User, pswd: &str) -> Result<(), DataAccessError>;
+    async fn insert_user(&self, user: &User, pswd_hash: &str) -> Result<(), DataAccessError>;
     async fn update_user_passport(
         &self,
         email: &String,
         passport: &Document,
     ) -> Result<(), DataAccessError>;
+    async fn update_user_pswd_hash(
+        &self,
+        email: &str,
+        pswd_hash: &str,
+    ) -> Result<(), DataAccessError>;
+}
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn insert_session(
+        &self,
+        refresh_token: &str,
+        email: &str,
+        ttl_secs: u64,
+    ) -> Result<(), DataAccessError>;
+    async fn take_session(&self, refresh_token: &str) -> Result<Option<String>, DataAccessError>;
+    async fn delete_session(&self, refresh_token: &str) -> Result<(), DataAccessError>;
 }
 
 #[async_trait]
 pub trait CarRepository: Send + Sync {
+    // Сортировка по track_time не поддерживается
     async fn get_cars_by_filters(
         &self,
-        firstname: Option<&str>,
-        surname: Option<&str>,
-        lastname: Option<&str>,
-        passport: Option<Document>,
-        gos_num_mask: Option<&str>,
-    ) -> Result<Vec<Car>, DataAccessError>;
+        filter: &CarFilter,
+        page: &Page,
+    ) -> Result<Paged<Car>, DataAccessError>;
     async fn get_car_by_gos_number_mask(
         &self,
         gos_number: &str,
@@ -46,6 +65,20 @@ pub trait CarRepository: Send + Sync {
         &self,
         passport: &Document,
     ) -> Result<Vec<Car>, DataAccessError>;
+    // Автомобили без СТС или ПТС в результат не попадают
+    async fn get_detailed_cars_by_vins(
+        &self,
+        vins: &[String],
+    ) -> Result<Vec<DetailedCar>, DataAccessError>;
+    // Владельцы в порядке регистрации, NotFound если автомобиля нет
+    async fn get_owner_history_by_vin(
+        &self,
+        vin: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError>;
+    async fn get_owner_history_by_gos_num(
+        &self,
+        gos_num: &str,
+    ) -> Result<Vec<OwnershipRecord>, DataAccessError>;
 }
 
 #[async_trait]
@@ -56,6 +89,7 @@ pub trait TrackInfoRepository: Send + Sync {
         user_login: &str,
         route_date: &str,
     ) -> Result<(), DataAccessError>;
+    #[allow(clippy::too_many_arguments)]
     async fn get_tracks_info_by_filters(
         &self,
         firstname: Option<&str>,
@@ -64,7 +98,8 @@ pub trait TrackInfoRepository: Send + Sync {
         passport: Option<Document>,
         gos_num_mask: Option<&str>,
         date: Option<&str>,
-    ) -> Result<Vec<TrackInfo>, DataAccessError>;
+        page: &Page,
+    ) -> Result<Paged<TrackInfo>, DataAccessError>;
     async fn get_track_info_by_date(&self, date: &str) -> Result<Vec<TrackInfo>, DataAccessError>;
     async fn get_track_info_by_car_gos_number_mask(
         &self,
@@ -84,12 +119,22 @@ pub trait TrackInfoRepository: Send + Sync {
 
 #[async_trait]
 pub trait SnapRepository: Send + Sync {
-    async fn insert_snap(&self, snap: &Snap) -> Result<(), DataAccessError>;
+    // false, если снимок уже сохранен и повтор только подтвержден
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError>;
+    // Снимки пакета, сохраненные впервые
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError>;
     async fn get_car_snaps_by_date(
         &self,
         gos_number: &str,
         date: &str,
     ) -> Result<Vec<Snap>, DataAccessError>;
+    // Снимки за период [from, to], упорядоченные по времени снимка
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError>;
 }
 
 #[async_trait]
@@ -97,6 +142,12 @@ pub trait SnapRepoTransfer {
     async fn get_all_snaps(&self) -> Result<Vec<Snap>, DataAccessError>;
     async fn insert_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError>;
     async fn clear_snaps(&self) -> Result<(), DataAccessError>;
+    async fn buffered_snaps_count(&self) -> Result<usize, DataAccessError>;
+    // Снимки остаются в хранилище, пока не будут удалены через remove_buffered_snaps
+    async fn peek_buffered_snaps(&self, limit: usize) -> Result<Vec<Snap>, DataAccessError>;
+    async fn remove_buffered_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError>;
+    // Снимки, отвергнутые основным хранилищем, убираются из буфера для ручного разбора
+    async fn dead_letter_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError>;
 }
 
 #[async_trait]
@@ -112,9 +163,69 @@ pub trait CameraRepository: Send + Sync {
     async fn get_camera_count(&self) -> Result<usize, DataAccessError>;
     async fn get_camera_by_id(&self, id: usize) -> Result<Camera, DataAccessError>;
     async fn get_camera_by_location(&self, location: &Location) -> Result<Camera, DataAccessError>;
+    // k ближайших камер, упорядоченных по расстоянию
+    async fn find_nearest_cameras(
+        &self,
+        location: &Location,
+        k: usize,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError>;
+    // Камеры в радиусе radius_m метров, упорядоченные по расстоянию
+    async fn find_cameras_within(
+        &self,
+        location: &Location,
+        radius_m: f64,
+    ) -> Result<Vec<NearbyCamera>, DataAccessError>;
+    async fn find_cameras_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<Camera>, DataAccessError>;
     async fn get_avg_speed_for_car_at_camera(
         &self,
         gos_num: &str,
         cam_id: usize,
     ) -> Result<f64, DataAccessError>;
+    // Возвращает id новой камеры
+    async fn insert_camera(&self, camera: &Camera) -> Result<usize, DataAccessError>;
+    async fn update_camera(&self, camera: &Camera) -> Result<(), DataAccessError>;
+    // Камера не удаляется: снимки и участки контроля ссылаются на нее
+    async fn decommission_camera(&self, id: usize) -> Result<(), DataAccessError>;
+}
+
+#[async_trait]
+pub trait WatchlistRepository: Send + Sync {
+    // Возвращает id новой записи, автор записи задается логином
+    async fn insert_entry(&self, entry: &WatchlistEntry) -> Result<usize, DataAccessError>;
+    async fn update_entry(&self, entry: &WatchlistEntry) -> Result<(), DataAccessError>;
+    async fn delete_entry(&self, id: usize) -> Result<(), DataAccessError>;
+    async fn get_entry(&self, id: usize) -> Result<Option<WatchlistEntry>, DataAccessError>;
+    async fn get_entries(&self) -> Result<Vec<WatchlistEntry>, DataAccessError>;
+    // None - фиксация уже записана (повторная доставка снимка)
+    async fn insert_alert(&self, alert: &WatchlistAlert) -> Result<Option<usize>, DataAccessError>;
+    async fn get_alerts_by_date(&self, date: &str) -> Result<Vec<WatchlistAlert>, DataAccessError>;
+}
+
+#[async_trait]
+pub trait SpeedViolationRepository: Send + Sync {
+    // None - нарушение уже записано (повторная доставка снимка)
+    async fn insert_violation(
+        &self,
+        violation: &SpeedViolation,
+    ) -> Result<Option<usize>, DataAccessError>;
+    // Нарушения за период [from, to], упорядоченные по времени фиксации
+    async fn get_violations_by_filters(
+        &self,
+        gos_num: Option<&str>,
+        camera_id: Option<usize>,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<SpeedViolation>, DataAccessError>;
+}
+
+#[async_trait]
+pub trait RoadSectionRepository: Send + Sync {
+    // None - участок между этими камерами уже существует
+    async fn insert_section(&self, section: &RoadSection)
+        -> Result<Option<usize>, DataAccessError>;
+    async fn delete_section(&self, id: usize) -> Result<(), DataAccessError>;
+    async fn get_sections(&self) -> Result<Vec<RoadSection>, DataAccessError>;
 }
@@ -3,7 +3,7 @@ use data_access::{
     repositories::postgres::{PgCameraRepo, PG_URL},
     repositories_traits::CameraRepository,
 };
-use models::Location;
+use models::{BoundingBox, Camera, Location};
 
 #[tokio::test]
 async fn test_get_cameras_cnt() {
@@ -86,3 +86,154 @@ async fn test_clickhouse_get_avg_speed_on_camera() {
     println!("{:?}", avg_speed);
     assert!(avg_speed.is_ok())
 }
+
+#[tokio::test]
+async fn test_find_nearest_cameras() {
+    let repo = PgCameraRepo::from(&PG_URL).await.unwrap();
+
+    let cameras = repo
+        .find_nearest_cameras(
+            &Location {
+                latitude: 55.573816,
+                longitude: 37.566005,
+            },
+            5,
+        )
+        .await;
+    println!("{:?}", cameras);
+    assert!(cameras.is_ok())
+}
+
+#[tokio::test]
+async fn test_find_cameras_within() {
+    let repo = PgCameraRepo::from(&PG_URL).await.unwrap();
+
+    let cameras = repo
+        .find_cameras_within(
+            &Location {
+                latitude: 55.573816,
+                longitude: 37.566005,
+            },
+            5000.,
+        )
+        .await;
+    println!("{:?}", cameras);
+    assert!(cameras.is_ok())
+}
+
+#[tokio::test]
+async fn test_find_cameras_in_bbox() {
+    let repo = PgCameraRepo::from(&PG_URL).await.unwrap();
+
+    let cameras = repo
+        .find_cameras_in_bbox(&BoundingBox {
+            south_west: Location {
+                latitude: 55.5,
+                longitude: 37.5,
+            },
+            north_east: Location {
+                latitude: 55.9,
+                longitude: 37.8,
+            },
+        })
+        .await;
+    println!("{:?}", cameras);
+    assert!(cameras.is_ok())
+}
+
+#[tokio::test]
+async fn test_clickhouse_find_nearest_cameras() {
+    let repo = ClickHouseCameraRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let cameras = repo
+        .find_nearest_cameras(
+            &Location {
+                latitude: 55.573816,
+                longitude: 37.566005,
+            },
+            5,
+        )
+        .await;
+    println!("{:?}", cameras);
+    assert!(cameras.is_ok())
+}
+
+#[tokio::test]
+async fn test_clickhouse_find_cameras_within() {
+    let repo = ClickHouseCameraRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let cameras = repo
+        .find_cameras_within(
+            &Location {
+                latitude: 55.573816,
+                longitude: 37.566005,
+            },
+            5000.,
+        )
+        .await;
+    println!("{:?}", cameras);
+    assert!(cameras.is_ok())
+}
+
+#[tokio::test]
+async fn test_clickhouse_find_cameras_in_bbox() {
+    let repo = ClickHouseCameraRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let cameras = repo
+        .find_cameras_in_bbox(&BoundingBox {
+            south_west: Location {
+                latitude: 55.5,
+                longitude: 37.5,
+            },
+            north_east: Location {
+                latitude: 55.9,
+                longitude: 37.8,
+            },
+        })
+        .await;
+    println!("{:?}", cameras);
+    assert!(cameras.is_ok())
+}
+
+fn new_camera() -> Camera {
+    Camera {
+        id: 0,
+        is_radar: false,
+        location: Location {
+            latitude: 55.7,
+            longitude: 37.6,
+        },
+        speed_limit: Some(60),
+        install_date: Some("01.01.2024".to_string()),
+        is_active: true,
+    }
+}
+
+#[tokio::test]
+async fn test_insert_update_decommission_camera() {
+    let repo = PgCameraRepo::from(&PG_URL).await.unwrap();
+
+    let id = repo.insert_camera(&new_camera()).await.unwrap();
+    let camera = Camera {
+        id,
+        speed_limit: Some(40),
+        ..new_camera()
+    };
+    assert!(repo.update_camera(&camera).await.is_ok());
+    assert!(repo.decommission_camera(id).await.is_ok());
+    assert!(!repo.get_camera_by_id(id).await.unwrap().is_active);
+}
+
+#[tokio::test]
+async fn test_clickhouse_insert_update_decommission_camera() {
+    let repo = ClickHouseCameraRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let id = repo.insert_camera(&new_camera()).await.unwrap();
+    let camera = Camera {
+        id,
+        speed_limit: Some(40),
+        ..new_camera()
+    };
+    assert!(repo.update_camera(&camera).await.is_ok());
+    assert!(repo.decommission_camera(id).await.is_ok());
+}
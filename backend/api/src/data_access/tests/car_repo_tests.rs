@@ -1,11 +1,12 @@
 use data_access::{
+    error::DataAccessError,
     repositories::{
         clickhouse::{ClickHouseCarRepo, CLICKHOUSE_URL},
         postgres::{PgCarRepo, PG_URL},
     },
     repositories_traits::CarRepository,
 };
-use models::Document;
+use models::{CarFilter, Document, Page, SortKey};
 
 #[tokio::test]
 async fn test_get_cars_by_gos_num_mask() {
@@ -46,7 +47,14 @@ async fn test_get_cars_by_filter1() {
     let repo = PgCarRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_cars_by_filters(None, Some("Дроздов"), None, None, Some("*5****77"))
+        .get_cars_by_filters(
+            &CarFilter {
+                surname: Some("Дроздов".to_string()),
+                gos_num_mask: Some("*5****77".to_string()),
+                ..CarFilter::default()
+            },
+            &Page::default(),
+        )
         .await;
 
     println!("{:#?}", res);
@@ -58,7 +66,13 @@ async fn test_get_cars_by_filter2() {
     let repo = PgCarRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_cars_by_filters(None, Some("Дроздов"), None, None, None)
+        .get_cars_by_filters(
+            &CarFilter {
+                surname: Some("Дроздов".to_string()),
+                ..CarFilter::default()
+            },
+            &Page::default(),
+        )
         .await;
 
     println!("{:#?}", res);
@@ -70,7 +84,14 @@ async fn test_get_cars_by_filter3() {
     let repo = PgCarRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_cars_by_filters(Some("Парамон"), Some("Артемьева"), None, None, None)
+        .get_cars_by_filters(
+            &CarFilter {
+                firstname: Some("Парамон".to_string()),
+                surname: Some("Артемьева".to_string()),
+                ..CarFilter::default()
+            },
+            &Page::default(),
+        )
         .await;
 
     println!("{:#?}", res);
@@ -81,12 +102,162 @@ async fn test_get_cars_by_filter3() {
 async fn test_get_cars_by_filter4() {
     let repo = PgCarRepo::from(&PG_URL).await.unwrap();
 
-    let res = repo.get_cars_by_filters(None, None, None, None, None).await;
+    let res = repo
+        .get_cars_by_filters(&CarFilter::default(), &Page::default())
+        .await;
 
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
 
+#[tokio::test]
+async fn test_get_cars_by_filter_page() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+    let page = Page {
+        offset: 0,
+        limit: 2,
+        sort: SortKey::owner_surname,
+    };
+
+    let res = repo
+        .get_cars_by_filters(
+            &CarFilter {
+                gos_num_mask: Some("*******".to_string()),
+                ..CarFilter::default()
+            },
+            &page,
+        )
+        .await
+        .unwrap();
+
+    assert!(res.items.len() <= 2);
+    assert!(res.total >= res.items.len());
+}
+
+#[tokio::test]
+async fn test_get_cars_by_filter_page_out_of_range() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let all = repo
+        .get_cars_by_filters(&CarFilter::default(), &Page::default())
+        .await
+        .unwrap();
+    let page = Page {
+        offset: all.total,
+        ..Page::default()
+    };
+    let res = repo
+        .get_cars_by_filters(&CarFilter::default(), &page)
+        .await
+        .unwrap();
+
+    assert!(res.items.is_empty());
+    assert_eq!(res.total, all.total);
+}
+
+#[tokio::test]
+async fn test_get_cars_by_car_filter() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+    let filter = CarFilter {
+        mark: Some("toyota".to_string()),
+        model: Some("CAMRY".to_string()),
+        year_from: Some(2010),
+        year_to: Some(2020),
+        ..CarFilter::default()
+    };
+
+    let res = repo
+        .get_cars_by_filters(&filter, &Page::default())
+        .await
+        .unwrap();
+
+    assert!(res
+        .items
+        .iter()
+        .all(|car| car.mark == "Toyota" && car.model == "Camry"));
+    assert!(res
+        .items
+        .iter()
+        .all(|car| (2010..=2020).contains(&car.year)));
+}
+
+#[tokio::test]
+async fn test_get_cars_by_vin_prefix() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let all = repo
+        .get_cars_by_filters(&CarFilter::default(), &Page::default())
+        .await
+        .unwrap();
+    let prefix = all.items[0].vin[..5].to_string();
+    let res = repo
+        .get_cars_by_filters(
+            &CarFilter {
+                vin: Some(prefix.clone()),
+                ..CarFilter::default()
+            },
+            &Page::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!res.items.is_empty());
+    assert!(res.items.iter().all(|car| car.vin.starts_with(&prefix)));
+}
+
+#[tokio::test]
+async fn test_get_cars_by_fio_ignore_case() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let exact = repo
+        .get_car_by_owner_fio(None, Some("Дроздов"), None)
+        .await
+        .unwrap();
+    let res = repo
+        .get_car_by_owner_fio(None, Some("дРОЗДОВ"), None)
+        .await
+        .unwrap();
+
+    assert!(!exact.is_empty());
+    assert_eq!(res.len(), exact.len());
+}
+
+#[tokio::test]
+async fn test_get_cars_by_fuzzy_fio() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let res = repo
+        .get_car_by_owner_fio(None, Some("Дроздав"), None)
+        .await
+        .unwrap();
+
+    assert!(res.iter().any(|car| car.owner_fio.0 == "Дроздов"));
+}
+
+#[tokio::test]
+async fn test_get_cars_by_fio_ranked() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+    let filter = CarFilter {
+        surname: Some("Дроздов".to_string()),
+        ..CarFilter::default()
+    };
+
+    let res = repo
+        .get_cars_by_filters(&filter, &Page::default())
+        .await
+        .unwrap();
+
+    let exact = res
+        .items
+        .iter()
+        .take_while(|car| car.owner_fio.0 == "Дроздов")
+        .count();
+    assert!(exact > 0);
+    assert!(res.items[exact..]
+        .iter()
+        .all(|car| car.owner_fio.0 != "Дроздов"));
+}
+
 #[tokio::test]
 async fn test_clickhouse_get_cars_by_gos_num_mask() {
     let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
@@ -120,3 +291,158 @@ async fn test_clickhouse_get_cars_by_fio() {
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
+
+#[tokio::test]
+async fn test_get_owner_history_unknown_car() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let res = repo.get_owner_history_by_vin("XTA00000000000000").await;
+
+    assert!(matches!(res, Err(DataAccessError::NotFoundError(_))));
+}
+
+#[tokio::test]
+async fn test_get_owner_history_by_gos_num() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let cars = repo.get_car_by_gos_number_mask("*5****77").await.unwrap();
+    let res = repo.get_owner_history_by_gos_num(&cars[0].gos_num).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok())
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_owner_history_unknown_car() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let res = repo.get_owner_history_by_vin("XTA00000000000000").await;
+
+    assert!(matches!(res, Err(DataAccessError::NotFoundError(_))));
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_owner_history_by_gos_num() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let cars = repo.get_car_by_gos_number_mask("*5****77").await.unwrap();
+    let res = repo.get_owner_history_by_gos_num(&cars[0].gos_num).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok())
+}
+
+#[tokio::test]
+async fn test_get_detailed_cars_by_vins() {
+    let repo = PgCarRepo::from(&PG_URL).await.unwrap();
+
+    let cars = repo.get_car_by_gos_number_mask("*5****77").await.unwrap();
+    let vins: Vec<String> = cars.iter().map(|car| car.vin.clone()).collect();
+    let res = repo.get_detailed_cars_by_vins(&vins).await.unwrap();
+
+    assert_eq!(res.len(), cars.len());
+    assert!(res.iter().all(|car| vins.contains(&car.car.vin)));
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_detailed_cars_by_vins() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let cars = repo.get_car_by_gos_number_mask("*5****77").await.unwrap();
+    let vins: Vec<String> = cars.iter().map(|car| car.vin.clone()).collect();
+    let res = repo.get_detailed_cars_by_vins(&vins).await.unwrap();
+
+    assert_eq!(res.len(), cars.len());
+    assert!(res.iter().all(|car| vins.contains(&car.car.vin)));
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_cars_by_filter_page() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+    let page = Page {
+        offset: 0,
+        limit: 2,
+        sort: SortKey::owner_surname,
+    };
+
+    let res = repo
+        .get_cars_by_filters(
+            &CarFilter {
+                gos_num_mask: Some("*******".to_string()),
+                ..CarFilter::default()
+            },
+            &page,
+        )
+        .await
+        .unwrap();
+
+    assert!(res.items.len() <= 2);
+    assert!(res.total >= res.items.len());
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_cars_by_car_filter() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+    let filter = CarFilter {
+        mark: Some("toyota".to_string()),
+        model: Some("CAMRY".to_string()),
+        year_from: Some(2010),
+        year_to: Some(2020),
+        ..CarFilter::default()
+    };
+
+    let res = repo
+        .get_cars_by_filters(&filter, &Page::default())
+        .await
+        .unwrap();
+
+    assert!(res
+        .items
+        .iter()
+        .all(|car| car.mark == "Toyota" && car.model == "Camry"));
+    assert!(res
+        .items
+        .iter()
+        .all(|car| (2010..=2020).contains(&car.year)));
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_cars_by_fio_ignore_case() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    let exact = repo
+        .get_car_by_owner_fio(None, Some("Дроздов"), None)
+        .await
+        .unwrap();
+    let res = repo
+        .get_car_by_owner_fio(None, Some("дРОЗДОВ"), None)
+        .await
+        .unwrap();
+
+    assert!(!exact.is_empty());
+    assert_eq!(res.len(), exact.len());
+}
+
+#[tokio::test]
+async fn test_clickhouse_get_cars_by_fio_ranked() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+    let filter = CarFilter {
+        surname: Some("Дроздов".to_string()),
+        ..CarFilter::default()
+    };
+
+    let res = repo
+        .get_cars_by_filters(&filter, &Page::default())
+        .await
+        .unwrap();
+
+    let exact = res
+        .items
+        .iter()
+        .take_while(|car| car.owner_fio.0 == "Дроздов")
+        .count();
+    assert!(exact > 0);
+    assert!(res.items[exact..]
+        .iter()
+        .all(|car| car.owner_fio.0 != "Дроздов"));
+}
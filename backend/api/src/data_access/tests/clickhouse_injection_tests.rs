@@ -0,0 +1,175 @@
+use data_access::{
+    repositories::clickhouse::{
+        ClickHouseCameraRepo, ClickHouseCarRepo, ClickHouseSnapRepo, ClickHouseTrackInfoRepo,
+        ClickHouseUserRepo, CLICKHOUSE_URL,
+    },
+    repositories_traits::{
+        CameraRepository, CarRepository, SnapRepository, TrackInfoRepository, UserRepository,
+    },
+};
+use models::{CarFilter, Page};
+
+const HOSTILE_INPUTS: &[&str] = &[
+    "O'Brien",
+    "' OR 1=1 --",
+    "' OR '1'='1",
+    "Дроздов' OR ''='",
+    "'; DROP TABLE AppUser; --",
+    "\\' OR 1=1 --",
+    "%' OR 1=1 --",
+    "? OR 1=1",
+    "') UNION ALL SELECT * FROM AppUser --",
+    "\0",
+];
+
+#[tokio::test]
+async fn test_clickhouse_car_search_by_fio_hostile_input() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo
+            .get_car_by_owner_fio(Some(input), Some(input), Some(input))
+            .await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_car_search_by_filters_hostile_input() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo
+            .get_cars_by_filters(
+                &CarFilter {
+                    surname: Some(input.to_string()),
+                    gos_num_mask: Some(input.to_string()),
+                    vin: Some(input.to_string()),
+                    mark: Some(input.to_string()),
+                    color: Some(input.to_string()),
+                    ..CarFilter::default()
+                },
+                &Page::default(),
+            )
+            .await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().items.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_car_search_by_gos_num_mask_hostile_input() {
+    let repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo.get_car_by_gos_number_mask(input).await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_track_info_search_by_fio_hostile_input() {
+    let repo = ClickHouseTrackInfoRepo::from(&CLICKHOUSE_URL)
+        .await
+        .unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo
+            .get_track_info_by_user_fio(Some(input), Some(input), Some(input))
+            .await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_track_info_search_by_filters_hostile_input() {
+    let repo = ClickHouseTrackInfoRepo::from(&CLICKHOUSE_URL)
+        .await
+        .unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo
+            .get_tracks_info_by_filters(
+                Some(input),
+                None,
+                Some(input),
+                None,
+                Some(input),
+                None,
+                &Page::default(),
+            )
+            .await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().items.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_track_info_search_by_date_hostile_input() {
+    let repo = ClickHouseTrackInfoRepo::from(&CLICKHOUSE_URL)
+        .await
+        .unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo
+            .get_track_info_by_date(&format!("22.04.2025{}", input))
+            .await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_user_get_by_email_hostile_input() {
+    let repo = ClickHouseUserRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo.get_user_by_email(input).await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_snaps_by_date_hostile_input() {
+    let repo = ClickHouseSnapRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo.get_car_snaps_by_date(input, "22.04.2025").await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.unwrap().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_avg_speed_hostile_input() {
+    let repo = ClickHouseCameraRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let res = repo.get_avg_speed_for_car_at_camera(input, 1).await;
+        println!("{:?}: {:#?}", input, res);
+        assert!(res.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_clickhouse_tables_survive_hostile_input() {
+    let user_repo = ClickHouseUserRepo::from(&CLICKHOUSE_URL).await.unwrap();
+    let car_repo = ClickHouseCarRepo::from(&CLICKHOUSE_URL).await.unwrap();
+
+    for input in HOSTILE_INPUTS {
+        let _ = car_repo.get_car_by_owner_fio(Some(input), None, None).await;
+        let _ = user_repo.get_user_by_email(input).await;
+    }
+
+    let res = user_repo.get_user_by_email("exist@exist.com").await;
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    let res = car_repo.get_car_by_gos_number_mask("*5****77").await;
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+}
@@ -0,0 +1,34 @@
+use data_access::{
+    repositories::redis::{RedisSessionRepo, REDIS_URL},
+    repositories_traits::SessionRepository,
+};
+
+#[tokio::test]
+async fn test_redis_take_session_rotates() {
+    let repo = RedisSessionRepo::from(&REDIS_URL).unwrap();
+    let token = "test_refresh_token_rotates";
+
+    let res = repo.insert_session(token, "email123@example.com", 60).await;
+    assert!(res.is_ok());
+
+    let res = repo.take_session(token).await;
+    assert_eq!(res.unwrap(), Some("email123@example.com".to_string()));
+
+    let res = repo.take_session(token).await;
+    assert_eq!(res.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_redis_delete_session() {
+    let repo = RedisSessionRepo::from(&REDIS_URL).unwrap();
+    let token = "test_refresh_token_delete";
+
+    let res = repo.insert_session(token, "email123@example.com", 60).await;
+    assert!(res.is_ok());
+
+    let res = repo.delete_session(token).await;
+    assert!(res.is_ok());
+
+    let res = repo.take_session(token).await;
+    assert_eq!(res.unwrap(), None);
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use data_access::{
     repositories::{
         clickhouse::{ClickHouseSnapRepo, CLICKHOUSE_URL},
@@ -5,7 +6,7 @@ use data_access::{
         redis::{RedisSnapRepo, REDIS_URL},
         tandem::TandemSnapRepo,
     },
-    repositories_traits::SnapRepository,
+    repositories_traits::{SnapRepoTransfer, SnapRepository},
 };
 use models::{Camera, Location, Snap};
 
@@ -19,6 +20,68 @@ async fn test_pg_get_snaps_by_date() {
     assert!(res.is_ok())
 }
 
+#[tokio::test]
+async fn test_pg_get_snaps_by_period() {
+    let repo = PgSnapRepo::from(&PG_URL).await.unwrap();
+
+    let from = NaiveDateTime::parse_from_str("10.05.2024 22:00", "%d.%m.%Y %H:%M").unwrap();
+    let to = NaiveDateTime::parse_from_str("11.05.2024 06:00", "%d.%m.%Y %H:%M").unwrap();
+    let res = repo.get_car_snaps_by_period("А889МН29", &from, &to).await;
+
+    println!("{:?}", res);
+    assert!(res.is_ok())
+}
+
+#[tokio::test]
+async fn test_redis_get_snaps_by_period_across_midnight() {
+    let repo = RedisSnapRepo::from(&REDIS_URL).unwrap();
+    let mut evening = replay_snap(1, None);
+    evening.time = "23:50".to_string();
+    let mut night = replay_snap(1, None);
+    night.date = "11.10.2020".to_string();
+    night.time = "0:10".to_string();
+
+    let _ = repo.insert_snap(&night).await;
+    let _ = repo.insert_snap(&evening).await;
+
+    let from = NaiveDateTime::parse_from_str("10.10.2020 23:00", "%d.%m.%Y %H:%M").unwrap();
+    let to = NaiveDateTime::parse_from_str("11.10.2020 1:00", "%d.%m.%Y %H:%M").unwrap();
+    let stored = repo.get_car_snaps_by_period("А889МН29", &from, &to).await;
+    let _ = repo.delete_snap(&evening).await;
+    let _ = repo.delete_snap(&night).await;
+
+    let times: Vec<_> = stored.unwrap().into_iter().map(|s| s.time).collect();
+    assert_eq!(times, vec!["23:50", "00:10"]);
+}
+
+#[tokio::test]
+async fn test_redis_buffer_drains_unpadded_time() {
+    let repo = RedisSnapRepo::from(&REDIS_URL).unwrap();
+    let mut snap = replay_snap(1, None);
+    snap.date = "09.10.2020".to_string();
+    snap.time = "8:10".to_string();
+
+    let inserted = repo.insert_snap(&snap).await;
+    let peeked: Vec<Snap> = repo
+        .peek_buffered_snaps(1000)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|s| s.date == snap.date && s.gos_num == snap.gos_num)
+        .collect();
+    let removed = repo.remove_buffered_snaps(&peeked).await;
+    let stored = repo.get_car_snaps_by_date("А889МН29", "09.10.2020").await;
+    let replayed = repo.insert_snap(&snap).await;
+    let _ = repo.delete_snap(&snap).await;
+
+    assert!(inserted.unwrap());
+    assert_eq!(peeked.len(), 1);
+    assert_eq!(peeked[0].time, "08:10");
+    assert!(removed.is_ok());
+    assert!(stored.unwrap().is_empty());
+    assert!(!replayed.unwrap());
+}
+
 #[tokio::test]
 async fn test_pg_insert_snap() {
     let repo = PgSnapRepo::from(&PG_URL).await.unwrap();
@@ -30,11 +93,15 @@ async fn test_pg_insert_snap() {
                 latitude: 12.22222,
                 longitude: 12.22222,
             },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
         },
         speed: Some(70),
         time: "12:12".to_string(),
         date: "10.10.2020".to_string(),
         gos_num: "А889МН29".to_string(),
+        idempotency_key: None,
     };
 
     let res = repo.insert_snap(&_snap).await;
@@ -65,11 +132,15 @@ async fn test_redis_insert_snap() {
                 latitude: 12.22222,
                 longitude: 12.22222,
             },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
         },
         speed: Some(70),
         time: "12:12".to_string(),
         date: "10.10.2020".to_string(),
         gos_num: "А889МН29".to_string(),
+        idempotency_key: None,
     };
 
     let res = repo.insert_snap(&_snap).await;
@@ -107,11 +178,15 @@ async fn test_tandem_insert_snap() {
                 latitude: 12.22222,
                 longitude: 12.22222,
             },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
         },
         speed: Some(70),
         time: "12:12".to_string(),
         date: "10.10.2020".to_string(),
         gos_num: "А889МН29".to_string(),
+        idempotency_key: None,
     };
 
     let res = repo.insert_snap(&_snap).await;
@@ -141,11 +216,15 @@ async fn test_clickhouse_insert_snap() {
                 latitude: 12.22222,
                 longitude: 12.22222,
             },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
         },
         speed: Some(70),
         time: "12:12".to_string(),
         date: "10.10.2020".to_string(),
         gos_num: "А889МН29".to_string(),
+        idempotency_key: None,
     };
 
     let res = repo.insert_snap(&_snap).await;
@@ -154,3 +233,138 @@ async fn test_clickhouse_insert_snap() {
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
+
+fn replay_snap(camera_id: usize, idempotency_key: Option<&str>) -> Snap {
+    Snap {
+        camera: Camera {
+            id: camera_id,
+            is_radar: true,
+            location: Location {
+                latitude: 12.22222,
+                longitude: 12.22222,
+            },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
+        },
+        speed: Some(70),
+        time: "12:13".to_string(),
+        date: "10.10.2020".to_string(),
+        gos_num: "А889МН29".to_string(),
+        idempotency_key: idempotency_key.map(|key| key.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_pg_insert_snap_replay() {
+    let repo = PgSnapRepo::from(&PG_URL).await.unwrap();
+    let snap = replay_snap(1, Some("test-replay-1"));
+
+    let first = repo.insert_snap(&snap).await;
+    let replay = repo.insert_snap(&snap).await;
+    let batch_replay = repo.insert_snap_batch(&[snap.clone(), snap.clone()]).await;
+    let stored = repo.get_car_snaps_by_date("А889МН29", "10.10.2020").await;
+    let _ = repo.delete_snap(&snap).await;
+
+    println!("{:#?}", stored);
+    assert!(first.unwrap());
+    assert!(!replay.unwrap());
+    assert!(batch_replay.unwrap().is_empty());
+    assert_eq!(
+        stored
+            .unwrap()
+            .iter()
+            .filter(|s| s.time == snap.time && s.camera.id == 1)
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_redis_insert_snap_replay() {
+    let repo = RedisSnapRepo::from(&REDIS_URL).unwrap();
+    let snap = replay_snap(1, None);
+
+    let first = repo.insert_snap(&snap).await;
+    let replay = repo.insert_snap(&snap).await;
+    let stored = repo.get_car_snaps_by_date("А889МН29", "10.10.2020").await;
+    let _ = repo.delete_snap(&snap).await;
+
+    println!("{:#?}", stored);
+    assert!(first.unwrap());
+    assert!(!replay.unwrap());
+    assert_eq!(
+        stored
+            .unwrap()
+            .iter()
+            .filter(|s| s.time == snap.time)
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_redis_batch_replay_by_idempotency_key() {
+    let repo = RedisSnapRepo::from(&REDIS_URL).unwrap();
+    let snap = replay_snap(1, Some("test-replay-redis"));
+    let mut resent = snap.clone();
+    resent.time = "12:14".to_string();
+
+    let inserted = repo
+        .insert_snap_batch(&[snap.clone(), resent.clone()])
+        .await;
+    let replayed = repo.insert_snap(&resent).await;
+    let _ = repo.delete_snap(&snap).await;
+    let _ = repo.delete_snap(&resent).await;
+
+    assert_eq!(inserted.unwrap().len(), 1);
+    assert!(!replayed.unwrap());
+}
+
+#[tokio::test]
+async fn test_redis_insert_snap_distinct_cameras() {
+    let repo = RedisSnapRepo::from(&REDIS_URL).unwrap();
+    let first_snap = replay_snap(1, None);
+    let second_snap = replay_snap(2, None);
+
+    let res = repo
+        .insert_snap_batch(&[first_snap.clone(), second_snap.clone()])
+        .await;
+    let stored = repo.get_car_snaps_by_date("А889МН29", "10.10.2020").await;
+    let _ = repo.delete_snap(&first_snap).await;
+    let _ = repo.delete_snap(&second_snap).await;
+
+    println!("{:#?}", stored);
+    assert!(res.is_ok());
+    assert_eq!(
+        stored
+            .unwrap()
+            .iter()
+            .filter(|s| s.time == first_snap.time)
+            .count(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_clickhouse_insert_snap_replay() {
+    let repo = ClickHouseSnapRepo::from(&CLICKHOUSE_URL).await.unwrap();
+    let snap = replay_snap(1, Some("test-replay-1"));
+
+    let first = repo.insert_snap(&snap).await;
+    let replay = repo.insert_snap(&snap).await;
+    let stored = repo.get_car_snaps_by_date("А889МН29", "10.10.2020").await;
+    let _ = repo.delete_snap(&snap).await;
+
+    println!("{:#?}", stored);
+    assert!(first.unwrap());
+    assert!(!replay.unwrap());
+    assert_eq!(
+        stored
+            .unwrap()
+            .iter()
+            .filter(|s| s.time == snap.time && s.camera.id == 1)
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,297 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use data_access::{
+    error::DataAccessError,
+    repositories::tandem::TandemSnapRepo,
+    repositories_traits::{
+        SnapRepoTransfer, SnapRepository, TandemRepoForTransfer, VolatileSnapRepo,
+    },
+};
+use models::{Camera, Location, Snap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+struct MemorySnapRepo {
+    snaps: Arc<Mutex<Vec<Snap>>>,
+    dead_letter: Arc<Mutex<Vec<Snap>>>,
+    rejected_time: Option<String>,
+}
+
+impl MemorySnapRepo {
+    fn len(&self) -> usize {
+        self.snaps.lock().unwrap().len()
+    }
+
+    // Хранилище, отвергающее снимки с указанным временем, как ограничение в БД
+    fn rejecting(time: &str) -> Self {
+        MemorySnapRepo {
+            rejected_time: Some(time.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn check(&self, snap: &Snap) -> Result<(), DataAccessError> {
+        match &self.rejected_time {
+            Some(time) if *time == snap.time => {
+                Err(DataAccessError::InvalidInput("snap rejected".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl SnapRepository for MemorySnapRepo {
+    async fn insert_snap(&self, snap: &Snap) -> Result<bool, DataAccessError> {
+        self.check(snap)?;
+        self.snaps.lock().unwrap().push(snap.clone());
+        Ok(true)
+    }
+
+    async fn insert_snap_batch(&self, snaps: &[Snap]) -> Result<Vec<Snap>, DataAccessError> {
+        snaps.iter().try_for_each(|snap| self.check(snap))?;
+        self.snaps.lock().unwrap().extend_from_slice(snaps);
+        Ok(snaps.to_vec())
+    }
+
+    async fn get_car_snaps_by_date(
+        &self,
+        gos_number: &str,
+        date: &str,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(self
+            .snaps
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.gos_num == gos_number && s.date == date)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_car_snaps_by_period(
+        &self,
+        gos_number: &str,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(self
+            .snaps
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.gos_num == gos_number)
+            .filter(|s| datetime(s) >= *from && datetime(s) <= *to)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SnapRepoTransfer for MemorySnapRepo {
+    async fn get_all_snaps(&self) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(self.snaps.lock().unwrap().clone())
+    }
+
+    async fn insert_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        self.insert_snap_batch(snaps).await.map(|_| ())
+    }
+
+    async fn clear_snaps(&self) -> Result<(), DataAccessError> {
+        self.snaps.lock().unwrap().clear();
+        Ok(())
+    }
+
+    async fn buffered_snaps_count(&self) -> Result<usize, DataAccessError> {
+        Ok(self.len())
+    }
+
+    async fn peek_buffered_snaps(&self, limit: usize) -> Result<Vec<Snap>, DataAccessError> {
+        Ok(self
+            .snaps
+            .lock()
+            .unwrap()
+            .iter()
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn remove_buffered_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        self.snaps.lock().unwrap().retain(|stored| {
+            !snaps
+                .iter()
+                .any(|s| s.time == stored.time && s.camera.id == stored.camera.id)
+        });
+        Ok(())
+    }
+    async fn dead_letter_snaps(&self, snaps: &[Snap]) -> Result<(), DataAccessError> {
+        self.remove_buffered_snaps(snaps).await?;
+        self.dead_letter.lock().unwrap().extend_from_slice(snaps);
+        Ok(())
+    }
+}
+
+impl VolatileSnapRepo for MemorySnapRepo {}
+
+fn datetime(snap: &Snap) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(&format!("{} {}", snap.date, snap.time), "%d.%m.%Y %H:%M")
+        .unwrap()
+}
+
+fn snap(time: &str) -> Snap {
+    Snap {
+        camera: Camera {
+            id: 1,
+            is_radar: true,
+            location: Location {
+                latitude: 12.22222,
+                longitude: 12.22222,
+            },
+            speed_limit: None,
+            install_date: None,
+            is_active: true,
+        },
+        speed: Some(70),
+        time: time.to_string(),
+        date: "10.10.2020".to_string(),
+        gos_num: "А889МН29".to_string(),
+        idempotency_key: None,
+    }
+}
+
+fn tandem(main: &MemorySnapRepo, extra: &MemorySnapRepo) -> TandemSnapRepo {
+    TandemSnapRepo::from(Box::new(main.clone()), Box::new(extra.clone())).with_threshold(3)
+}
+
+#[tokio::test]
+async fn test_tandem_keeps_snaps_below_threshold() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = tandem(&main, &extra);
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    repo.insert_snap(&snap("12:11")).await.unwrap();
+
+    assert_eq!(main.len(), 0);
+    assert_eq!(extra.len(), 2);
+}
+
+#[tokio::test]
+async fn test_tandem_insert_does_not_transfer_inline() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = tandem(&main, &extra);
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    repo.insert_snap(&snap("12:11")).await.unwrap();
+    repo.insert_snap(&snap("12:12")).await.unwrap();
+
+    assert_eq!(main.len(), 0);
+    assert_eq!(extra.len(), 3);
+}
+
+#[tokio::test]
+async fn test_tandem_worker_transfers_on_threshold() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = Arc::new(tandem(&main, &extra).with_flush_interval(Duration::from_secs(3600)));
+    let worker = TandemSnapRepo::spawn_flush_worker(repo.clone());
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    repo.insert_snap(&snap("12:11")).await.unwrap();
+    repo.insert_snap(&snap("12:12")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(main.len(), 3);
+    assert_eq!(extra.len(), 0);
+    worker.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_tandem_transfer_keeps_new_snaps() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = tandem(&main, &extra);
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    let drained = extra.peek_buffered_snaps(10).await.unwrap();
+    repo.insert_snap(&snap("12:11")).await.unwrap();
+    extra.remove_buffered_snaps(&drained).await.unwrap();
+
+    assert_eq!(extra.len(), 1);
+
+    repo.transfer().await.unwrap();
+
+    assert_eq!(main.len(), 1);
+    assert_eq!(extra.len(), 0);
+}
+
+#[tokio::test]
+async fn test_tandem_transfer_dead_letters_rejected_snaps() {
+    let (main, extra) = (
+        MemorySnapRepo::rejecting("12:11"),
+        MemorySnapRepo::default(),
+    );
+    let repo = tandem(&main, &extra);
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    repo.insert_snap(&snap("12:11")).await.unwrap();
+    repo.insert_snap(&snap("12:12")).await.unwrap();
+    repo.transfer().await.unwrap();
+
+    assert_eq!(main.len(), 2);
+    assert_eq!(extra.len(), 0);
+    let dead_letter = extra.dead_letter.lock().unwrap();
+    assert_eq!(dead_letter.len(), 1);
+    assert_eq!(dead_letter[0].time, "12:11");
+}
+
+#[tokio::test]
+async fn test_tandem_worker_flushes_on_timer() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = Arc::new(tandem(&main, &extra).with_flush_interval(Duration::from_millis(50)));
+    let worker = TandemSnapRepo::spawn_flush_worker(repo.clone());
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(main.len(), 1);
+    assert_eq!(extra.len(), 0);
+    worker.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_tandem_worker_drains_on_shutdown() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = Arc::new(tandem(&main, &extra).with_flush_interval(Duration::from_secs(3600)));
+    let worker = TandemSnapRepo::spawn_flush_worker(repo.clone());
+
+    repo.insert_snap(&snap("12:10")).await.unwrap();
+    worker.shutdown().await;
+    worker.shutdown().await;
+
+    assert_eq!(main.len(), 1);
+    assert_eq!(extra.len(), 0);
+}
+
+#[tokio::test]
+async fn test_tandem_period_merges_storages_in_time_order() {
+    let (main, extra) = (MemorySnapRepo::default(), MemorySnapRepo::default());
+    let repo = tandem(&main, &extra);
+
+    let mut late = snap("0:20");
+    late.date = "11.10.2020".to_string();
+    main.insert_snap(&late).await.unwrap();
+    main.insert_snap(&snap("9:05")).await.unwrap();
+    extra.insert_snap(&snap("23:40")).await.unwrap();
+    extra.insert_snap(&snap("9:05")).await.unwrap();
+    extra.insert_snap(&snap("8:00")).await.unwrap();
+
+    let from = NaiveDateTime::parse_from_str("10.10.2020 9:00", "%d.%m.%Y %H:%M").unwrap();
+    let to = NaiveDateTime::parse_from_str("11.10.2020 1:00", "%d.%m.%Y %H:%M").unwrap();
+    let snaps = repo
+        .get_car_snaps_by_period("А889МН29", &from, &to)
+        .await
+        .unwrap();
+
+    let times: Vec<_> = snaps.iter().map(|s| s.time.as_str()).collect();
+    assert_eq!(times, vec!["9:05", "23:40", "0:20"]);
+}
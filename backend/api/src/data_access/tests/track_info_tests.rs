@@ -5,7 +5,7 @@ use data_access::{
     },
     repositories_traits::TrackInfoRepository,
 };
-use models::Document;
+use models::{Document, Page, SortKey};
 
 #[tokio::test]
 async fn test_get_track_infos_by_gos_num_mask() {
@@ -43,6 +43,22 @@ async fn test_get_track_infos_by_fio() {
     assert!(res.is_ok())
 }
 
+#[tokio::test]
+async fn test_get_track_infos_by_fio_prefix() {
+    let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
+
+    let exact = repo
+        .get_track_info_by_user_fio(None, Some("Дроздов"), None)
+        .await
+        .unwrap();
+    let res = repo
+        .get_track_info_by_user_fio(None, Some("дрозд"), None)
+        .await
+        .unwrap();
+
+    assert!(res.len() >= exact.len());
+}
+
 #[tokio::test]
 async fn test_get_track_infos_by_date() {
     let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
@@ -58,7 +74,7 @@ async fn test_get_track_infos_by_filter1() {
     let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_tracks_info_by_filters(None, None, None, None, None, None)
+        .get_tracks_info_by_filters(None, None, None, None, None, None, &Page::default())
         .await;
 
     println!("{:#?}", res);
@@ -70,7 +86,15 @@ async fn test_get_track_infos_by_filter2() {
     let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_tracks_info_by_filters(None, None, None, None, None, Some("22.04.2025"))
+        .get_tracks_info_by_filters(
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("22.04.2025"),
+            &Page::default(),
+        )
         .await;
 
     println!("{:#?}", res);
@@ -82,7 +106,15 @@ async fn test_get_track_infos_by_filter3() {
     let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_tracks_info_by_filters(Some("Герасим"), Some("Игнатьева"), None, None, None, Some("22.04.2025"))
+        .get_tracks_info_by_filters(
+            Some("Герасим"),
+            Some("Игнатьева"),
+            None,
+            None,
+            None,
+            Some("22.04.2025"),
+            &Page::default(),
+        )
         .await;
 
     println!("{:#?}", res);
@@ -94,13 +126,38 @@ async fn test_get_track_infos_by_filter4() {
     let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
 
     let res = repo
-        .get_tracks_info_by_filters(Some("Герасим"), Some("Игнатьева"), None, None, Some("Е******"), Some("22.04.2025"))
+        .get_tracks_info_by_filters(
+            Some("Герасим"),
+            Some("Игнатьева"),
+            None,
+            None,
+            Some("Е******"),
+            Some("22.04.2025"),
+            &Page::default(),
+        )
         .await;
 
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
 
+#[tokio::test]
+async fn test_get_track_infos_by_filter_page() {
+    let repo = PgTrackInfoRepo::from(&PG_URL).await.unwrap();
+    let page = Page {
+        offset: 1,
+        limit: 5,
+        sort: SortKey::track_time,
+    };
+
+    let res = repo
+        .get_tracks_info_by_filters(None, None, None, None, None, None, &page)
+        .await
+        .unwrap();
+
+    assert!(res.items.len() <= 5);
+    assert!(res.total >= res.items.len());
+}
 
 #[tokio::test]
 async fn test_insert_track_info() {
@@ -154,6 +211,24 @@ async fn test_clickhouse_get_track_infos_by_fio() {
     assert!(res.is_ok())
 }
 
+#[tokio::test]
+async fn test_clickhouse_get_track_infos_by_fio_prefix() {
+    let repo = ClickHouseTrackInfoRepo::from(&CLICKHOUSE_URL)
+        .await
+        .unwrap();
+
+    let exact = repo
+        .get_track_info_by_user_fio(None, Some("Никонова"), None)
+        .await
+        .unwrap();
+    let res = repo
+        .get_track_info_by_user_fio(None, Some("никон"), None)
+        .await
+        .unwrap();
+
+    assert!(res.len() >= exact.len());
+}
+
 #[tokio::test]
 async fn test_clickhouse_get_track_infos_by_passport() {
     let repo = ClickHouseTrackInfoRepo::from(&CLICKHOUSE_URL)
@@ -181,3 +256,23 @@ async fn test_clickhouse_get_track_infos_by_gos_num_mask() {
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
+
+#[tokio::test]
+async fn test_clickhouse_get_track_infos_by_filter_page() {
+    let repo = ClickHouseTrackInfoRepo::from(&CLICKHOUSE_URL)
+        .await
+        .unwrap();
+    let page = Page {
+        offset: 1,
+        limit: 5,
+        sort: SortKey::track_time,
+    };
+
+    let res = repo
+        .get_tracks_info_by_filters(None, None, None, None, None, None, &page)
+        .await
+        .unwrap();
+
+    assert!(res.items.len() <= 5);
+    assert!(res.total >= res.items.len());
+}
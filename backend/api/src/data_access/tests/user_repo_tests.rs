@@ -6,12 +6,10 @@ use data_access::{
 use models::{Document, Role, User};
 
 #[tokio::test]
-async fn test_get_user_by_auth_info() {
+async fn test_get_user_with_pswd_hash() {
     let repo = PgUserRepo::from(&PG_URL).await.unwrap();
 
-    let res = repo
-        .get_user_by_auth_info("email123@example.com", "12345678")
-        .await;
+    let res = repo.get_user_with_pswd_hash("email123@example.com").await;
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
@@ -62,6 +60,33 @@ async fn test_update_user_passport_success() {
     assert_eq!(new_user.clone().passport.unwrap().number, passport.number);
 }
 
+#[tokio::test]
+async fn test_update_user_pswd_hash() {
+    let new_user = User {
+        name: "mock_name".to_string(),
+        surname: "mock_surname".to_string(),
+        lastname: None,
+        email: "pswd_hash_update@example.com".to_string(),
+        passport: None,
+        role: Role::user,
+        is_verified: false,
+    };
+    let new_hash = "$argon2id$v=19$mock";
+
+    let repo = PgUserRepo::from(&PG_URL).await.unwrap();
+    repo.insert_user(&new_user, "123456789").await.unwrap();
+
+    let res = repo.update_user_pswd_hash(&new_user.email, new_hash).await;
+
+    println!("{:#?}", res);
+    let stored = repo.get_user_with_pswd_hash(&new_user.email).await;
+    repo.delete_user(&new_user).await.unwrap();
+
+    assert!(res.is_ok());
+    let (_, stored_hash) = stored.unwrap().unwrap();
+    assert_eq!(stored_hash, new_hash);
+}
+
 #[tokio::test]
 async fn test_add_user() {
     let new_user = User {
@@ -87,12 +112,10 @@ async fn test_add_user() {
 }
 
 #[tokio::test]
-async fn test_clickhouse_get_user_by_auth_info() {
+async fn test_clickhouse_get_user_with_pswd_hash() {
     let repo = ClickHouseUserRepo::from(&CLICKHOUSE_URL).await.unwrap();
 
-    let res = repo
-        .get_user_by_auth_info("uewmleii@icloud.com", "Krd!G0RW&")
-        .await;
+    let res = repo.get_user_with_pswd_hash("uewmleii@icloud.com").await;
     println!("{:#?}", res);
     assert!(res.is_ok())
 }
@@ -1,169 +1,133 @@
+use business_logic::alert_hub::AlertHub;
 use business_logic::services::auth_service::AuthService;
 use business_logic::services::camera_data_get_service::CameraDataGetService;
+use business_logic::services::camera_manage_service::CameraManageService;
+use business_logic::services::live_track_service::LiveTrackService;
 use business_logic::services::route_service::RouteService;
 use business_logic::services::search_service::SearchService;
+use business_logic::services::session_service::SessionService;
 use business_logic::services::snap_send_service::SnapSendService;
+use business_logic::services::speed_control_service::SpeedControlService;
+use business_logic::services::watchlist_service::WatchlistService;
 use business_logic::services_traits::{
-    Authorizer, CameraDataGetter, RouteGetter, Searcher, SnapSender,
+    Authorizer, CameraDataGetter, CameraManager, LiveTracker, RouteGetter, Searcher,
+    SessionManager, SnapSender, SpeedController, WatchlistManager,
 };
+use business_logic::snap_hub::SnapHub;
+use std::sync::Arc;
 
+pub use business_logic::alert_hub;
 pub use business_logic::error;
+pub use business_logic::snap_hub;
+
+// Секрет подписи токенов из окружения, чтобы не хранить его в config.cfg
+const JWT_SECRET_ENV: &str = "JWT_SECRET";
 
 mod repo_connect;
-pub use repo_connect::{DARepos, DATA_ACCESSES};
+pub use repo_connect::{DataAccesses, DATA_ACCESSES};
 
-pub enum BLServices {
-    RouteGetService(Box<dyn RouteGetter>),
-    AuthService(Box<dyn Authorizer>),
-    SnapSendService(Box<dyn SnapSender>),
-    SearchService(Box<dyn Searcher>),
-    CameraDataGetService(Box<dyn CameraDataGetter>),
+#[derive(Clone)]
+pub struct BusinessServices {
+    pub route_getter: Arc<dyn RouteGetter>,
+    pub auther: Arc<dyn Authorizer>,
+    pub searcher: Arc<dyn Searcher>,
+    pub snap_sender: Arc<dyn SnapSender>,
+    pub camera_data_getter: Arc<dyn CameraDataGetter>,
+    pub camera_manager: Arc<dyn CameraManager>,
+    pub session_manager: Arc<dyn SessionManager>,
+    pub live_tracker: Arc<dyn LiveTracker>,
+    pub watchlist: Arc<dyn WatchlistManager>,
+    pub speed_controller: Arc<dyn SpeedController>,
+    snap_hub: Arc<SnapHub>,
+    alert_hub: Arc<AlertHub>,
 }
 
-#[allow(non_camel_case_types)]
-pub struct BUSINESS_SERVICES;
-
-impl BUSINESS_SERVICES {
-    pub async fn get(name: &str) -> Option<BLServices> {
-        match name {
-            "route_getter" => {
-                let snap_repo = match DATA_ACCESSES::get("snap_repo").await {
-                    Some(DARepos::SnapRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get SnapRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted SnapRepository");
-
-                let track_info_repo = match DATA_ACCESSES::get("track_info_repo").await {
-                    Some(DARepos::TrackInfoRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get TrackInfoRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted TrackInfoRepository");
-
-                let user_repo = match DATA_ACCESSES::get("user_repo").await {
-                    Some(DARepos::UserRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get UserRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted UserRepository");
-
-                log::info!("Sending RouteGetter");
-                Some(BLServices::RouteGetService(Box::new(RouteService::from(
-                    user_repo,
-                    snap_repo,
-                    track_info_repo,
-                ))))
-            }
-            "auther" => {
-                let user_repo = match DATA_ACCESSES::get("user_repo").await {
-                    Some(DARepos::UserRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get UserRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted UserRepository");
+impl BusinessServices {
+    pub fn from(repos: &DataAccesses) -> Self {
+        let access_ttl = cfg::var("auth.access_token_ttl")
+            .parse::<u64>()
+            .expect("auth.access_token_ttl must be a number of seconds");
+        let refresh_ttl = cfg::var("auth.refresh_token_ttl")
+            .parse::<u64>()
+            .expect("auth.refresh_token_ttl must be a number of seconds");
+        let trip_gap = cfg::var("route.trip_gap_minutes")
+            .parse::<u32>()
+            .expect("route.trip_gap_minutes must be a number of minutes");
+        let speed_tolerance = cfg::var("speed.violation_tolerance")
+            .parse::<u16>()
+            .expect("speed.violation_tolerance must be a number of km/h");
+        let jwt_secret =
+            std::env::var(JWT_SECRET_ENV).unwrap_or_else(|_| cfg::var("auth.jwt_secret"));
+        let snap_hub = Arc::new(SnapHub::new());
+        let alert_hub = Arc::new(AlertHub::new());
+        let watchlist: Arc<dyn WatchlistManager> = Arc::new(WatchlistService::from(
+            repos.watchlist_repo.clone(),
+            alert_hub.clone(),
+        ));
+        let speed_controller: Arc<dyn SpeedController> = Arc::new(
+            SpeedControlService::from(
+                repos.camera_repo.clone(),
+                repos.speed_violation_repo.clone(),
+                repos.road_section_repo.clone(),
+                repos.snap_repo.clone(),
+            )
+            .with_tolerance(speed_tolerance),
+        );
 
-                log::info!("Sending Auther");
-                Some(BLServices::AuthService(Box::new(AuthService::from(
-                    user_repo,
-                ))))
-            }
-            "searcher" => {
-                let car_repo = match DATA_ACCESSES::get("car_repo").await {
-                    Some(DARepos::CarRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get CarRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted CarRepository");
-
-                let track_info_repo = match DATA_ACCESSES::get("track_info_repo").await {
-                    Some(DARepos::TrackInfoRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get TrackInfoRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted TrackInfoRepository");
+        BusinessServices {
+            route_getter: Arc::new(
+                RouteService::from(
+                    repos.user_repo.clone(),
+                    repos.snap_repo.clone(),
+                    repos.track_info_repo.clone(),
+                )
+                .with_trip_gap_minutes(trip_gap),
+            ),
+            auther: Arc::new(AuthService::from(repos.user_repo.clone())),
+            searcher: Arc::new(SearchService::from(
+                repos.car_repo.clone(),
+                repos.track_info_repo.clone(),
+            )),
+            snap_sender: Arc::new(
+                SnapSendService::from(repos.snap_repo.clone(), repos.camera_repo.clone())
+                    .with_hub(snap_hub.clone())
+                    .with_watchlist(watchlist.clone())
+                    .with_speed_controller(speed_controller.clone()),
+            ),
+            camera_data_getter: Arc::new(CameraDataGetService::from(repos.camera_repo.clone())),
+            camera_manager: Arc::new(CameraManageService::from(repos.camera_repo.clone())),
+            session_manager: Arc::new(
+                SessionService::from(
+                    repos.user_repo.clone(),
+                    repos.session_repo.clone(),
+                    &jwt_secret,
+                )
+                .expect("JWT_SECRET or auth.jwt_secret must be a non-default secret of at least 32 bytes")
+                .with_access_ttl(access_ttl)
+                .with_refresh_ttl(refresh_ttl),
+            ),
+            live_tracker: Arc::new(LiveTrackService::from(
+                repos.user_repo.clone(),
+                repos.track_info_repo.clone(),
+                snap_hub.clone(),
+            )),
+            watchlist,
+            speed_controller,
+            snap_hub,
+            alert_hub,
+        }
+    }
 
-                log::info!("Sending Searcher");
-                Some(BLServices::SearchService(Box::new(SearchService::from(
-                    car_repo,
-                    track_info_repo,
-                ))))
-            }
-            "snap_sender" => {
-                let snap_repo = match DATA_ACCESSES::get("snap_repo").await {
-                    Some(DARepos::SnapRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get SnapRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted SnapRepository");
+    // Завершает потоки отслеживания и фиксаций розыска при остановке сервера
+    pub fn close_live_streams(&self) {
+        self.snap_hub.close();
+        self.alert_hub.close();
+    }
 
-                log::info!("Sending SnapSender");
-                Some(BLServices::SnapSendService(Box::new(
-                    SnapSendService::from(snap_repo),
-                )))
-            }
-            "camera_data_getter" => {
-                let camera_repo = match DATA_ACCESSES::get("camera_repo").await {
-                    Some(DARepos::CameraRepo(repo)) => repo,
-                    Some(_) => {
-                        log::error!("Getted incorrect repository");
-                        panic!("Getted incorrect repository");
-                    }
-                    None => {
-                        log::error!("Can't get CameraRepository");
-                        return None;
-                    }
-                };
-                log::info!("Successfull getted CameraRepository");
+    pub async fn build() -> Option<Self> {
+        let repos = DATA_ACCESSES::shared().await?;
 
-                log::info!("Sending CameraDataGetter");
-                Some(BLServices::CameraDataGetService(Box::new(
-                    CameraDataGetService::from(camera_repo),
-                )))
-            }
-            _ => None,
-        }
+        log::info!("Business services created");
+        Some(BusinessServices::from(&repos))
     }
 }
@@ -1,4 +1,8 @@
-use lazy_static::lazy_static;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use data_access::error::DataAccessError;
 
 #[allow(unused_imports)]
 use data_access::repositories::clickhouse::*;
@@ -13,62 +17,6 @@ use data_access::repositories::tandem::*;
 #[allow(unused_imports)]
 use data_access::repositories_traits::*;
 
-#[allow(unused_macros)]
-macro_rules! connect_repository {
-    ($reconnect_manager:expr, $repo_type:ty, $from_fn:path, $url:expr) => {
-        match $from_fn($url).await {
-            Ok(repo) => Ok(repo),
-            Err(_) => match $reconnect_manager.reconnect().await {
-                Ok(_) => $from_fn($url).await,
-                Err(e) => Err(e),
-            },
-        }
-    };
-}
-
-macro_rules! select_repository {
-    (
-        $db:expr, 
-        $postgres_repo_type:ty, 
-        $clickhouse_repo_type:ty, 
-        $output_enum:path
-    ) => {
-        match $db {
-            AvailableDB::Postgres => {
-                let repo = match <$postgres_repo_type>::from(&PG_URL).await {
-                    Ok(repo) => repo,
-                    Err(e) => {
-                        log::error!("Can't connect to postgres: {}", e);
-                        return None;
-                    }
-                };
-                log::debug!("Getted {} repo", stringify!($postgres_repo_type));
-                $output_enum(Box::new(repo))
-            }
-            AvailableDB::ClickHouse => {
-                let repo = match <$clickhouse_repo_type>::from(&CLICKHOUSE_URL).await {
-                    Ok(repo) => repo,
-                    Err(e) => {
-                        log::error!("Can't connect to clickhouse: {}", e);
-                        return None;
-                    }
-                };
-                log::debug!("Getted {} repo", stringify!($clickhouse_repo_type));
-                $output_enum(Box::new(repo))
-            }
-        }
-    };
-}
-
-const MAX_RETRIES: usize = 5;
-const RETRY_DELAY_SECS: usize = 5;
-const TANDEM_THRESHOLD: usize = 10;
-
-lazy_static! {
-    static ref PG_CONN_MANAGER: PgConnectionManager =
-        PgConnectionManager::new(PG_URL.to_string(), MAX_RETRIES, RETRY_DELAY_SECS);
-}
-
 #[derive(PartialEq)]
 enum AvailableDB {
     Postgres,
@@ -85,104 +33,128 @@ impl AvailableDB {
     }
 }
 
-pub enum DARepos {
-    UserRepo(Box<dyn UserRepository>),
-    SnapRepo(Box<dyn SnapRepository>),
-    CameraRepo(Box<dyn CameraRepository>),
-    CarRepo(Box<dyn CarRepository>),
-    TrackInfoRepo(Box<dyn TrackInfoRepository>),
+#[derive(Clone)]
+pub struct DataAccesses {
+    pub user_repo: Arc<dyn UserRepository>,
+    pub snap_repo: Arc<dyn SnapRepository>,
+    pub camera_repo: Arc<dyn CameraRepository>,
+    pub car_repo: Arc<dyn CarRepository>,
+    pub track_info_repo: Arc<dyn TrackInfoRepository>,
+    pub session_repo: Arc<dyn SessionRepository>,
+    pub watchlist_repo: Arc<dyn WatchlistRepository>,
+    pub speed_violation_repo: Arc<dyn SpeedViolationRepository>,
+    pub road_section_repo: Arc<dyn RoadSectionRepository>,
+    snap_flush_worker: Option<Arc<TandemFlushWorker>>,
 }
 
-#[allow(non_camel_case_types)]
-pub struct DATA_ACCESSES;
-
-impl DATA_ACCESSES {
-    pub async fn get(name: &str) -> Option<DARepos> {
+impl DataAccesses {
+    pub async fn connect() -> Result<Self, DataAccessError> {
         let db = AvailableDB::from_str(cfg::var("vars.main_db"))
             .expect("Incorrect DB var. Avalible DB: postgres, clickhouse");
 
-        match name {
-            "snap_repo" => {
-                let snap_repo = match db {
-                    AvailableDB::Postgres => {
-                        let psql_snap_repo = match PgSnapRepo::from(&PG_URL).await {
-                            Ok(repo) => repo,
-                            Err(e) => {
-                                log::error!("Can't connect to redis: {}", e);
-                                return None;
-                            }
-                        };
-                        log::debug!("Getted PgSnapRepo");
-
-                        let redis_snap_repo = match RedisSnapRepo::from(&REDIS_URL) {
-                            Ok(repo) => repo,
-                            Err(e) => {
-                                log::error!("Can't connect to redis: {}", e);
-                                return None;
-                            }
-                        };
-                        log::debug!("Getted RedisSnapRepo");
-
-                        let tandem_snap_repo = TandemSnapRepo::from(
-                            Box::new(psql_snap_repo),
-                            Box::new(redis_snap_repo),
-                        )
-                        .with_threshold(TANDEM_THRESHOLD);
-                        log::debug!(
-                            "Formed TandemSnapRepo where:
-                                - main_storage: PgSnapRepo
-                                - extra_storage: RedisSnapRepo
-                        "
-                        );
-
-                        DARepos::SnapRepo(Box::new(tandem_snap_repo))
-                    }
-                    AvailableDB::ClickHouse => {
-                        select_repository!(
-                            AvailableDB::ClickHouse,
-                            PgSnapRepo,
-                            ClickHouseSnapRepo,
-                            DARepos::SnapRepo
-                        )
-                    }
-                };
-
-                log::info!("Sending SnapRepository");
-                Some(snap_repo)
-            }
-            "user_repo" => {
-                let res = select_repository!(db, PgUserRepo, ClickHouseUserRepo, DARepos::UserRepo);
+        let session_repo = RedisSessionRepo::from(&REDIS_URL)?;
+        log::debug!("Getted RedisSessionRepo");
 
-                log::info!("Sending UserRepository");
-                Some(res)
-            }
-            "camera_repo" => {
-                let res =
-                    select_repository!(db, PgCameraRepo, ClickHouseCameraRepo, DARepos::CameraRepo);
+        let max_connections = cfg::var("database.postgres_max_connections")
+            .parse::<u32>()
+            .expect("database.postgres_max_connections must be a number");
 
-                log::info!("Sending CameraRepository");
-                Some(res)
-            }
-            "car_repo" => {
-                let res = select_repository!(db, PgCarRepo, ClickHouseCarRepo, DARepos::CarRepo);
+        match db {
+            AvailableDB::Postgres => {
+                let pool = create_pg_pool(&PG_URL, max_connections).await?;
+                let buffer_threshold = cfg::var("cache.snap_buffer_threshold")
+                    .parse::<usize>()
+                    .expect("cache.snap_buffer_threshold must be a number");
+                let flush_interval = cfg::var("cache.snap_flush_interval")
+                    .parse::<u64>()
+                    .expect("cache.snap_flush_interval must be a number of seconds");
+
+                let tandem_snap_repo = Arc::new(
+                    TandemSnapRepo::from(
+                        Box::new(PgSnapRepo::from_pool(pool.clone())),
+                        Box::new(RedisSnapRepo::from(&REDIS_URL)?),
+                    )
+                    .with_threshold(buffer_threshold)
+                    .with_flush_interval(Duration::from_secs(flush_interval)),
+                );
+                let snap_flush_worker =
+                    TandemSnapRepo::spawn_flush_worker(tandem_snap_repo.clone());
+                log::debug!(
+                    "Formed TandemSnapRepo where:
+                        - main_storage: PgSnapRepo
+                        - extra_storage: RedisSnapRepo
+                "
+                );
 
-                log::info!("Sending CarRepository");
-                Some(res)
+                log::info!("Postgres repositories created on shared pool");
+                Ok(DataAccesses {
+                    user_repo: Arc::new(PgUserRepo::from_pool(pool.clone())),
+                    snap_repo: tandem_snap_repo,
+                    camera_repo: Arc::new(PgCameraRepo::from_pool(pool.clone())),
+                    car_repo: Arc::new(PgCarRepo::from_pool(pool.clone())),
+                    track_info_repo: Arc::new(PgTrackInfoRepo::from_pool(pool.clone())),
+                    session_repo: Arc::new(session_repo),
+                    watchlist_repo: Arc::new(PgWatchlistRepo::from_pool(pool.clone())),
+                    speed_violation_repo: Arc::new(PgSpeedViolationRepo::from_pool(pool.clone())),
+                    road_section_repo: Arc::new(PgRoadSectionRepo::from_pool(pool)),
+                    snap_flush_worker: Some(Arc::new(snap_flush_worker)),
+                })
             }
-            "track_info_repo" => {
-                let res = select_repository!(
-                    db,
-                    PgTrackInfoRepo,
-                    ClickHouseTrackInfoRepo,
-                    DARepos::TrackInfoRepo
+            AvailableDB::ClickHouse => {
+                let client = create_clickhouse_client(&CLICKHOUSE_URL);
+                // Список розыска, нарушения и участки контроля хранятся только в PostgreSQL,
+                // поэтому в режиме ClickHouse database.postgres_url тоже должен быть доступен
+                let pg_pool = create_lazy_pg_pool(&PG_URL, max_connections)?;
+                log::warn!(
+                    "ClickHouse mode: watchlist, speed violations and road sections are stored in \
+                     PostgreSQL (database.postgres_url); these endpoints fail until it is reachable. \
+                     Camera administration updates ClickHouse only"
                 );
 
-                log::info!("Sending TrackInfoRepository");
-                Some(res)
+                log::info!("ClickHouse repositories created on shared client");
+                Ok(DataAccesses {
+                    user_repo: Arc::new(ClickHouseUserRepo::from_client(client.clone())),
+                    snap_repo: Arc::new(ClickHouseSnapRepo::from_client(client.clone())),
+                    camera_repo: Arc::new(ClickHouseCameraRepo::from_client(client.clone())),
+                    car_repo: Arc::new(ClickHouseCarRepo::from_client(client.clone())),
+                    track_info_repo: Arc::new(ClickHouseTrackInfoRepo::from_client(client)),
+                    session_repo: Arc::new(session_repo),
+                    watchlist_repo: Arc::new(PgWatchlistRepo::from_pool(pg_pool.clone())),
+                    speed_violation_repo: Arc::new(PgSpeedViolationRepo::from_pool(
+                        pg_pool.clone(),
+                    )),
+                    road_section_repo: Arc::new(PgRoadSectionRepo::from_pool(pg_pool)),
+                    snap_flush_worker: None,
+                })
             }
-            _ => {
-                log::error!("Incorrect data access key");
-                panic!("Incorrect data acces key");
+        }
+    }
+
+    // Переносит буфер снимков в основное хранилище и останавливает фоновые задачи
+    pub async fn shutdown(&self) {
+        if let Some(worker) = &self.snap_flush_worker {
+            worker.shutdown().await;
+        }
+        log::info!("Data accesses shut down");
+    }
+}
+
+static SHARED_DATA_ACCESSES: OnceCell<DataAccesses> = OnceCell::const_new();
+
+#[allow(non_camel_case_types)]
+pub struct DATA_ACCESSES;
+
+impl DATA_ACCESSES {
+    // Репозитории создаются один раз на процесс и переиспользуют общий пул соединений
+    pub async fn shared() -> Option<DataAccesses> {
+        match SHARED_DATA_ACCESSES
+            .get_or_try_init(DataAccesses::connect)
+            .await
+        {
+            Ok(repos) => Some(repos.clone()),
+            Err(e) => {
+                log::error!("Can't create repositories: {}", e);
+                None
             }
         }
     }
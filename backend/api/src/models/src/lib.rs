@@ -22,12 +22,51 @@ pub struct Car {
     pub pts: Document,
 }
 
-#[allow(non_camel_case_types)]
+// Регистрационная карточка автомобиля по СТС и ПТС
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct DetailedCar {
+    pub car: Car,
+    // Текущий пробег (км)
+    #[schema(example = 84000)]
+    pub mileage: u32,
+    #[schema(example = 106)]
+    pub horse_power: Option<u32>,
+    // Масса (кг)
+    #[schema(example = 1280)]
+    pub car_weight: Option<u32>,
+    #[schema(example = "petrol")]
+    pub engine_type: String,
+    #[schema(example = "B")]
+    pub car_class: String,
+    #[schema(example = "15.03.2015")]
+    pub release_date: String,
+    #[schema(example = "21.06.2019")]
+    pub reg_date: String,
+    #[schema(example = "Германия")]
+    pub import_country: String,
+}
+
+// Период владения автомобилем по ПТС
 #[derive(Serialize, Deserialize, Debug, ToSchema, Clone)]
+pub struct OwnershipRecord {
+    pub owner_fio: (String, String, Option<String>),
+    #[schema(example = "01.01.2020")]
+    pub reg_date: String,
+    // Отсутствует у текущего владельца
+    #[schema(example = "01.01.2023")]
+    pub dereg_date: Option<String>,
+    // Пробег (км) на момент регистрации
+    #[schema(example = 45000)]
+    pub mileage: u32,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Debug, ToSchema, Clone, PartialEq)]
 pub enum Role {
     user,
     operator,
     audit,
+    camera,
 }
 
 #[derive(Serialize, Deserialize, Debug, ToSchema, Clone)]
@@ -51,6 +90,16 @@ pub struct User {
     pub passport: Option<Document>,
 }
 
+#[derive(Serialize, Deserialize, Debug, ToSchema, Clone)]
+pub struct Session {
+    #[schema(example = "eyJ0eXAiOiJKV1QiLCJhbGciOiJIUzI1NiJ9...")]
+    pub access_token: String,
+    #[schema(example = "3f9c1e0b7a4d4c2e9b1f8a6d5e2c7b0a3f9c1e0b7a4d4c2e9b1f8a6d5e2c7b0a")]
+    pub refresh_token: String,
+    #[schema(example = 900)]
+    pub expires_in: u64,
+}
+
 #[derive(Debug, ToSchema, Clone, Copy, Serialize, Deserialize)]
 pub struct Location {
     #[schema(example = 53.9222)]
@@ -69,6 +118,103 @@ pub struct PointData {
     pub cords: Location,
 }
 
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct RoutePoint {
+    #[schema(example = "01.01.2025")]
+    pub date: String,
+    #[schema(example = "8:10")]
+    pub time: String,
+    #[schema(example = 1)]
+    pub camera_id: usize,
+    #[schema(example = true)]
+    pub is_radar: bool,
+    #[schema(example = 60)]
+    pub speed: Option<u16>,
+    pub cords: Location,
+}
+
+// Участок между соседними точками маршрута
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct RouteSegment {
+    #[schema(example = 1)]
+    pub from_camera_id: usize,
+    #[schema(example = 2)]
+    pub to_camera_id: usize,
+    #[schema(example = 12.4)]
+    pub distance_km: f64,
+    #[schema(example = 900)]
+    pub duration_secs: i64,
+    // Средняя скорость (км/ч) по расстоянию и времени, нет при нулевом времени
+    #[schema(example = 49.6)]
+    pub avg_speed: Option<f64>,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct RouteStats {
+    #[schema(example = 24.8)]
+    pub distance_km: f64,
+    #[schema(example = 1800)]
+    pub duration_secs: i64,
+    // Максимальная скорость, зафиксированная радарами
+    #[schema(example = 70)]
+    pub max_speed: Option<u16>,
+    // Средняя скорость (км/ч) по расстоянию и времени всего маршрута
+    #[schema(example = 49.6)]
+    pub avg_speed: Option<f64>,
+    #[schema(example = 2)]
+    pub cameras_count: usize,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct DetailedRoute {
+    pub points: Vec<RoutePoint>,
+    pub segments: Vec<RouteSegment>,
+    pub stats: RouteStats,
+}
+
+// Поездка между стоянками: отдельная линия маршрута
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct Trip {
+    #[schema(example = 1)]
+    pub start_camera_id: usize,
+    #[schema(example = 2)]
+    pub end_camera_id: usize,
+    #[schema(example = "01.01.2025")]
+    pub start_date: String,
+    #[schema(example = "8:10")]
+    pub start_time: String,
+    #[schema(example = "01.01.2025")]
+    pub end_date: String,
+    #[schema(example = "8:47")]
+    pub end_time: String,
+    pub points: Vec<RoutePoint>,
+    pub stats: RouteStats,
+}
+
+// Стоянка у камеры последней фиксации поездки до начала следующей
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct RouteStop {
+    #[schema(example = 2)]
+    pub camera_id: usize,
+    pub cords: Location,
+    #[schema(example = "01.01.2025")]
+    pub arrival_date: String,
+    #[schema(example = "8:47")]
+    pub arrival_time: String,
+    #[schema(example = "01.01.2025")]
+    pub departure_date: String,
+    #[schema(example = "17:30")]
+    pub departure_time: String,
+    #[schema(example = 31380)]
+    pub dwell_secs: i64,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone)]
+pub struct RouteTrips {
+    pub trips: Vec<Trip>,
+    pub stops: Vec<RouteStop>,
+}
+
 #[derive(Debug, ToSchema, Serialize, Deserialize, Clone)]
 pub struct Snap {
     pub camera: Camera,
@@ -76,9 +222,25 @@ pub struct Snap {
     pub speed: Option<u16>,
     pub date: String,
     pub gos_num: String,
+    // Ключ идемпотентности от камеры, уникален в пределах одной камеры
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
-#[derive(Debug, ToSchema, Clone, Copy, Serialize, Deserialize)]
+// Снимок от камеры до сверки с реестром камер
+#[derive(Debug, Clone)]
+pub struct IncomingSnap {
+    pub camera_id: usize,
+    // Данные камеры, переданные вместе со снимком
+    pub reported_camera: Option<Camera>,
+    pub time: String,
+    pub speed: Option<u16>,
+    pub date: String,
+    pub gos_num: String,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize, Deserialize)]
 pub struct Camera {
     #[schema(example = 1)]
     pub id: usize,
@@ -88,6 +250,95 @@ pub struct Camera {
         { "longitude": 54.98989 , "latitude": 56.89882 }]
     ))]
     pub location: Location,
+    // Ограничение скорости (км/ч) на участке камеры
+    #[serde(default)]
+    #[schema(example = 60)]
+    pub speed_limit: Option<u16>,
+    #[serde(default)]
+    #[schema(example = "04.07.2021")]
+    pub install_date: Option<String>,
+    // Выведенная из эксплуатации камера не принимает снимки
+    #[serde(default = "default_is_active")]
+    #[schema(example = true)]
+    pub is_active: bool,
+}
+
+fn default_is_active() -> bool {
+    true
+}
+
+// Камера с расстоянием до точки запроса
+#[derive(Debug, ToSchema, Clone, Serialize, Deserialize)]
+pub struct NearbyCamera {
+    pub camera: Camera,
+    // Расстояние (м)
+    #[schema(example = 350.5)]
+    pub distance: f64,
+}
+
+// Прямоугольная область карты по юго-западному и северо-восточному углам
+#[derive(Debug, ToSchema, Clone, Copy, Serialize, Deserialize)]
+pub struct BoundingBox {
+    #[schema(example = json!({ "longitude": 37.55, "latitude": 55.70 }))]
+    pub south_west: Location,
+    #[schema(example = json!({ "longitude": 37.70, "latitude": 55.80 }))]
+    pub north_east: Location,
+}
+
+// Фильтр поиска автомобилей, незаданные поля не ограничивают выборку
+#[derive(Debug, Clone, Default)]
+pub struct CarFilter {
+    pub firstname: Option<String>,
+    pub surname: Option<String>,
+    pub lastname: Option<String>,
+    pub passport: Option<Document>,
+    pub gos_num_mask: Option<String>,
+    // Полный VIN (17 символов) или его начало
+    pub vin: Option<String>,
+    // Марка, модель и цвет сравниваются без учета регистра
+    pub mark: Option<String>,
+    pub model: Option<String>,
+    pub color: Option<String>,
+    // Границы года выпуска включаются в выборку
+    pub year_from: Option<u16>,
+    pub year_to: Option<u16>,
+    pub engine_type: Option<String>,
+    pub car_class: Option<String>,
+}
+
+// Ключ сортировки результатов поиска, track_time - сначала новые отслеживания
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Debug, ToSchema, Clone, Copy, PartialEq, Default)]
+pub enum SortKey {
+    #[default]
+    gos_num,
+    owner_surname,
+    track_time,
+}
+
+// Страница результатов поиска
+#[derive(Debug, Clone, Copy)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+    pub sort: SortKey,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Page {
+            offset: 0,
+            limit: 20,
+            sort: SortKey::default(),
+        }
+    }
+}
+
+// Страница результатов вместе с общим числом найденных записей
+#[derive(Debug)]
+pub struct Paged<T> {
+    pub items: Vec<T>,
+    pub total: usize,
 }
 
 #[derive(Debug, ToSchema, Serialize, Deserialize)]
@@ -97,3 +348,92 @@ pub struct TrackInfo {
     pub car: Car,
     pub user: User,
 }
+
+// Запись списка розыска: номер или маска номера
+#[derive(Debug, ToSchema, Serialize, Deserialize, Clone)]
+pub struct WatchlistEntry {
+    #[schema(example = 1)]
+    pub id: usize,
+    #[schema(example = "А***МР77")]
+    pub gos_num_mask: String,
+    #[schema(example = "Угон")]
+    pub reason: String,
+    // Без срока действия запись активна до удаления
+    #[schema(example = "31.12.2025 23:59")]
+    pub expires_at: Option<String>,
+    #[schema(example = "operator@example.ru")]
+    pub created_by: String,
+}
+
+// Фиксация разыскиваемого автомобиля камерой
+#[derive(Debug, ToSchema, Serialize, Deserialize, Clone)]
+pub struct WatchlistAlert {
+    #[schema(example = 1)]
+    pub id: usize,
+    #[schema(example = 1)]
+    pub entry_id: usize,
+    #[schema(example = "Угон")]
+    pub reason: String,
+    #[schema(example = "А777МР77")]
+    pub gos_num: String,
+    #[schema(example = 1)]
+    pub camera_id: usize,
+    pub cords: Location,
+    #[schema(example = "01.01.2025")]
+    pub date: String,
+    #[schema(example = "8:10")]
+    pub time: String,
+}
+
+// Степень превышения скорости сверх ограничения (не сверх допуска):
+// до 40, до 60, до 80 и более 80 км/ч
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Debug, ToSchema, Clone, Copy, PartialEq)]
+pub enum ViolationSeverity {
+    low,
+    medium,
+    high,
+    critical,
+}
+
+#[derive(Debug, ToSchema, Serialize, Deserialize, Clone)]
+pub struct SpeedViolation {
+    #[schema(example = 1)]
+    pub id: usize,
+    #[schema(example = "А777МР77")]
+    pub gos_num: String,
+    #[schema(example = 1)]
+    pub camera_id: usize,
+    pub cords: Location,
+    #[schema(example = "01.01.2025")]
+    pub date: String,
+    #[schema(example = "8:10")]
+    pub time: String,
+    #[schema(example = 95)]
+    pub speed: u16,
+    // Ограничение камеры или участка на момент фиксации
+    #[schema(example = 60)]
+    pub speed_limit: u16,
+    #[schema(example = "low")]
+    pub severity: ViolationSeverity,
+    // Участок контроля средней скорости, для мгновенной скорости отсутствует.
+    // Нарушение на участке фиксируется камерой конца участка
+    #[schema(example = json!(null))]
+    pub section_id: Option<usize>,
+}
+
+// Участок контроля средней скорости между двумя камерами по направлению движения
+#[derive(Debug, ToSchema, Serialize, Deserialize, Clone)]
+pub struct RoadSection {
+    #[schema(example = 1)]
+    pub id: usize,
+    #[schema(example = 1)]
+    pub start_camera_id: usize,
+    #[schema(example = 2)]
+    pub end_camera_id: usize,
+    // Длина участка по дороге (м)
+    #[schema(example = 10000)]
+    pub distance: u32,
+    #[schema(example = 60)]
+    pub speed_limit: u16,
+}
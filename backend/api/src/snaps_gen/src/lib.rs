@@ -3,15 +3,12 @@ use data_access::{
     models::{Camera, Snap},
     repositories_traits::{CameraRepository, CarRepository},
 };
-use di_container::{DARepos, DATA_ACCESSES};
+use di_container::DATA_ACCESSES;
 use rand::{Rng, seq::IndexedRandom};
 
 const MAX_SPEED: u16 = 120;
 
-async fn get_rand_camera<Rand: Rng>(
-    rng: &mut Rand,
-    repo: Box<dyn CameraRepository>,
-) -> Option<Camera> {
+async fn get_rand_camera<Rand: Rng>(rng: &mut Rand, repo: &dyn CameraRepository) -> Option<Camera> {
     let cnt = repo.get_camera_count().await.unwrap_or(0);
     if cnt == 0 {
         log::warn!("No camera in database");
@@ -28,7 +25,7 @@ async fn get_rand_camera<Rand: Rng>(
 
 async fn get_rand_gos_num_from_db<Rand: Rng>(
     rng: &mut Rand,
-    repo: Box<dyn CarRepository>,
+    repo: &dyn CarRepository,
 ) -> Option<String> {
     let cars = match repo.get_car_by_gos_number_mask("*******").await {
         Ok(cars) => cars,
@@ -43,17 +40,11 @@ async fn get_rand_gos_num_from_db<Rand: Rng>(
 }
 
 pub async fn gen_snap<Rand: Rng>(rng: &mut Rand) -> Option<Snap> {
-    let car_repo = match DATA_ACCESSES::get("car_repo").await {
-        Some(DARepos::CarRepo(repo)) => repo,
-        _ => return None,
-    };
-
-    let camera_repo = match DATA_ACCESSES::get("camera_repo").await {
-        Some(DARepos::CameraRepo(repo)) => repo,
-        _ => return None,
-    };
+    let repos = DATA_ACCESSES::shared().await?;
+    let car_repo = repos.car_repo;
+    let camera_repo = repos.camera_repo;
 
-    let camera = match get_rand_camera(rng, camera_repo).await {
+    let camera = match get_rand_camera(rng, &*camera_repo).await {
         Some(c) => c,
         _ => {
             log::error!("Can't get random camera");
@@ -61,7 +52,7 @@ pub async fn gen_snap<Rand: Rng>(rng: &mut Rand) -> Option<Snap> {
         }
     };
 
-    let gos_num = match get_rand_gos_num_from_db(rng, car_repo).await {
+    let gos_num = match get_rand_gos_num_from_db(rng, &*car_repo).await {
         Some(gn) => gn,
         _ => {
             log::error!("Can't get random gos_num");
@@ -86,6 +77,7 @@ pub async fn gen_snap<Rand: Rng>(rng: &mut Rand) -> Option<Snap> {
         time,
         date,
         gos_num,
+        idempotency_key: None,
     };
     log::info!("Generated snap: {:#?}", snap);
     Some(snap)
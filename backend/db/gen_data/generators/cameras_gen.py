@@ -3,6 +3,7 @@ import pandas as pd
 from datetime import datetime, timedelta
 
 CAMS_DATA_FILE_PATH = './gen_data/generators/help_data/cams_data.xlsx'
+SPEED_LIMITS = [40, 60, 60, 80, 90]
 
 def gen_rand_cameras(n: int) -> pd.DataFrame:
     def extract_random_cams_coords(n):
@@ -20,7 +21,8 @@ def gen_rand_cameras(n: int) -> pd.DataFrame:
         'longitude': lon,
         'latitude': lat,
         'install_date': [],
-        'is_radar': []
+        'is_radar': [],
+        'speed_limit': []
     }
 
     for _ in range(n):
@@ -32,6 +34,7 @@ def gen_rand_cameras(n: int) -> pd.DataFrame:
         
         data['install_date'].append(random_date.strftime('%Y-%m-%d'))
         data['is_radar'].append(is_radar)
+        data['speed_limit'].append(random.choice(SPEED_LIMITS))
 
     df = pd.DataFrame(data)
     return df
@@ -20,7 +20,8 @@ CAR_MODELS = {
 
 def gen_rand_cars(n: int, owner_df: pd.DataFrame) -> pd.DataFrame:
     def generate_vin():
-        chars = string.ascii_uppercase + string.digits
+        # VIN не содержит букв I, O и Q
+        chars = ''.join(c for c in string.ascii_uppercase if c not in 'IOQ') + string.digits
         return ''.join(random.choices(chars, k=17))
     
     data = {
@@ -10,6 +10,7 @@ DATA_DIR="/data"
 function load_csv() {
   local table=$1
   local file=$2
+  local columns=$3
 
   echo "Загружаем $file в таблицу $table..."
 
@@ -19,13 +20,13 @@ function load_csv() {
     --user "$CLICKHOUSE_USER" \
     --password "$CLICKHOUSE_PASSWORD" \
     --database "$CLICKHOUSE_DB" \
-    --query="INSERT INTO $table FORMAT CSV"
+    --query="INSERT INTO $table $columns FORMAT CSV"
 }
 
-load_csv Camera       "$DATA_DIR/cameras.csv"
+load_csv Camera       "$DATA_DIR/cameras.csv" "(id, longitude, latitude, install_date, is_radar, speed_limit)"
 load_csv CarOwner     "$DATA_DIR/owners.csv"
 load_csv Car          "$DATA_DIR/cars.csv"
-load_csv CarSnapshot  "$DATA_DIR/snaps.csv"
+load_csv CarSnapshot  "$DATA_DIR/snaps.csv" "(id, camera_id, snap_datetime, speed, gos_num, road_line)"
 load_csv STS          "$DATA_DIR/stss.csv"
 load_csv PTS          "$DATA_DIR/ptss.csv"
 load_csv AppUser      "$DATA_DIR/users.csv"
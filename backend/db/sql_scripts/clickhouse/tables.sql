@@ -61,7 +61,7 @@ CREATE TABLE OwnerHistory (
     pts_id UInt32,
     mileage Int32,
     reg_date Date,
-    dereg_date Date
+    dereg_date Nullable(Date)
 ) ENGINE = MergeTree()
 ORDER BY id;
 
@@ -87,7 +87,10 @@ CREATE TABLE STS (
     engine_type String,
     car_class String,
     release_date Date,
-    reg_date Date
+    reg_date Date,
+    INDEX sts_vin_idx vin TYPE ngrambf_v1(4, 1024, 2, 0) GRANULARITY 1,
+    INDEX sts_mark_model_idx (lower(mark), lower(model)) TYPE bloom_filter GRANULARITY 1,
+    INDEX sts_release_date_idx release_date TYPE minmax GRANULARITY 1
 ) ENGINE = MergeTree()
 ORDER BY id;
 
@@ -96,7 +99,10 @@ CREATE TABLE Camera (
     longitude Float64,
     latitude Float64,
     install_date Date,
-    is_radar UInt8
+    is_radar UInt8,
+    speed_limit Nullable(UInt16),
+    is_active UInt8 DEFAULT 1,
+    INDEX camera_location_idx (latitude, longitude) TYPE minmax GRANULARITY 1
 ) ENGINE = MergeTree()
 ORDER BY id;
 
@@ -106,6 +112,23 @@ CREATE TABLE CarSnapshot (
     snap_datetime DateTime,
     speed Nullable(Int32),
     gos_num String,
-    road_line Int32 DEFAULT 0
-) ENGINE = MergeTree()
-ORDER BY id;
+    road_line Int32 DEFAULT 0,
+    idempotency_key Nullable(String)
+) ENGINE = ReplacingMergeTree()
+ORDER BY (camera_id, gos_num, snap_datetime);
+
+-- Имя в нижнем регистре, ё заменяется на е
+CREATE FUNCTION normalize_name AS (name) -> replaceAll(lowerUTF8(name), 'ё', 'е');
+
+-- Оценка совпадения имени с образцом: 0 - полное совпадение, 1 - совпадение по началу,
+-- 1 + расстояние Левенштейна - нечеткое совпадение, NULL - имя не подходит.
+-- Пустому образцу не подходит ни одно имя, короткие сравниваются только точно и по началу
+CREATE FUNCTION name_match_score AS (name, pattern) -> multiIf(
+    normalize_name(pattern) = '', NULL,
+    normalize_name(name) = normalize_name(pattern), 0,
+    startsWith(normalize_name(name), normalize_name(pattern)), 1,
+    editDistanceUTF8(normalize_name(name), normalize_name(pattern))
+        <= least(2, intDiv(lengthUTF8(pattern), 4)),
+    1 + editDistanceUTF8(normalize_name(name), normalize_name(pattern)),
+    NULL
+);
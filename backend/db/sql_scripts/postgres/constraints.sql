@@ -1,6 +1,6 @@
 ALTER TABLE AppUser
     ADD CONSTRAINT unique_passport UNIQUE (passport_serial, passport_num),
-    ADD CONSTRAINT check_role CHECK (role IN ('user', 'operator', 'audit')),
+    ADD CONSTRAINT check_role CHECK (role IN ('user', 'operator', 'audit', 'camera')),
     ADD CONSTRAINT unique_login UNIQUE (login),
     ADD CONSTRAINT check_login_email CHECK (login ~ '^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$'),
     ADD CONSTRAINT check_passport_format CHECK (passport_serial BETWEEN 1 AND 9999 AND passport_num BETWEEN 1 AND 999999);
@@ -46,14 +46,17 @@ ALTER TABLE OwnerHistory
     ADD CONSTRAINT check_dates CHECK (dereg_date IS NULL OR dereg_date >= reg_date);
 
 ALTER TABLE Camera
-    ADD CONSTRAINT check_install_date CHECK (install_date <= CURRENT_DATE);
+    ADD CONSTRAINT check_install_date CHECK (install_date <= CURRENT_DATE),
+    ADD CONSTRAINT check_speed_limit CHECK (speed_limit > 0);
 
 ALTER TABLE CarSnapshot
     ADD FOREIGN KEY (camera_id) REFERENCES Camera(id) ON DELETE CASCADE,
     ADD CONSTRAINT check_speed CHECK (speed >= 0),
     ADD CONSTRAINT check_road_line CHECK (road_line >= 0),
     ADD CONSTRAINT check_gos_num_format CHECK (gos_num ~ '^[АВЕКМНОРСТУХ]\d{3}[АВЕКМНОРСТУХ]{2}\d{2,3}$'),
-    ADD CONSTRAINT check_snapshot_date CHECK (snap_datetime <= (NOW() AT TIME ZONE 'Europe/Moscow' + INTERVAL '5 seconds'));
+    ADD CONSTRAINT check_snapshot_date CHECK (snap_datetime <= (NOW() AT TIME ZONE 'Europe/Moscow' + INTERVAL '5 seconds')),
+    ADD CONSTRAINT unique_snap UNIQUE (camera_id, gos_num, snap_datetime),
+    ADD CONSTRAINT unique_snap_idempotency_key UNIQUE (camera_id, idempotency_key);
 
 ALTER TABLE TrackInfo
     ADD FOREIGN KEY (user_id) REFERENCES AppUser(id) ON DELETE CASCADE,
@@ -62,4 +65,31 @@ ALTER TABLE TrackInfo
 
 ALTER TABLE OwnerHistoryOwner
     ADD FOREIGN KEY (owner_id) REFERENCES CarOwner(id) ON DELETE CASCADE,
-    ADD FOREIGN KEY (owner_history_id) REFERENCES OwnerHistory(id) ON DELETE CASCADE;
\ No newline at end of file
+    ADD FOREIGN KEY (owner_history_id) REFERENCES OwnerHistory(id) ON DELETE CASCADE;
+
+ALTER TABLE Watchlist
+    ADD FOREIGN KEY (created_by) REFERENCES AppUser(id) ON DELETE CASCADE,
+    ADD CONSTRAINT check_gos_num_mask_format CHECK (gos_num_mask ~ '^[АВЕКМНОРСТУХ*][\d*]{3}[АВЕКМНОРСТУХ*]{2}(\d{2,3}|\*)$'),
+    ADD CONSTRAINT check_reason CHECK (LENGTH(reason) > 0);
+
+ALTER TABLE WatchlistAlert
+    ADD FOREIGN KEY (watchlist_id) REFERENCES Watchlist(id) ON DELETE CASCADE,
+    ADD FOREIGN KEY (camera_id) REFERENCES Camera(id) ON DELETE CASCADE,
+    ADD CONSTRAINT check_gos_num_format CHECK (gos_num ~ '^[АВЕКМНОРСТУХ]\d{3}[АВЕКМНОРСТУХ]{2}\d{2,3}$'),
+    ADD CONSTRAINT unique_watchlist_alert UNIQUE (watchlist_id, camera_id, gos_num, snap_datetime);
+
+ALTER TABLE RoadSection
+    ADD FOREIGN KEY (start_camera_id) REFERENCES Camera(id) ON DELETE CASCADE,
+    ADD FOREIGN KEY (end_camera_id) REFERENCES Camera(id) ON DELETE CASCADE,
+    ADD CONSTRAINT check_section_cameras CHECK (start_camera_id <> end_camera_id),
+    ADD CONSTRAINT check_distance CHECK (distance > 0),
+    ADD CONSTRAINT check_speed_limit CHECK (speed_limit > 0),
+    ADD CONSTRAINT unique_road_section UNIQUE (start_camera_id, end_camera_id);
+
+ALTER TABLE SpeedViolation
+    ADD FOREIGN KEY (camera_id) REFERENCES Camera(id) ON DELETE CASCADE,
+    ADD FOREIGN KEY (section_id) REFERENCES RoadSection(id) ON DELETE CASCADE,
+    ADD CONSTRAINT check_speed CHECK (speed > speed_limit),
+    ADD CONSTRAINT check_severity CHECK (severity IN ('low', 'medium', 'high', 'critical')),
+    ADD CONSTRAINT check_gos_num_format CHECK (gos_num ~ '^[АВЕКМНОРСТУХ]\d{3}[АВЕКМНОРСТУХ]{2}\d{2,3}$'),
+    ADD CONSTRAINT unique_speed_violation UNIQUE NULLS NOT DISTINCT (camera_id, gos_num, snap_datetime, section_id);
\ No newline at end of file
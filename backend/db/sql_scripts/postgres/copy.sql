@@ -1,7 +1,8 @@
-COPY Camera FROM '/data/cameras.csv' DELIMITER ',' CSV HEADER;
+COPY Camera (id, longitude, latitude, install_date, is_radar, speed_limit) FROM '/data/cameras.csv' DELIMITER ',' CSV HEADER;
+SELECT setval('camera_id_seq', (SELECT MAX(id) FROM Camera));
 COPY CarOwner FROM '/data/owners.csv' DELIMITER ',' CSV HEADER;
 COPY Car FROM '/data/cars.csv' DELIMITER ',' CSV HEADER;
-COPY CarSnapshot FROM '/data/snaps.csv' DELIMITER ',' CSV HEADER;
+COPY CarSnapshot (id, camera_id, snap_datetime, speed, gos_num, road_line) FROM '/data/snaps.csv' DELIMITER ',' CSV HEADER;
 COPY STS FROM '/data/stss.csv' DELIMITER ',' CSV HEADER;
 COPY PTS FROM '/data/ptss.csv' DELIMITER ',' CSV HEADER;
 COPY AppUser FROM '/data/users.csv' DELIMITER ',' CSV HEADER;
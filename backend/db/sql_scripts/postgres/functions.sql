@@ -16,6 +16,67 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE EXTENSION IF NOT EXISTS fuzzystrmatch;
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Имя в нижнем регистре, ё заменяется на е
+CREATE OR REPLACE FUNCTION normalize_name(p_name TEXT)
+RETURNS TEXT AS $$
+    SELECT translate(lower(p_name), 'ё', 'е');
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Оценка совпадения имени с образцом: 0 - полное совпадение, 1 - совпадение по началу,
+-- 1 + расстояние Левенштейна - нечеткое совпадение, NULL - имя не подходит.
+-- Пустому образцу не подходит ни одно имя
+CREATE OR REPLACE FUNCTION name_match_score(p_name TEXT, p_pattern TEXT)
+RETURNS INT AS $$
+DECLARE
+    v_name TEXT := normalize_name(p_name);
+    v_pattern TEXT := normalize_name(p_pattern);
+    v_distance INT;
+BEGIN
+    IF v_pattern = '' THEN
+        RETURN NULL;
+    END IF;
+
+    IF v_name = v_pattern THEN
+        RETURN 0;
+    END IF;
+
+    IF starts_with(v_name, v_pattern) THEN
+        RETURN 1;
+    END IF;
+
+    -- Короткие образцы сравниваются только точно и по началу
+    v_distance := levenshtein(v_name, v_pattern);
+    IF v_distance <= LEAST(2, length(v_pattern) / 4) THEN
+        RETURN 1 + v_distance;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+-- Отбор кандидатов для name_match_score по триграммному индексу на normalize_name:
+-- совпадение по началу или триграммное сходство не ниже pg_trgm.similarity_threshold.
+-- Функция встраивается в запрос, поэтому условие использует индекс
+CREATE OR REPLACE FUNCTION name_candidate(p_name TEXT, p_pattern TEXT)
+RETURNS BOOLEAN AS $$
+    SELECT normalize_name(p_pattern) <> ''
+        AND (normalize_name(p_name) LIKE replace(replace(replace(
+                normalize_name(p_pattern), '\', '\\'), '%', '\%'), '_', '\_') || '%'
+            OR normalize_name(p_name) % normalize_name(p_pattern));
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE INDEX IF NOT EXISTS carowner_name_trgm_idx ON CarOwner USING GIN (normalize_name(name) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS carowner_surname_trgm_idx ON CarOwner USING GIN (normalize_name(surname) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS carowner_lastname_trgm_idx ON CarOwner USING GIN (normalize_name(lastname) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS appuser_name_trgm_idx ON AppUser USING GIN (normalize_name(name) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS appuser_surname_trgm_idx ON AppUser USING GIN (normalize_name(surname) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS appuser_lastname_trgm_idx ON AppUser USING GIN (normalize_name(lastname) gin_trgm_ops);
+
+DROP FUNCTION IF EXISTS get_tracks_info(TEXT, TEXT, TEXT, DATE, TEXT, INT, INT);
+
 CREATE OR REPLACE FUNCTION get_tracks_info(
     p_firstname TEXT DEFAULT NULL,
     p_surname TEXT DEFAULT NULL,
@@ -23,7 +84,10 @@ CREATE OR REPLACE FUNCTION get_tracks_info(
     p_track_date DATE DEFAULT NULL,
     p_gos_num_mask TEXT DEFAULT NULL,
     p_passport_serial INT DEFAULT NULL,
-    p_passport_number INT DEFAULT NULL
+    p_passport_number INT DEFAULT NULL,
+    p_sort TEXT DEFAULT 'gos_num',
+    p_limit INT DEFAULT NULL,
+    p_offset INT DEFAULT 0
 )
 RETURNS TABLE (
     route_date DATE,
@@ -48,13 +112,27 @@ RETURNS TABLE (
     sts_serial INT,
     sts_num INT,
     pts_serial INT,
-    pts_number INT
+    pts_number INT,
+    total_count BIGINT
 ) 
 LANGUAGE plpgsql AS $$
 DECLARE
     query_text TEXT;
     where_clause TEXT := '';
+    -- Найденные по ФИО записи ранжируются по сумме оценок совпадения
+    score_clause TEXT := '';
+    order_clause TEXT;
 BEGIN
+    order_clause := CASE p_sort
+        WHEN 'gos_num' THEN 's.gos_num, t.id'
+        WHEN 'owner_surname' THEN 'o.surname, o.name, t.id'
+        WHEN 'track_time' THEN 't.track_time DESC, t.id'
+    END;
+
+    IF order_clause IS NULL THEN
+        RAISE EXCEPTION 'Unknown sort key: %', p_sort;
+    END IF;
+
     query_text := '
         SELECT 
             t.route_date, 
@@ -79,7 +157,8 @@ BEGIN
             s.sts_serial, 
             s.sts_num, 
             p.pts_serial, 
-            p.pts_number
+            p.pts_number,
+            COUNT(*) OVER () AS total_count
         FROM 
             CarOwner o
             JOIN Car c ON o.id = c.owner_id
@@ -89,15 +168,18 @@ BEGIN
             JOIN PTS p ON c.id = p.id';
 
     IF p_firstname IS NOT NULL THEN
-        where_clause := where_clause || ' AND a.name = ' || quote_literal(p_firstname);
+        where_clause := where_clause || ' AND name_candidate(a.name, ' || quote_literal(p_firstname) || ')';
+        score_clause := score_clause || ' + name_match_score(a.name, ' || quote_literal(p_firstname) || ')';
     END IF;
 
     IF p_surname IS NOT NULL THEN
-        where_clause := where_clause || ' AND a.surname = ' || quote_literal(p_surname);
+        where_clause := where_clause || ' AND name_candidate(a.surname, ' || quote_literal(p_surname) || ')';
+        score_clause := score_clause || ' + name_match_score(a.surname, ' || quote_literal(p_surname) || ')';
     END IF;
 
     IF p_lastname IS NOT NULL THEN
-        where_clause := where_clause || ' AND a.lastname = ' || quote_literal(p_lastname);
+        where_clause := where_clause || ' AND name_candidate(a.lastname, ' || quote_literal(p_lastname) || ')';
+        score_clause := score_clause || ' + name_match_score(a.lastname, ' || quote_literal(p_lastname) || ')';
     END IF;
 
     IF p_track_date IS NOT NULL THEN
@@ -116,21 +198,50 @@ BEGIN
         where_clause := where_clause || ' AND a.passport_num = ' || quote_literal(p_passport_number);
     END IF;
 
+    -- Оценка считается один раз для кандидатов, отобранных по индексу;
+    -- OFFSET 0 не дает планировщику подставить ее в условие и сортировку повторно.
+    -- Сумма оценок NULL, если хотя бы одно имя не подходит
+    IF score_clause != '' THEN
+        query_text := query_text || ' CROSS JOIN LATERAL (SELECT 0' || score_clause || ' OFFSET 0) AS ns(name_score)';
+        where_clause := where_clause || ' AND ns.name_score IS NOT NULL';
+        order_clause := 'ns.name_score, ' || order_clause;
+    END IF;
+
     IF where_clause != '' THEN
         query_text := query_text || ' WHERE 1=1' || where_clause;
     END IF;
 
+    query_text := query_text || ' ORDER BY ' || order_clause || ' OFFSET ' || p_offset;
+
+    IF p_limit IS NOT NULL THEN
+        query_text := query_text || ' LIMIT ' || p_limit;
+    END IF;
+
     RETURN QUERY EXECUTE query_text;
 END;
 $$;
 
+DROP FUNCTION IF EXISTS get_cars(TEXT, TEXT, TEXT, TEXT, INT, INT);
+DROP FUNCTION IF EXISTS get_cars(TEXT, TEXT, TEXT, TEXT, INT, INT, TEXT, INT, INT);
+
 CREATE OR REPLACE FUNCTION get_cars(
     p_firstname TEXT DEFAULT NULL,
     p_surname TEXT DEFAULT NULL,
     p_lastname TEXT DEFAULT NULL,
     p_gos_num_mask TEXT DEFAULT NULL,
     p_passport_serial INT DEFAULT NULL,
-    p_passport_number INT DEFAULT NULL
+    p_passport_number INT DEFAULT NULL,
+    p_vin TEXT DEFAULT NULL,
+    p_mark TEXT DEFAULT NULL,
+    p_model TEXT DEFAULT NULL,
+    p_color TEXT DEFAULT NULL,
+    p_year_from INT DEFAULT NULL,
+    p_year_to INT DEFAULT NULL,
+    p_engine_type TEXT DEFAULT NULL,
+    p_car_class TEXT DEFAULT NULL,
+    p_sort TEXT DEFAULT 'gos_num',
+    p_limit INT DEFAULT NULL,
+    p_offset INT DEFAULT 0
 )
 RETURNS TABLE (
     name TEXT,
@@ -145,13 +256,26 @@ RETURNS TABLE (
     sts_serial INT,
     sts_num INT,
     pts_serial INT,
-    pts_number INT
+    pts_number INT,
+    total_count BIGINT
 ) 
 LANGUAGE plpgsql AS $$
 DECLARE
     query_text TEXT;
     where_clause TEXT := '';
+    -- Найденные по ФИО записи ранжируются по сумме оценок совпадения
+    score_clause TEXT := '';
+    order_clause TEXT;
 BEGIN
+    order_clause := CASE p_sort
+        WHEN 'gos_num' THEN 's.gos_num, s.vin'
+        WHEN 'owner_surname' THEN 'o.surname, o.name, s.gos_num, s.vin'
+    END;
+
+    IF order_clause IS NULL THEN
+        RAISE EXCEPTION 'Unknown sort key: %', p_sort;
+    END IF;
+
     query_text := '
         SELECT 
             o.name,
@@ -166,22 +290,26 @@ BEGIN
             s.sts_serial,
             s.sts_num,
             p.pts_serial,
-            p.pts_number 
+            p.pts_number,
+            COUNT(*) OVER () AS total_count
         FROM CarOwner o
             JOIN Car c ON o.id = c.owner_id
             JOIN STS s ON c.id = s.car_id
             JOIN PTS p ON c.id = p.id';
 
     IF p_firstname IS NOT NULL THEN
-        where_clause := where_clause || ' AND o.name = ' || quote_literal(p_firstname);
+        where_clause := where_clause || ' AND name_candidate(o.name, ' || quote_literal(p_firstname) || ')';
+        score_clause := score_clause || ' + name_match_score(o.name, ' || quote_literal(p_firstname) || ')';
     END IF;
 
     IF p_surname IS NOT NULL THEN
-        where_clause := where_clause || ' AND o.surname = ' || quote_literal(p_surname);
+        where_clause := where_clause || ' AND name_candidate(o.surname, ' || quote_literal(p_surname) || ')';
+        score_clause := score_clause || ' + name_match_score(o.surname, ' || quote_literal(p_surname) || ')';
     END IF;
 
     IF p_lastname IS NOT NULL THEN
-        where_clause := where_clause || ' AND o.lastname = ' || quote_literal(p_lastname);
+        where_clause := where_clause || ' AND name_candidate(o.lastname, ' || quote_literal(p_lastname) || ')';
+        score_clause := score_clause || ' + name_match_score(o.lastname, ' || quote_literal(p_lastname) || ')';
     END IF;
 
     IF p_gos_num_mask IS NOT NULL THEN
@@ -196,10 +324,60 @@ BEGIN
         where_clause := where_clause || ' AND o.passport_num = ' || quote_literal(p_passport_number);
     END IF;
 
+    -- VIN короче 17 символов ищется по началу
+    IF p_vin IS NOT NULL AND length(p_vin) = 17 THEN
+        where_clause := where_clause || ' AND s.vin = ' || quote_literal(p_vin);
+    ELSIF p_vin IS NOT NULL THEN
+        where_clause := where_clause || ' AND s.vin LIKE ' || quote_literal(p_vin || '%');
+    END IF;
+
+    IF p_mark IS NOT NULL THEN
+        where_clause := where_clause || ' AND lower(s.mark) = lower(' || quote_literal(p_mark) || ')';
+    END IF;
+
+    IF p_model IS NOT NULL THEN
+        where_clause := where_clause || ' AND lower(s.model) = lower(' || quote_literal(p_model) || ')';
+    END IF;
+
+    IF p_color IS NOT NULL THEN
+        where_clause := where_clause || ' AND lower(c.color) = lower(' || quote_literal(p_color) || ')';
+    END IF;
+
+    IF p_year_from IS NOT NULL THEN
+        where_clause := where_clause || ' AND s.release_date >= ' || quote_literal(make_date(p_year_from, 1, 1));
+    END IF;
+
+    IF p_year_to IS NOT NULL THEN
+        where_clause := where_clause || ' AND s.release_date < ' || quote_literal(make_date(p_year_to + 1, 1, 1));
+    END IF;
+
+    IF p_engine_type IS NOT NULL THEN
+        where_clause := where_clause || ' AND s.engine_type = ' || quote_literal(p_engine_type);
+    END IF;
+
+    IF p_car_class IS NOT NULL THEN
+        where_clause := where_clause || ' AND s.car_class = ' || quote_literal(p_car_class);
+    END IF;
+
+    -- Оценка считается один раз для кандидатов, отобранных по индексу;
+    -- OFFSET 0 не дает планировщику подставить ее в условие и сортировку повторно.
+    -- Сумма оценок NULL, если хотя бы одно имя не подходит
+    IF score_clause != '' THEN
+        query_text := query_text || ' CROSS JOIN LATERAL (SELECT 0' || score_clause || ' OFFSET 0) AS ns(name_score)';
+        where_clause := where_clause || ' AND ns.name_score IS NOT NULL';
+        order_clause := 'ns.name_score, ' || order_clause;
+    END IF;
+
     IF where_clause != '' THEN
         query_text := query_text || ' WHERE 1=1' || where_clause;
     END IF;
 
+    query_text := query_text || ' ORDER BY ' || order_clause || ' OFFSET ' || p_offset;
+
+    IF p_limit IS NOT NULL THEN
+        query_text := query_text || ' LIMIT ' || p_limit;
+    END IF;
+
     RETURN QUERY EXECUTE query_text;
 END;
 $$;
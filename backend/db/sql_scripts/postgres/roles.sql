@@ -81,6 +81,15 @@ GRANT SELECT ON
     STS
 TO operator_role;
 
+GRANT SELECT, INSERT, UPDATE, DELETE ON Watchlist TO operator_role;
+GRANT SELECT, INSERT ON WatchlistAlert TO operator_role;
+GRANT USAGE ON SEQUENCE watchlist_id_seq, watchlistalert_id_seq TO operator_role;
+GRANT SELECT ON SpeedViolation TO operator_role;
+GRANT SELECT, INSERT, DELETE ON RoadSection TO operator_role;
+GRANT USAGE ON SEQUENCE roadsection_id_seq TO operator_role;
+GRANT INSERT, UPDATE ON Camera TO operator_role;
+GRANT USAGE ON SEQUENCE camera_id_seq TO operator_role;
+
 CREATE POLICY operator_car_access ON Car
 FOR SELECT TO operator_role
 USING (true);
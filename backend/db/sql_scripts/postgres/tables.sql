@@ -82,19 +82,64 @@ CREATE TABLE STS (
     reg_date DATE NOT NULL
 );
 
+CREATE INDEX sts_vin_idx ON STS (vin text_pattern_ops);
+CREATE INDEX sts_mark_model_idx ON STS (lower(mark), lower(model));
+CREATE INDEX sts_release_date_idx ON STS (release_date);
+
 CREATE TABLE Camera (
     id SERIAL PRIMARY KEY,
     longitude DOUBLE PRECISION NOT NULL,
     latitude DOUBLE PRECISION NOT NULL,
     install_date DATE NOT NULL,
-    is_radar BOOLEAN DEFAULT FALSE
+    is_radar BOOLEAN DEFAULT FALSE,
+    speed_limit INTEGER,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE
 );
 
+CREATE INDEX camera_location_idx ON Camera (latitude, longitude);
+
 CREATE TABLE CarSnapshot (
     id SERIAL PRIMARY KEY,
     camera_id INTEGER NOT NULL,
     snap_datetime TIMESTAMP NOT NULL,
     speed INTEGER,
     gos_num TEXT NOT NULL,
-    road_line INTEGER
-);
\ No newline at end of file
+    road_line INTEGER,
+    idempotency_key TEXT
+);
+CREATE TABLE Watchlist (
+    id SERIAL PRIMARY KEY,
+    gos_num_mask TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    expires_at TIMESTAMP,
+    created_by INTEGER NOT NULL
+);
+
+CREATE TABLE WatchlistAlert (
+    id SERIAL PRIMARY KEY,
+    watchlist_id INTEGER NOT NULL,
+    camera_id INTEGER NOT NULL,
+    gos_num TEXT NOT NULL,
+    snap_datetime TIMESTAMP NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL
+);
+
+CREATE TABLE RoadSection (
+    id SERIAL PRIMARY KEY,
+    start_camera_id INTEGER NOT NULL,
+    end_camera_id INTEGER NOT NULL,
+    distance INTEGER NOT NULL,
+    speed_limit INTEGER NOT NULL
+);
+
+CREATE TABLE SpeedViolation (
+    id SERIAL PRIMARY KEY,
+    camera_id INTEGER NOT NULL,
+    gos_num TEXT NOT NULL,
+    snap_datetime TIMESTAMP NOT NULL,
+    speed INTEGER NOT NULL,
+    speed_limit INTEGER NOT NULL,
+    severity TEXT NOT NULL,
+    section_id INTEGER
+);